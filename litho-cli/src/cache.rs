@@ -0,0 +1,382 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs::{create_dir_all, read_to_string, write};
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+use litho_compiler::{Compiler, Consumer, Dependency, Producer};
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::Node;
+use litho_language::lex::{SourceId, SourceMap, Span};
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+
+const FILENAME: &str = "litho-cache.json";
+
+/// Persistent cache that stores the product, dependencies and diagnostics of
+/// each definition in a file (or imported schema), keyed on the hash of its
+/// contents.
+#[derive(Default, Deserialize, Serialize)]
+pub struct CompileCache {
+    fingerprint: String,
+    paths: Vec<String>,
+    files: HashMap<String, u64>,
+    imports: HashMap<String, u64>,
+    entries: HashMap<u64, Vec<Entry>>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct Entry {
+    start: usize,
+    end: usize,
+    product: Option<CachedDependency>,
+    consumes: Vec<CachedDependency>,
+    diagnostics: Vec<Diagnostic<CachedSpan>>,
+}
+
+/// Span that refers to either the file of the entry that contains it (if
+/// `source` is `None`) or to one of the paths of the cache.
+#[derive(Clone, Copy, Deserialize, Serialize)]
+struct CachedSpan {
+    source: Option<usize>,
+    start: usize,
+    end: usize,
+}
+
+#[derive(Deserialize, Serialize)]
+enum CachedDependency {
+    Schema,
+    Type(String),
+    Directive(String),
+    Fragment(String),
+}
+
+impl From<Dependency<SmolStr>> for CachedDependency {
+    fn from(dependency: Dependency<SmolStr>) -> Self {
+        match dependency {
+            Dependency::Schema => CachedDependency::Schema,
+            Dependency::Type(name) => CachedDependency::Type(name.to_string()),
+            Dependency::Directive(name) => CachedDependency::Directive(name.to_string()),
+            Dependency::Fragment(name) => CachedDependency::Fragment(name.to_string()),
+        }
+    }
+}
+
+impl From<&CachedDependency> for Dependency<SmolStr> {
+    fn from(dependency: &CachedDependency) -> Self {
+        match dependency {
+            CachedDependency::Schema => Dependency::Schema,
+            CachedDependency::Type(name) => Dependency::Type(name.into()),
+            CachedDependency::Directive(name) => Dependency::Directive(name.into()),
+            CachedDependency::Fragment(name) => Dependency::Fragment(name.into()),
+        }
+    }
+}
+
+fn hash(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl CompileCache {
+    /// Loads the cache from the given directory. The cache is discarded if it
+    /// was written with a different fingerprint (i.e. by a different version
+    /// or with different options).
+    pub fn load(directory: &Path, fingerprint: String) -> CompileCache {
+        read_to_string(directory.join(FILENAME))
+            .ok()
+            .and_then(|text| serde_json::from_str::<CompileCache>(&text).ok())
+            .filter(|cache| cache.fingerprint == fingerprint)
+            .unwrap_or_else(|| CompileCache {
+                fingerprint,
+                ..Default::default()
+            })
+    }
+
+    pub fn save(&self, directory: &Path) -> std::io::Result<()> {
+        create_dir_all(directory)?;
+        write(directory.join(FILENAME), serde_json::to_string(self)?)
+    }
+
+    /// Restores the diagnostics of the definitions in unchanged files and
+    /// invalidates the dependents of definitions that were added, changed or
+    /// removed since the cache was written.
+    pub fn restore<'a, I>(
+        &self,
+        compiler: &mut Compiler<SmolStr>,
+        source_map: &SourceMap<String>,
+        files: I,
+    ) where
+        I: IntoIterator<Item = (SourceId, &'a String, &'a String)>,
+    {
+        let mut changed = vec![];
+        let mut paths = HashSet::new();
+
+        for (source_id, path, text) in files {
+            paths.insert(path);
+
+            let previous = self.files.get(path).copied();
+            let entries = previous.and_then(|hash| self.entries.get(&hash));
+            let Some(document) = compiler.document(source_id).cloned() else {
+                continue;
+            };
+
+            if previous == Some(hash(text)) {
+                if let Some(entries) = entries {
+                    for (definition, entry) in document.definitions.iter().zip(entries) {
+                        let span = definition.span();
+
+                        if (span.start, span.end) != (entry.start, entry.end) {
+                            continue;
+                        }
+
+                        if let Some(diagnostics) = self.diagnostics(entry, source_id, source_map) {
+                            compiler.restore_definition_diagnostics(definition.id(), diagnostics);
+                        }
+                    }
+
+                    continue;
+                }
+            }
+
+            changed.extend(entries.into_iter().flatten().map(Entry::dependencies));
+            changed.extend(
+                document
+                    .definitions
+                    .iter()
+                    .map(|definition| (definition.product(), definition.consumes())),
+            );
+        }
+
+        for (path, hash) in self.files.iter() {
+            if !paths.contains(path) {
+                changed.extend(
+                    self.entries
+                        .get(hash)
+                        .into_iter()
+                        .flatten()
+                        .map(Entry::dependencies),
+                );
+            }
+        }
+
+        for (product, consumes) in changed {
+            compiler.invalidate_dependents(product, consumes);
+        }
+    }
+
+    /// Invalidates the dependents of the definitions of imported schemas that
+    /// were added, changed or removed since the cache was written. Imports are
+    /// only resolved after the first build, so this must be called after
+    /// adding the imported schemas to the compiler.
+    pub fn restore_imports<'a, I>(&self, compiler: &mut Compiler<SmolStr>, imports: I)
+    where
+        I: IntoIterator<Item = (SourceId, &'a String, &'a String)>,
+    {
+        let mut changed = vec![];
+        let mut urls = HashSet::new();
+
+        for (source_id, url, text) in imports {
+            urls.insert(url);
+
+            let previous = self.imports.get(url).copied();
+
+            if previous == Some(hash(text)) {
+                continue;
+            }
+
+            changed.extend(
+                previous
+                    .and_then(|hash| self.entries.get(&hash))
+                    .into_iter()
+                    .flatten()
+                    .map(Entry::dependencies),
+            );
+            changed.extend(
+                compiler
+                    .document(source_id)
+                    .into_iter()
+                    .flat_map(|document| document.definitions.iter())
+                    .map(|definition| (definition.product(), definition.consumes())),
+            );
+        }
+
+        for (url, hash) in self.imports.iter() {
+            if !urls.contains(url) {
+                changed.extend(
+                    self.entries
+                        .get(hash)
+                        .into_iter()
+                        .flatten()
+                        .map(Entry::dependencies),
+                );
+            }
+        }
+
+        for (product, consumes) in changed {
+            compiler.invalidate_dependents(product, consumes);
+        }
+    }
+
+    /// Stores the products, dependencies and diagnostics of the definitions
+    /// in the given files and imported schemas. Files with diagnostics of
+    /// custom rules are not stored, because those can't be serialized.
+    pub fn store<'a, I, J>(
+        &mut self,
+        compiler: &Compiler<SmolStr>,
+        source_map: &SourceMap<String>,
+        files: I,
+        imports: J,
+    ) where
+        I: IntoIterator<Item = (SourceId, &'a String, &'a String)>,
+        J: IntoIterator<Item = (SourceId, &'a String, &'a String)>,
+    {
+        let mut cache = CompileCache {
+            fingerprint: self.fingerprint.clone(),
+            ..Default::default()
+        };
+
+        // Imports are stored before files, so that a file with the same
+        // contents as an import keeps its diagnostics.
+        for (source_id, url, text) in imports {
+            let Some(document) = compiler.document(source_id) else {
+                continue;
+            };
+
+            let entries = document
+                .definitions
+                .iter()
+                .map(|definition| {
+                    let span = definition.span();
+
+                    Entry {
+                        start: span.start,
+                        end: span.end,
+                        product: definition.product().map(Into::into),
+                        consumes: definition.consumes().into_iter().map(Into::into).collect(),
+                        diagnostics: vec![],
+                    }
+                })
+                .collect();
+
+            let hash = hash(text);
+            cache.imports.insert(url.to_owned(), hash);
+            cache.entries.insert(hash, entries);
+        }
+
+        'files: for (source_id, path, text) in files {
+            let Some(document) = compiler.document(source_id) else {
+                continue;
+            };
+
+            let mut entries = vec![];
+
+            for definition in document.definitions.iter() {
+                let diagnostics = compiler
+                    .definition_diagnostics(definition.id())
+                    .unwrap_or_default();
+
+                if diagnostics.iter().any(Diagnostic::is_custom) {
+                    continue 'files;
+                }
+
+                let span = definition.span();
+
+                entries.push(Entry {
+                    start: span.start,
+                    end: span.end,
+                    product: definition.product().map(Into::into),
+                    consumes: definition.consumes().into_iter().map(Into::into).collect(),
+                    diagnostics: diagnostics
+                        .iter()
+                        .map(|diagnostic| {
+                            diagnostic.map(|span| cache.span(span, source_id, source_map))
+                        })
+                        .collect(),
+                });
+            }
+
+            let hash = hash(text);
+            cache.files.insert(path.to_owned(), hash);
+            cache.entries.insert(hash, entries);
+        }
+
+        *self = cache;
+    }
+
+    fn span(
+        &mut self,
+        span: Span,
+        source_id: SourceId,
+        source_map: &SourceMap<String>,
+    ) -> CachedSpan {
+        let source = match span.source_id == source_id {
+            true => None,
+            false => source_map.get_id(&span.source_id).map(|path| {
+                match self.paths.iter().position(|other| other == path) {
+                    Some(index) => index,
+                    None => {
+                        self.paths.push(path.to_owned());
+                        self.paths.len() - 1
+                    }
+                }
+            }),
+        };
+
+        CachedSpan {
+            source,
+            start: span.start,
+            end: span.end,
+        }
+    }
+
+    fn diagnostics(
+        &self,
+        entry: &Entry,
+        source_id: SourceId,
+        source_map: &SourceMap<String>,
+    ) -> Option<Vec<Diagnostic<Span>>> {
+        let mut missing = false;
+
+        let diagnostics = entry
+            .diagnostics
+            .iter()
+            .map(|diagnostic| {
+                diagnostic.map(|span| {
+                    let source_id = match span.source {
+                        Some(index) => self
+                            .paths
+                            .get(index)
+                            .and_then(|path| source_map.get(path))
+                            .unwrap_or_else(|| {
+                                missing = true;
+                                source_id
+                            }),
+                        None => source_id,
+                    };
+
+                    Span {
+                        source_id,
+                        start: span.start,
+                        end: span.end,
+                    }
+                })
+            })
+            .collect();
+
+        match missing {
+            true => None,
+            false => Some(diagnostics),
+        }
+    }
+}
+
+impl Entry {
+    fn dependencies(&self) -> (Option<Dependency<SmolStr>>, Vec<Dependency<SmolStr>>) {
+        (
+            self.product.as_ref().map(Into::into),
+            self.consumes.iter().map(Into::into).collect(),
+        )
+    }
+}
@@ -1,16 +1,26 @@
 use std::env::args;
 use std::process::ExitCode;
+use std::sync::Arc;
+
+use litho_compiler::Rule;
+use smol_str::SmolStr;
 
 use super::commands;
 
 pub fn cli() -> ExitCode {
+    cli_with_rules(vec![])
+}
+
+/// Runs the CLI with additional custom rules that are checked on every input.
+pub fn cli_with_rules(rules: Vec<Arc<dyn Rule<SmolStr>>>) -> ExitCode {
     let command = args().skip(1).next();
 
     match command.as_deref() {
         Some("generate") => {}
+        Some("find") => return commands::find(),
         Some("--version" | "-v" | "version") => return commands::version(),
         _ => {}
     }
 
-    commands::generate()
+    commands::generate(rules)
 }
@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+use std::env::args;
+use std::iter::once;
+use std::process::ExitCode;
+
+use litho_compiler::{Limits, Lints, Project};
+use litho_language::SchemaCoordinate;
+use smol_str::SmolStr;
+
+use super::generate::projects;
+use crate::Workspace;
+
+/// Prints the definition and all usages of the schema element with the given
+/// schema coordinate (e.g. `litho find 'Query.user(id:)' schema.graphql`).
+pub fn find() -> ExitCode {
+    let mut coordinate = None;
+    let mut inputs = vec![];
+    let mut config = None;
+    let mut names = vec![];
+    let mut allow_exec = false;
+
+    let mut args = args().skip(2);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--allow-exec" => allow_exec = true,
+            "--config" => match args.next() {
+                Some(arg) => config = Some(arg),
+                None => panic!("Missing config argument."),
+            },
+            "--project" => match args.next() {
+                Some(arg) => names.push(arg),
+                None => panic!("Missing project argument."),
+            },
+            _ if coordinate.is_none() => coordinate = Some(arg),
+            _ => inputs.push(arg),
+        }
+    }
+
+    let Some(coordinate) = coordinate else {
+        panic!("Missing coordinate argument.");
+    };
+
+    let coordinate = match SchemaCoordinate::<SmolStr>::parse(&coordinate) {
+        Ok(coordinate) => coordinate,
+        Err(error) => {
+            eprintln!("Invalid schema coordinate `{}`: {}.", coordinate, error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let projects = match config {
+        Some(config) => {
+            if !inputs.is_empty() {
+                panic!("Inputs must be configured per project.");
+            }
+
+            projects(&config, &names)
+        }
+        None => vec![(Project::default(), inputs)],
+    };
+
+    let mut found = false;
+    let mut printed = HashSet::new();
+
+    for (project, inputs) in projects {
+        let workspace = Workspace::new(
+            inputs,
+            &project,
+            Lints::default(),
+            Limits::default(),
+            vec![],
+            None,
+            allow_exec,
+        );
+
+        let database = workspace.compiler().database();
+
+        let Some(element) = database.resolve_coordinate(&coordinate) else {
+            continue;
+        };
+
+        found = true;
+
+        let mut files = workspace.files().collect::<Vec<_>>();
+        files.sort_by_key(|file| file.path);
+
+        let usages = files
+            .iter()
+            .flat_map(|file| element.usages(database, file.document.as_ref()))
+            .map(|span| ("usage", span));
+
+        for (kind, span) in once(("definition", element.span())).chain(usages) {
+            let Some((path, line, column)) = workspace.position(span) else {
+                continue;
+            };
+
+            // Files that are part of several projects (e.g. a shared schema)
+            // are only listed once.
+            if printed.insert((path.to_owned(), line, column)) {
+                println!("{}:{}:{}: {}", path, line, column, kind);
+            }
+        }
+    }
+
+    match found {
+        true => ExitCode::SUCCESS,
+        false => {
+            eprintln!("Couldn't find `{}`.", coordinate);
+            ExitCode::FAILURE
+        }
+    }
+}
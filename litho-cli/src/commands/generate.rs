@@ -1,18 +1,36 @@
+use std::collections::HashSet;
 use std::env::args;
-use std::fs::{metadata, write};
+use std::fs::{metadata, read_to_string, write};
+use std::path::PathBuf;
 use std::process::ExitCode;
+use std::sync::Arc;
 
 use ariadne::{Label, Report, ReportKind};
+use litho_compiler::{Limits, Lints, Project, Rule};
+use litho_diagnostics::Severity;
 use litho_language::fmt::Format;
 use litho_language::lex::Span;
+use litho_language::SchemaCoordinate;
+use serde::Deserialize;
+use smol_str::SmolStr;
 use yansi::Paint;
 
 use crate::Workspace;
 
 #[derive(Default)]
 pub struct Options {
+    allow_exec: bool,
+    cache: Option<PathBuf>,
+    config: Option<String>,
+    cost: bool,
+    exemptions: Vec<SchemaCoordinate<String>>,
+    federation: bool,
     fix: bool,
     format: bool,
+    limits: Limits,
+    lint: bool,
+    projects: Vec<String>,
+    relay: bool,
 }
 
 pub enum FormattingError {
@@ -20,7 +38,35 @@ pub enum FormattingError {
     Changed,
 }
 
-pub fn generate() -> ExitCode {
+fn limit(name: &str, arg: Option<String>) -> usize {
+    match arg.map(|arg| arg.parse()) {
+        Some(Ok(limit)) => limit,
+        Some(Err(_)) => panic!("Invalid {} argument.", name),
+        None => panic!("Missing {} argument.", name),
+    }
+}
+
+fn coordinate(name: &str, arg: Option<String>) -> SchemaCoordinate<String> {
+    match arg.as_deref().map(SchemaCoordinate::parse) {
+        Some(Ok(coordinate)) => coordinate,
+        Some(Err(error)) => panic!("Invalid {} argument: {}.", name, error),
+        None => panic!("Missing {} argument.", name),
+    }
+}
+
+/// Parses a cost override (e.g. `Query.users=10`).
+fn cost(arg: Option<String>) -> (SchemaCoordinate<String>, usize) {
+    let Some((element, weight)) = arg.as_deref().and_then(|arg| arg.rsplit_once('=')) else {
+        panic!("Missing or invalid cost argument.");
+    };
+
+    (
+        coordinate("--cost", Some(element.to_owned())),
+        limit("--cost", Some(weight.to_owned())),
+    )
+}
+
+pub fn generate(rules: Vec<Arc<dyn Rule<SmolStr>>>) -> ExitCode {
     let mut inputs = vec![];
     let mut outputs = vec![];
     let mut options = Options::default();
@@ -29,8 +75,31 @@ pub fn generate() -> ExitCode {
 
     while let Some(arg) = args.next() {
         match arg.as_str() {
+            "--allow-exec" => options.allow_exec = true,
+            "--cache" => match args.next() {
+                Some(arg) => options.cache = Some(arg.into()),
+                None => panic!("Missing cache argument."),
+            },
+            "--config" => match args.next() {
+                Some(arg) => options.config = Some(arg),
+                None => panic!("Missing config argument."),
+            },
+            "--cost" => options.limits.costs.push(cost(args.next())),
+            "--cost-directives" => options.cost = true,
+            "--federation" => options.federation = true,
             "--fix" => options.fix = true,
             "--fmt" | "--format" => options.format = true,
+            "--lint" => options.lint = true,
+            "--lint-exempt" => options.exemptions.push(coordinate(&arg, args.next())),
+            "--max-aliases" => options.limits.max_aliases = Some(limit(&arg, args.next())),
+            "--max-cost" => options.limits.max_cost = Some(limit(&arg, args.next())),
+            "--max-depth" => options.limits.max_depth = Some(limit(&arg, args.next())),
+            "--max-fields" => options.limits.max_fields = Some(limit(&arg, args.next())),
+            "--project" => match args.next() {
+                Some(arg) => options.projects.push(arg),
+                None => panic!("Missing project argument."),
+            },
+            "--relay" => options.relay = true,
             "-o" | "--output" => match args.next() {
                 Some(arg) => outputs.push(arg),
                 None => panic!("Missing output argument."),
@@ -39,81 +108,151 @@ pub fn generate() -> ExitCode {
         }
     }
 
-    let workspace = Workspace::new(inputs);
+    let projects = match options.config.as_ref() {
+        Some(config) => {
+            if !inputs.is_empty() || !outputs.is_empty() {
+                panic!("Inputs and outputs must be configured per project.");
+            }
+
+            projects(config, &options.projects)
+        }
+        None => vec![(
+            Project {
+                output: outputs,
+                cost: options.cost,
+                federation: options.federation,
+                relay: options.relay,
+                ..Default::default()
+            },
+            inputs,
+        )],
+    };
+
+    let lints = match options.lint {
+        true => Lints {
+            exemptions: options.exemptions.clone(),
+            ..Lints::all()
+        },
+        false => Lints::default(),
+    };
 
-    let mut files = workspace.files().collect::<Vec<_>>();
-    files.sort_by_key(|file| file.path);
+    let workspaces = projects
+        .into_iter()
+        .map(|(project, inputs)| {
+            let cache = options
+                .cache
+                .as_ref()
+                .map(|cache| cache.join(&project.name));
 
-    let mut sources = workspace.to_sources();
+            let workspace = Workspace::new(
+                inputs,
+                &project,
+                lints.clone(),
+                options.limits.clone(),
+                rules.clone(),
+                cache,
+                options.allow_exec,
+            );
+
+            (project, workspace)
+        })
+        .collect::<Vec<_>>();
 
     let mut success = true;
+    let mut formatted = HashSet::new();
+    let mut reported = HashSet::new();
+
+    for (_, workspace) in workspaces.iter() {
+        let mut files = workspace.files().collect::<Vec<_>>();
+        files.sort_by_key(|file| file.path);
+
+        let mut sources = workspace.to_sources();
 
-    for file in files {
-        if options.format {
-            let formatted = file.document.format_to_string(80);
-            if &formatted != file.text {
-                let formatting_error = if options.fix {
-                    let modified = metadata(file.path).and_then(|metadata| metadata.modified());
+        for file in files {
+            if options.format && formatted.insert(file.path.clone()) {
+                let formatted = file.document.format_to_string(80);
+                if &formatted != file.text {
+                    let formatting_error = if options.fix {
+                        let modified = metadata(file.path).and_then(|metadata| metadata.modified());
 
-                    if file.modified.as_ref().ok() != modified.as_ref().ok() {
-                        Some(FormattingError::Changed)
+                        if file.modified.as_ref().ok() != modified.as_ref().ok() {
+                            Some(FormattingError::Changed)
+                        } else {
+                            write(file.path, formatted).unwrap();
+                            None
+                        }
                     } else {
-                        write(file.path, formatted).unwrap();
-                        None
-                    }
-                } else {
-                    Some(FormattingError::Unformatted)
-                };
+                        Some(FormattingError::Unformatted)
+                    };
 
-                if let Some(diagnostic) = formatting_error {
-                    eprintln!(
-                        "{} {}\n   {}{}{}\n",
-                        Paint::red("[E0000] Error:"),
-                        match diagnostic {
-                            FormattingError::Changed =>
-                                "File has changed on disk while formatting.",
-                            FormattingError::Unformatted => "File must be formatted.",
-                        },
-                        Paint::new("──[").dimmed(),
-                        file.path,
-                        Paint::new("]").dimmed(),
-                    );
+                    if let Some(diagnostic) = formatting_error {
+                        eprintln!(
+                            "{} {}\n   {}{}{}\n",
+                            Paint::red("[E0000] Error:"),
+                            match diagnostic {
+                                FormattingError::Changed =>
+                                    "File has changed on disk while formatting.",
+                                FormattingError::Unformatted => "File must be formatted.",
+                            },
+                            Paint::new("──[").dimmed(),
+                            file.path,
+                            Paint::new("]").dimmed(),
+                        );
 
-                    success = false;
+                        success = false;
+                    }
                 }
             }
-        }
 
-        for diagnostic in workspace.compiler().diagnostics(file.source_id).cloned() {
-            let span = diagnostic.span();
-            let mut builder = Report::<Span>::build(ReportKind::Error, span.source_id, span.start)
-                .with_code(diagnostic.code())
-                .with_message(diagnostic.message());
-            builder.add_labels(
-                diagnostic
-                    .labels()
-                    .into_iter()
-                    .map(|(span, message)| Label::new(span).with_message(message)),
-            );
-            builder.finish().eprint(&mut sources).unwrap();
-            eprintln!("");
+            for diagnostic in workspace.compiler().diagnostics(file.source_id).cloned() {
+                let span = diagnostic.span();
+
+                // Files that are part of several projects (e.g. a shared
+                // schema) are only reported once.
+                if !reported.insert((file.path.clone(), diagnostic.code(), span.start, span.end)) {
+                    continue;
+                }
 
-            success = false;
+                let kind = match diagnostic.severity() {
+                    Severity::Error => ReportKind::Error,
+                    Severity::Warning => ReportKind::Warning,
+                };
+                let mut builder = Report::<Span>::build(kind, span.source_id, span.start)
+                    .with_code(diagnostic.code())
+                    .with_message(diagnostic.message());
+                if let Some(help) = diagnostic.help() {
+                    builder.set_note(help);
+                }
+                builder.add_labels(
+                    diagnostic
+                        .labels()
+                        .into_iter()
+                        .map(|(span, message)| Label::new(span).with_message(message)),
+                );
+                builder.finish().eprint(&mut sources).unwrap();
+                eprintln!("");
+
+                if diagnostic.severity() == Severity::Error {
+                    success = false;
+                }
+            }
         }
     }
 
     match success {
         true => {
-            for output in outputs {
-                litho_codegen::codegen(
-                    workspace.compiler().database(),
-                    workspace
-                        .files()
-                        .map(|file| (file.source_id, (file.path.as_str(), file.text.as_str())))
-                        .collect(),
-                    &output,
-                )
-                .unwrap();
+            for (project, workspace) in workspaces.iter() {
+                for output in project.output.iter() {
+                    litho_codegen::codegen(
+                        workspace.compiler().database(),
+                        workspace
+                            .files()
+                            .map(|file| (file.source_id, (file.path.as_str(), file.text.as_str())))
+                            .collect(),
+                        output,
+                    )
+                    .unwrap();
+                }
             }
 
             ExitCode::SUCCESS
@@ -121,3 +260,38 @@ pub fn generate() -> ExitCode {
         false => ExitCode::FAILURE,
     }
 }
+
+/// Reads the projects from a config file and returns them along with their
+/// inputs. If any names are given, only those projects are returned.
+pub(super) fn projects(config: &str, names: &[String]) -> Vec<(Project, Vec<String>)> {
+    let text = match read_to_string(config) {
+        Ok(text) => text,
+        Err(error) => panic!("Couldn't read config {}: {}", config, error),
+    };
+
+    #[derive(Deserialize)]
+    struct Config {
+        projects: Vec<Project>,
+    }
+
+    let config = match serde_json::from_str::<Config>(&text) {
+        Ok(config) => config,
+        Err(error) => panic!("Invalid config {}: {}", config, error),
+    };
+
+    for name in names {
+        if !config.projects.iter().any(|project| &project.name == name) {
+            panic!("Unknown project {}.", name);
+        }
+    }
+
+    config
+        .projects
+        .into_iter()
+        .filter(|project| names.is_empty() || names.contains(&project.name))
+        .map(|project| {
+            let inputs = project.patterns().map(ToOwned::to_owned).collect();
+            (project, inputs)
+        })
+        .collect()
+}
@@ -1,5 +1,7 @@
+mod find;
 mod generate;
 mod version;
 
+pub use find::find;
 pub use generate::generate;
 pub use version::version;
@@ -1,6 +1,7 @@
+mod cache;
 mod cli;
 pub mod commands;
 mod workspace;
 
-pub use cli::cli;
+pub use cli::{cli, cli_with_rules};
 pub use workspace::Workspace;
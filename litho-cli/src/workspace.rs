@@ -2,17 +2,20 @@ use std::collections::HashMap;
 use std::fmt::Display;
 use std::fs::{metadata, read_to_string};
 use std::iter::once;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::SystemTime;
 
 use ariadne::{Cache, Source};
 use glob::glob;
-use litho_compiler::{builtins, Compiler};
+use litho_compiler::{Compiler, Limits, Lints, Project, Rule};
+use litho_import::{DefaultImporter, Importer};
 use litho_language::ast::Document;
-use litho_language::lex::{SourceId, SourceMap};
+use litho_language::lex::{SourceId, SourceMap, Span};
 use smol_str::SmolStr;
 
+use crate::cache::CompileCache;
+
 #[derive(Default)]
 pub struct Sources(HashMap<SourceId, (String, Source)>);
 
@@ -54,11 +57,90 @@ pub fn find(path: &str, results: &mut impl Extend<String>) {
     );
 }
 
+type Files = HashMap<SourceId, (String, std::io::Result<SystemTime>)>;
+
+fn inputs<'a>(
+    files: &'a Files,
+    source_map: &'a SourceMap<String>,
+) -> impl Iterator<Item = (SourceId, &'a String, &'a String)> {
+    files
+        .iter()
+        .flat_map(|(&source_id, (text, _))| Some((source_id, source_map.get_id(&source_id)?, text)))
+}
+
+fn imported<'a>(
+    imports: &'a HashMap<SourceId, String>,
+    source_map: &'a SourceMap<String>,
+) -> impl Iterator<Item = (SourceId, &'a String, &'a String)> {
+    imports
+        .iter()
+        .flat_map(|(&source_id, text)| Some((source_id, source_map.get_id(&source_id)?, text)))
+}
+
+/// Resolves the schemas that are imported with `@litho(url: ...)` and adds
+/// them to the compiler. Relative paths and commands are resolved against the
+/// directory of the document that contains the import. Commands are only run
+/// if `exec` is enabled. Returns the text of each imported schema.
+fn import(
+    compiler: &mut Compiler<SmolStr>,
+    source_map: &mut SourceMap<String>,
+    texts: &mut HashMap<SourceId, String>,
+    cache: Option<PathBuf>,
+    exec: bool,
+) -> HashMap<SourceId, String> {
+    let imports = compiler.imports().clone();
+    let mut imported = HashMap::new();
+
+    if imports.is_empty() {
+        return imported;
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    let mut resolved = HashMap::new();
+
+    for (url, import) in imports {
+        let base = source_map
+            .get_id(&import.span.source_id)
+            .and_then(|path| Path::new(path).parent())
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+
+        let headers = import
+            .headers
+            .iter()
+            .map(|header| (header.name.clone(), header.value.clone()))
+            .collect::<Vec<_>>();
+
+        let result = runtime
+            .block_on(
+                DefaultImporter::new(base, cache.clone(), exec).import::<String>(&url, &headers),
+            )
+            .map(|text| {
+                let source_id = source_map.get_or_insert(url.clone());
+                compiler.add_document(source_id, &text, true);
+                texts.insert(source_id, text.clone());
+                imported.insert(source_id, text);
+                source_id
+            });
+
+        resolved.insert(url, result);
+    }
+
+    compiler.update_resolved_imports(resolved);
+    compiler.rebuild();
+
+    imported
+}
+
 pub struct Workspace {
     compiler: Compiler<SmolStr>,
     texts: HashMap<SourceId, String>,
     source_map: SourceMap<String>,
-    files: HashMap<SourceId, (String, std::io::Result<SystemTime>)>,
+    files: Files,
 }
 
 pub struct File<'a> {
@@ -70,18 +152,43 @@ pub struct File<'a> {
 }
 
 impl Workspace {
-    pub fn new<I>(iterator: I) -> Workspace
+    pub fn new<I>(
+        iterator: I,
+        project: &Project,
+        lints: Lints,
+        limits: Limits,
+        rules: Vec<Arc<dyn Rule<SmolStr>>>,
+        cache: Option<PathBuf>,
+        exec: bool,
+    ) -> Workspace
     where
         I: IntoIterator<Item = String>,
     {
+        let fingerprint = format!(
+            "{} {} {:?} {:?} {:?} {:?}",
+            env!("CARGO_PKG_VERSION"),
+            env!("VERGEN_GIT_SHA_SHORT"),
+            project,
+            lints,
+            limits,
+            rules.iter().map(|rule| rule.name()).collect::<Vec<_>>(),
+        );
+
         let mut compiler = Compiler::new();
+        compiler.set_lints(lints);
+        compiler.set_limits(limits);
+
+        for rule in rules {
+            compiler.add_rule(rule);
+        }
+
         let mut source_map = SourceMap::new();
         let mut files = HashMap::new();
         let mut texts = HashMap::new();
 
-        for (path, text) in builtins().into_iter().copied() {
+        for (path, text) in project.builtins() {
             let source_id = source_map.get_or_insert(path.to_owned());
-            compiler.add_document(source_id, &text, true);
+            compiler.add_document(source_id, text, true);
             texts.insert(source_id, text.to_owned());
         }
 
@@ -92,19 +199,51 @@ impl Workspace {
         }
 
         paths.sort();
+        paths.dedup();
 
         for path in paths {
             let source_id = source_map.get_or_insert(path.clone());
             let metadata = metadata(&path);
             let modified = metadata.and_then(|metadata| metadata.modified());
             let text = read_to_string(path.clone()).unwrap();
-            compiler.add_document(source_id, &text, true);
+            compiler.add_document(source_id, &text, false);
             files.insert(source_id, (text.clone(), modified));
             texts.insert(source_id, text);
         }
 
+        let import_cache = cache.as_ref().map(|directory| directory.join("imports"));
+
+        let cache = cache.map(|directory| {
+            let cache = CompileCache::load(&directory, fingerprint);
+            cache.restore(&mut compiler, &source_map, inputs(&files, &source_map));
+            (directory, cache)
+        });
+
         compiler.rebuild();
 
+        let imports = import(
+            &mut compiler,
+            &mut source_map,
+            &mut texts,
+            import_cache,
+            exec,
+        );
+
+        if let Some((directory, mut cache)) = cache {
+            cache.restore_imports(&mut compiler, imported(&imports, &source_map));
+            compiler.rebuild();
+            cache.store(
+                &compiler,
+                &source_map,
+                inputs(&files, &source_map),
+                imported(&imports, &source_map),
+            );
+
+            if let Err(error) = cache.save(&directory) {
+                eprintln!("Couldn't write cache to {}: {}", directory.display(), error);
+            }
+        }
+
         Workspace {
             compiler,
             source_map,
@@ -117,6 +256,23 @@ impl Workspace {
         &self.compiler
     }
 
+    /// Returns the path and the (1-based) line and column of the start of
+    /// the given span.
+    pub fn position(&self, span: Span) -> Option<(&String, usize, usize)> {
+        let path = self.source_map.get_id(&span.source_id)?;
+        let prefix = self.texts.get(&span.source_id)?.get(..span.start)?;
+        let line = prefix.matches('\n').count() + 1;
+        let column = prefix
+            .rsplit('\n')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .count()
+            + 1;
+
+        Some((path, line, column))
+    }
+
     pub fn to_sources(&self) -> Sources {
         let mut sources = Sources::default();
 
@@ -143,3 +299,67 @@ impl Workspace {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::fs::{create_dir_all, remove_dir_all, write};
+
+    use litho_compiler::{Limits, Lints, Project};
+    use litho_diagnostics::Diagnostic;
+    use litho_language::lex::Span;
+
+    use super::Workspace;
+
+    #[test]
+    fn test_cached_imports() {
+        let directory = temp_dir().join(format!("litho-cli-{}", std::process::id()));
+        let cache = directory.join("cache");
+        let main = directory.join("main.graphql");
+        let vendor = directory.join("vendor.graphql");
+
+        create_dir_all(&directory).unwrap();
+        write(
+            &main,
+            "schema @litho(url: \"vendor.graphql\", headers: []) { query: Query }\ntype Query { user: User }",
+        )
+        .unwrap();
+        write(&vendor, "type User { name: String }").unwrap();
+
+        let codes = |cache| {
+            let workspace = Workspace::new(
+                [main.to_string_lossy().into_owned()],
+                &Project::default(),
+                Lints::default(),
+                Limits::default(),
+                vec![],
+                cache,
+                false,
+            );
+
+            let files = workspace.files().collect::<Vec<_>>();
+            assert_eq!(files.len(), 1);
+
+            workspace
+                .compiler()
+                .diagnostics(files[0].source_id)
+                .map(Diagnostic::<Span>::code)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(codes(Some(cache.clone())), Vec::<&str>::new());
+
+        write(&vendor, "type Other { name: String }").unwrap();
+
+        let uncached = codes(None);
+
+        assert!(uncached.contains(&"E0100"));
+        assert_eq!(codes(Some(cache.clone())), uncached);
+
+        write(&vendor, "type User { name: String }").unwrap();
+
+        assert_eq!(codes(Some(cache)), Vec::<&str>::new());
+
+        remove_dir_all(directory).unwrap();
+    }
+}
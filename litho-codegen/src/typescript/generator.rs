@@ -1,9 +1,11 @@
 use std::borrow::Borrow;
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::sync::Arc;
 
-use litho_language::ast::{Type, TypeDefinition};
+use litho_language::ast::{Directive, OperationDefinition, OperationType, Type, TypeDefinition};
 use litho_language::lex::{Name, SourceId};
+use litho_types::selection::{SelectedField, Selections};
 use litho_types::Database;
 use serde::Deserialize;
 
@@ -132,6 +134,11 @@ where
 
         match ty {
             TypeDefinition::EnumTypeDefinition(_) => self.process_enum(name),
+            TypeDefinition::InputObjectTypeDefinition(_)
+                if self.database.is_one_of_input_object(name.as_ref()) =>
+            {
+                self.process_one_of_input_object(name)
+            }
             TypeDefinition::InputObjectTypeDefinition(_) => self.process_input_object(name),
             TypeDefinition::InterfaceTypeDefinition(_) => self.process_interface(name),
             TypeDefinition::ObjectTypeDefinition(_) => self.process_object(name),
@@ -205,6 +212,49 @@ where
         self.dts.text("}\n\n");
     }
 
+    fn process_one_of_input_object(&mut self, name: &Name<T>) {
+        self.dts
+            .text("export type ")
+            .token(name.as_raw_token())
+            .text(" =");
+
+        let fields = self
+            .database
+            .input_value_definitions(name.as_ref())
+            .collect::<Vec<_>>();
+
+        if fields.is_empty() {
+            self.dts.text(" never");
+        }
+
+        for field in fields.iter() {
+            self.dts
+                .text("\n    | { ")
+                .token(field.name.as_raw_token())
+                .text(": ");
+
+            match field.ty.ok() {
+                Some(ty) => self.write_type(ty),
+                None => {
+                    self.dts.text("never");
+                }
+            }
+
+            for other in fields.iter() {
+                if other.name.as_ref() != field.name.as_ref() {
+                    self.dts
+                        .text(", ")
+                        .token(other.name.as_raw_token())
+                        .text("?: never");
+                }
+            }
+
+            self.dts.text(" }");
+        }
+
+        self.dts.text(";\n\n");
+    }
+
     fn process_interface(&mut self, name: &Name<T>) {
         self.dts
             .text("export interface ")
@@ -308,6 +358,187 @@ where
         self.dts.text(";\n\n");
     }
 
+    /// Writes the type of the response data of a named operation. Fields that
+    /// are deferred (relative to the field that selects them) or that are
+    /// only included under some conditions are possibly absent.
+    fn process_operation(&mut self, operation: &'a Arc<OperationDefinition<T>>) {
+        let Some(name) = operation.name.as_ref() else {
+            return;
+        };
+
+        let Some(tree) = self.database.selection_tree(operation) else {
+            return;
+        };
+
+        let suffix = operation
+            .ty
+            .as_ref()
+            .map(OperationType::default_type_name)
+            .unwrap_or("Query");
+
+        self.dts
+            .text("export type ")
+            .token(name.as_raw_token())
+            .text(suffix)
+            .text(" = ");
+
+        self.write_selections(operation, &tree.selections, None, 0);
+
+        self.dts.text(";\n\n");
+    }
+
+    fn write_selections(
+        &mut self,
+        operation: &'a Arc<OperationDefinition<T>>,
+        selections: &Selections<'a, T>,
+        deferred: Option<&'a Arc<Directive<T>>>,
+        depth: usize,
+    ) {
+        let union = selections.by_type.len() > 1;
+
+        if selections.by_type.is_empty() {
+            self.dts.text("{}");
+        }
+
+        if union {
+            self.dts.text("(");
+        }
+
+        for (index, (_, fields)) in selections.by_type.iter().enumerate() {
+            if index > 0 {
+                self.dts.text(" | ");
+            }
+
+            self.dts.text("{\n");
+
+            for field in fields.iter() {
+                self.write_selected_field(operation, field, deferred, depth + 1);
+            }
+
+            self.dts.text(&"    ".repeat(depth)).text("}");
+        }
+
+        if union {
+            self.dts.text(")");
+        }
+    }
+
+    fn write_selected_field(
+        &mut self,
+        operation: &'a Arc<OperationDefinition<T>>,
+        field: &SelectedField<'a, T>,
+        deferred: Option<&'a Arc<Directive<T>>>,
+        depth: usize,
+    ) {
+        self.dts.text(&"    ".repeat(depth));
+
+        let key = field
+            .fields
+            .first()
+            .and_then(|field| match field.alias.as_ref() {
+                Some(alias) => Some(&alias.name),
+                None => field.name.ok(),
+            });
+
+        match key {
+            Some(key) => self.dts.token(key.as_raw_token()),
+            None => self.dts.text(field.response_key.borrow()),
+        };
+
+        let deferrals = self.deferrals(operation, field);
+
+        // A field is possibly absent if any of the merged fields is deferred
+        // by a different `@defer` than the field that selects it. Its own
+        // fields are only deferred along with it if all merged fields are.
+        let is_deferred = deferrals.iter().any(|defer| match (defer, deferred) {
+            (Some(defer), Some(deferred)) => !Arc::ptr_eq(defer, deferred),
+            (Some(_), None) => true,
+            (None, _) => false,
+        });
+
+        let defer = match deferrals.iter().all(Option::is_some) {
+            true => deferrals.first().copied().flatten(),
+            false => None,
+        };
+
+        if is_deferred || !field.conditions.is_unconditional() {
+            self.dts.text("?");
+        }
+
+        self.dts.text(": ");
+
+        match field.ty {
+            Some(ty) => self.write_selected_type(operation, ty, field, defer, depth),
+            None => {
+                self.dts.text("never");
+            }
+        }
+
+        match field.ty {
+            Some(ty) if ty.is_nullable() => {
+                self.dts.text(" | null");
+            }
+            Some(_) | None => {}
+        }
+
+        self.dts.text(";\n");
+    }
+
+    fn write_selected_type(
+        &mut self,
+        operation: &'a Arc<OperationDefinition<T>>,
+        ty: &Type<T>,
+        field: &SelectedField<'a, T>,
+        deferred: Option<&'a Arc<Directive<T>>>,
+        depth: usize,
+    ) {
+        match ty {
+            Type::List(list) => {
+                match list.ty.ok() {
+                    Some(ty) => self.write_selected_type(operation, ty, field, deferred, depth),
+                    None => {
+                        self.dts.text("never");
+                    }
+                }
+
+                self.dts.text("[]");
+            }
+            Type::NonNull(ty) => {
+                self.write_selected_type(operation, &ty.ty, field, deferred, depth);
+            }
+            Type::Named(_)
+                if field
+                    .fields
+                    .iter()
+                    .any(|field| field.selection_set.is_some()) =>
+            {
+                self.write_selections(operation, &field.selections, deferred, depth);
+            }
+            Type::Named(_) => self.write_type(ty),
+        }
+    }
+
+    /// Returns the `@defer` directive (if any) that makes each of the merged
+    /// fields of a selected field possibly absent from the initial response
+    /// of an operation.
+    fn deferrals(
+        &self,
+        operation: &'a Arc<OperationDefinition<T>>,
+        field: &SelectedField<'a, T>,
+    ) -> Vec<Option<&'a Arc<Directive<T>>>> {
+        field
+            .fields
+            .iter()
+            .map(|field| {
+                self.database
+                    .deferrals(field)
+                    .into_iter()
+                    .find(|(other, _)| Arc::ptr_eq(other, operation))
+                    .and_then(|(_, directive)| directive)
+            })
+            .collect()
+    }
+
     fn generate(mut self) -> Self {
         self.preprocess_export_directives();
 
@@ -323,6 +554,25 @@ where
             self.process_type_definition(ty);
         }
 
+        let mut operations = self
+            .database
+            .operations
+            .by_name
+            .keys()
+            .flat_map(|name| self.database.operations.by_name(name))
+            .collect::<Vec<_>>();
+        operations.sort_by_key(|operation| {
+            operation
+                .name
+                .as_ref()
+                .map(|name| name.as_raw_token().source.borrow())
+                .unwrap_or_default()
+        });
+
+        for operation in operations {
+            self.process_operation(operation);
+        }
+
         self
     }
 }
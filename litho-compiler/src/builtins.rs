@@ -18,3 +18,24 @@ pub const fn builtins() -> &'static [(&'static str, &'static str)] {
         ),
     ]
 }
+
+pub const fn cost_builtins() -> &'static [(&'static str, &'static str)] {
+    &[(
+        "litho://std.litho.dev/cost.graphql",
+        include_str!("../std/cost.graphql"),
+    )]
+}
+
+pub const fn federation_builtins() -> &'static [(&'static str, &'static str)] {
+    &[(
+        "litho://std.litho.dev/federation.graphql",
+        include_str!("../std/federation.graphql"),
+    )]
+}
+
+pub const fn relay_builtins() -> &'static [(&'static str, &'static str)] {
+    &[(
+        "litho://std.litho.dev/relay.graphql",
+        include_str!("../std/relay.graphql"),
+    )]
+}
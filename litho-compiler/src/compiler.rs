@@ -1,15 +1,18 @@
 use std::borrow::Borrow;
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
+use std::mem::take;
 use std::sync::Arc;
 
 use litho_diagnostics::Diagnostic;
-use litho_language::ast::{DefinitionId, Document};
+use litho_language::ast::*;
 use litho_language::chk::collect_errors;
 use litho_language::lex::{SourceId, Span};
 use litho_language::Parse;
 use litho_types::{Database, Import};
-use litho_validation::check;
+use litho_validation::{check, check_limits, lint, Limits, Lints, Rule};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 use super::{Consumer, DepGraph, Dependency, Producer};
 
@@ -19,12 +22,19 @@ where
     T: Eq + Hash,
 {
     definition_diagnostics: HashMap<DefinitionId, Vec<Diagnostic<Span>>>,
-    definition_sources: HashMap<DefinitionId, SourceId>,
+    definitions: HashMap<DefinitionId, (SourceId, Arc<Definition<T>>)>,
     documents: HashMap<SourceId, (Arc<Document<T>>, bool)>,
     document_diagnostics: HashMap<SourceId, Vec<Diagnostic<Span>>>,
+    texts: HashMap<SourceId, String>,
     graph: DepGraph<DefinitionId, Dependency<T>>,
     database: Database<T>,
+    stale: HashSet<DefinitionId>,
+    unresolved: bool,
     imports: HashMap<String, Result<SourceId, String>>,
+    import_diagnostics: HashMap<SourceId, Vec<Diagnostic<Span>>>,
+    limits: Limits,
+    lints: Lints,
+    rules: Vec<Arc<dyn Rule<T>>>,
 }
 
 impl<T> Compiler<T>
@@ -34,12 +44,19 @@ where
     pub fn new() -> Compiler<T> {
         Compiler {
             definition_diagnostics: Default::default(),
-            definition_sources: Default::default(),
+            definitions: Default::default(),
             documents: Default::default(),
             document_diagnostics: Default::default(),
+            texts: Default::default(),
             graph: DepGraph::new(),
             database: Database::new(),
+            stale: Default::default(),
+            unresolved: false,
             imports: HashMap::new(),
+            import_diagnostics: HashMap::new(),
+            limits: Limits::default(),
+            lints: Lints::default(),
+            rules: vec![],
         }
     }
 
@@ -50,19 +67,54 @@ where
     pub fn document(&self, source_id: SourceId) -> Option<&Arc<Document<T>>> {
         self.documents.get(&source_id).map(|(doc, _)| doc)
     }
+
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    pub fn set_limits(&mut self, limits: Limits) {
+        if self.limits != limits {
+            self.limits = limits;
+            self.definition_diagnostics.clear();
+        }
+    }
+
+    pub fn lints(&self) -> &Lints {
+        &self.lints
+    }
+
+    pub fn set_lints(&mut self, lints: Lints) {
+        if self.lints != lints {
+            self.lints = lints;
+            self.definition_diagnostics.clear();
+        }
+    }
+
+    pub fn rules(&self) -> &[Arc<dyn Rule<T>>] {
+        &self.rules
+    }
+
+    /// Registers a custom rule that runs on all non-imported definitions, in
+    /// addition to the builtin checks and lints.
+    pub fn add_rule(&mut self, rule: Arc<dyn Rule<T>>) {
+        self.rules.push(rule);
+        self.definition_diagnostics.clear();
+    }
 }
 
 impl<T> Compiler<T>
 where
-    T: Eq + Clone + Hash + Borrow<str> + ToString,
+    T: Eq + Clone + Hash + Borrow<str> + for<'a> From<&'a str> + ToString,
 {
     pub fn imports(&self) -> &HashMap<String, Import> {
         self.database.imports()
     }
 
+    /// Updates the results of resolving the imports. Failed imports are
+    /// reported on their `@litho` directive by the next `rebuild`.
     pub fn update_resolved_imports(&mut self, imports: HashMap<String, Result<SourceId, String>>)
     where
-        T: for<'a> From<&'a str> + for<'a> PartialEq<&'a str>,
+        T: for<'a> PartialEq<&'a str>,
     {
         self.imports = imports;
     }
@@ -87,9 +139,46 @@ where
                     .flatten()
             });
 
-        document_diagnostics.chain(definition_diagnostics)
+        let import_diagnostics = self
+            .import_diagnostics
+            .get(&source_id)
+            .into_iter()
+            .flatten();
+
+        document_diagnostics
+            .chain(definition_diagnostics)
+            .chain(import_diagnostics)
     }
 
+    /// Returns the diagnostics of a single definition, which are only
+    /// available after `rebuild`.
+    pub fn definition_diagnostics(
+        &self,
+        definition_id: DefinitionId,
+    ) -> Option<&[Diagnostic<Span>]> {
+        self.definition_diagnostics
+            .get(&definition_id)
+            .map(Vec::as_slice)
+    }
+
+    /// Restores the diagnostics of a definition that were computed earlier
+    /// (e.g. by a previous run), so that `rebuild` doesn't check it again
+    /// unless it is invalidated first.
+    pub fn restore_definition_diagnostics(
+        &mut self,
+        definition_id: DefinitionId,
+        diagnostics: Vec<Diagnostic<Span>>,
+    ) {
+        if self.definitions.contains_key(&definition_id) {
+            self.definition_diagnostics
+                .insert(definition_id, diagnostics);
+        }
+    }
+
+    /// Replaces the text of a document. Definitions whose text and position
+    /// didn't change are kept as is (along with their bindings, inference
+    /// and diagnostics), so only the definitions that did change and those
+    /// that depend on them are checked again by the next `rebuild`.
     pub fn replace_document(
         &mut self,
         source_id: SourceId,
@@ -97,12 +186,73 @@ where
         is_import: bool,
     ) -> HashSet<SourceId>
     where
-        T: for<'a> From<&'a str> + for<'b> PartialEq<&'b str>,
+        T: for<'b> PartialEq<&'b str>,
     {
-        let mut source_ids = self.remove_document(source_id);
-        source_ids.extend(self.add_document(source_id, text, is_import));
+        let result = Document::parse_from_str(source_id, text).unwrap_or_default();
+        let diagnostics = collect_errors(&result);
 
-        source_ids
+        let mut previous = HashMap::new();
+
+        if let Some(((document, was_import), previous_text)) = self
+            .documents
+            .get(&source_id)
+            .zip(self.texts.get(&source_id))
+        {
+            for definition in document.definitions.iter() {
+                let span = definition.span();
+
+                if *was_import == is_import
+                    && previous_text.get(span.start..span.end) == text.get(span.start..span.end)
+                {
+                    previous.insert((span.start, span.end), definition.clone());
+                }
+            }
+        }
+
+        let definitions = result
+            .0
+            .definitions
+            .into_iter()
+            .map(|definition| {
+                let span = definition.span();
+                previous
+                    .remove(&(span.start, span.end))
+                    .unwrap_or(definition)
+            })
+            .collect::<Vec<_>>();
+
+        let existing = definitions
+            .iter()
+            .map(|definition| definition.id())
+            .collect::<HashSet<_>>();
+
+        let removed = self
+            .documents
+            .get(&source_id)
+            .into_iter()
+            .flat_map(|(document, _)| document.definitions.iter())
+            .filter(|definition| !existing.contains(&definition.id()))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let added = definitions
+            .iter()
+            .filter(|definition| !self.definitions.contains_key(&definition.id()))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut definition_ids = self.remove_definitions(&removed);
+        definition_ids.extend(self.add_definitions(source_id, &added));
+
+        self.documents
+            .insert(source_id, (Arc::new(Document { definitions }), is_import));
+        self.texts.insert(source_id, text.to_owned());
+        self.document_diagnostics.insert(source_id, diagnostics);
+
+        let changed = removed.into_iter().chain(added).collect::<Vec<_>>();
+        let mut set = self.invalidate(definition_ids, &changed);
+        set.insert(source_id);
+        set
     }
 
     pub fn add_document(
@@ -112,17 +262,38 @@ where
         is_import: bool,
     ) -> HashSet<SourceId>
     where
-        T: for<'a> From<&'a str> + for<'b> PartialEq<&'b str>,
+        T: for<'b> PartialEq<&'b str>,
     {
-        let result = Document::parse_from_str(source_id, text).unwrap_or_default();
-        let diagnostics = collect_errors(&result);
+        self.replace_document(source_id, text, is_import)
+    }
 
-        let mut definition_ids = HashSet::new();
+    pub fn remove_document(&mut self, source_id: SourceId) -> HashSet<SourceId> {
+        let Some((document, _)) = self.documents.remove(&source_id) else {
+            return HashSet::new();
+        };
+
+        self.texts.remove(&source_id);
+        self.document_diagnostics.remove(&source_id);
+
+        let definition_ids = self.remove_definitions(&document.definitions);
 
-        for definition in result.0.definitions.iter() {
-            self.definition_sources.insert(definition.id(), source_id);
+        let mut set = self.invalidate(definition_ids, &document.definitions);
+        set.insert(source_id);
+        set
+    }
+
+    fn add_definitions(
+        &mut self,
+        source_id: SourceId,
+        definitions: &[Arc<Definition<T>>],
+    ) -> HashSet<DefinitionId> {
+        let mut definition_ids = HashSet::new();
 
-            definition_ids.insert(definition.id());
+        for definition in definitions {
+            self.definitions
+                .insert(definition.id(), (source_id, definition.clone()));
+            self.database.index(definition);
+            self.unresolved |= !is_executable_definition(definition);
 
             if let Some(product) = definition.product() {
                 definition_ids.extend(self.graph.produce(definition.id(), product));
@@ -133,74 +304,928 @@ where
             }
         }
 
-        self.documents
-            .insert(source_id, (Arc::new(result.0), is_import));
-        self.document_diagnostics.insert(source_id, diagnostics);
+        for definition in definitions {
+            self.affected(definition, &mut definition_ids);
+        }
 
-        let mut set = self.invalidate(definition_ids);
-        set.insert(source_id);
-        set
+        definition_ids
     }
 
-    pub fn remove_document(&mut self, source_id: SourceId) -> HashSet<SourceId> {
-        let document = self.documents.remove(&source_id);
-
+    fn remove_definitions(&mut self, definitions: &[Arc<Definition<T>>]) -> HashSet<DefinitionId> {
         let mut definition_ids = HashSet::new();
 
-        for definition in document
-            .as_ref()
-            .map(|document| document.0.definitions.iter())
-            .into_iter()
-            .flatten()
-        {
-            self.graph.invalidate(definition.id(), &mut definition_ids);
+        for definition in definitions {
+            self.affected(definition, &mut definition_ids);
+        }
+
+        for definition in definitions {
             self.graph.remove(definition.id());
+            self.unresolved |= !is_executable_definition(definition);
+            self.database.uninfer(definition);
+            self.database.unindex(definition);
+            self.definitions.remove(&definition.id());
+            self.definition_diagnostics.remove(&definition.id());
         }
 
-        let source_ids = self.invalidate(definition_ids);
+        definition_ids
+    }
 
-        for definition in document
-            .as_ref()
-            .map(|document| document.0.definitions.iter())
-            .into_iter()
-            .flatten()
-        {
-            self.definition_sources.remove(&definition.id());
+    /// Collects the definitions whose diagnostics depend on the given
+    /// definition: its consumers, other definitions with the same name, the
+    /// directives and fragments that it uses, which are checked for usages,
+    /// the interfaces that it implements (and their consumers, which depend on
+    /// their possible types) and operations whose names may conflict with it.
+    fn affected(
+        &mut self,
+        definition: &Arc<Definition<T>>,
+        definition_ids: &mut HashSet<DefinitionId>,
+    ) {
+        self.graph
+            .invalidate(definition.id(), definition_ids, &is_fragment);
+        self.dependents(definition.product(), definition.consumes(), definition_ids);
+
+        for interface in implemented_interfaces(definition) {
+            let interface = Dependency::Type(interface.clone());
+            definition_ids.extend(self.graph.producers(&interface));
+            self.graph
+                .invalidate_consumers(&interface, definition_ids, &is_fragment);
         }
 
-        source_ids
+        if let Some(operation) = operation_definition(definition) {
+            let name = operation.name.as_ref().map(|name| name.as_ref());
+            let operations = self
+                .definitions
+                .iter()
+                .filter(|(_, (_, other))| {
+                    operation_definition(other).is_some_and(|other| {
+                        let other = other.name.as_ref().map(|name| name.as_ref());
+                        name.is_none() || other.is_none() || name == other
+                    })
+                })
+                .map(|(definition_id, _)| *definition_id);
+
+            definition_ids.extend(operations);
+        }
     }
 
-    fn invalidate<I>(&mut self, definition_ids: I) -> HashSet<SourceId>
-    where
-        I: IntoIterator<Item = DefinitionId>,
-    {
+    fn dependents(
+        &self,
+        product: Option<Dependency<T>>,
+        consumes: Vec<Dependency<T>>,
+        definition_ids: &mut HashSet<DefinitionId>,
+    ) {
+        if let Some(product) = product {
+            definition_ids.extend(self.graph.producers(&product));
+            self.graph
+                .invalidate_consumers(&product, definition_ids, &is_fragment);
+        }
+
+        for dependency in consumes {
+            match dependency {
+                Dependency::Directive(_) => {
+                    definition_ids.extend(self.graph.producers(&dependency));
+                }
+                Dependency::Fragment(_) => self.fragments(&dependency, definition_ids),
+                Dependency::Schema | Dependency::Type(_) => {}
+            }
+        }
+    }
+
+    /// Collects the producers of the given fragment and (transitively) of the
+    /// fragments that they spread, whose variables depend on the operations
+    /// that use them.
+    fn fragments(&self, fragment: &Dependency<T>, definition_ids: &mut HashSet<DefinitionId>) {
+        for node in self.graph.producers(fragment) {
+            if !definition_ids.insert(*node) {
+                continue;
+            }
+
+            for dependency in self.graph.dependencies(node) {
+                if let Dependency::Fragment(_) = dependency {
+                    self.fragments(dependency, definition_ids);
+                }
+            }
+        }
+    }
+
+    /// Discards the diagnostics of all definitions that depend on a
+    /// definition with the given product and dependencies, which may no
+    /// longer exist (e.g. because it changed since its diagnostics were
+    /// restored from a cache). Since the dependencies that can only be
+    /// inferred aren't known for such a definition, executable definitions are
+    /// checked again if that definition is part of the type system, and (if
+    /// unused definitions are linted) so is the rest of the type system.
+    pub fn invalidate_dependents(
+        &mut self,
+        product: Option<Dependency<T>>,
+        consumes: Vec<Dependency<T>>,
+    ) {
+        let is_type_system = !matches!(product, None | Some(Dependency::Fragment(_)));
+        let unused_definitions = self.lints.unused_definitions;
+
+        let mut definition_ids = HashSet::new();
+        self.dependents(product, consumes, &mut definition_ids);
+
+        if is_type_system {
+            definition_ids.extend(
+                self.definitions
+                    .iter()
+                    .filter(|(_, (_, definition))| {
+                        unused_definitions || is_executable_definition(definition)
+                    })
+                    .map(|(definition_id, _)| *definition_id),
+            );
+        }
+
+        for definition_id in definition_ids {
+            self.definition_diagnostics.remove(&definition_id);
+        }
+    }
+
+    /// Marks the given definitions as stale and returns the sources that
+    /// contain them. Unused definitions depend on the entire type system (e.g.
+    /// reachability), so if they are linted, the rest of the type system is
+    /// checked again (but not inferred again) after a change to it.
+    fn invalidate(
+        &mut self,
+        definition_ids: HashSet<DefinitionId>,
+        changed: &[Arc<Definition<T>>],
+    ) -> HashSet<SourceId> {
         let mut source_ids = HashSet::new();
 
+        if self.lints.unused_definitions
+            && changed
+                .iter()
+                .any(|definition| !is_executable_definition(definition))
+        {
+            for (definition_id, (source_id, definition)) in self.definitions.iter() {
+                if !is_executable_definition(definition) {
+                    self.definition_diagnostics.remove(definition_id);
+                    source_ids.insert(*source_id);
+                }
+            }
+        }
+
         for definition_id in definition_ids.into_iter() {
             self.definition_diagnostics.remove(&definition_id);
+            self.stale.insert(definition_id);
 
-            source_ids.extend(self.definition_sources.get(&definition_id).into_iter());
+            source_ids.extend(
+                self.definitions
+                    .get(&definition_id)
+                    .map(|(source_id, _)| *source_id),
+            );
         }
 
         source_ids
     }
 
+    /// Infers stale definitions again and checks all definitions that don't
+    /// have diagnostics yet (in parallel).
+    #[cfg(feature = "parallel")]
     pub fn rebuild(&mut self)
     where
-        T: From<&'static str>,
+        T: Send + Sync,
     {
-        self.database = Database::with_imports(
-            self.documents.values().map(|(doc, _)| doc.as_ref()),
-            &Default::default(),
-        );
+        self.reinfer();
 
-        for document in self.documents.values() {
-            for definition in document.0.definitions.iter() {
-                self.definition_diagnostics
-                    .entry(definition.id())
-                    .or_insert_with(|| check(definition, &self.database));
+        let diagnostics = self
+            .pending()
+            .into_par_iter()
+            .map(|(definition, is_import)| {
+                (
+                    definition.id(),
+                    self.check_definition(definition, is_import),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        self.finish(diagnostics);
+    }
+
+    /// Infers stale definitions again and checks all definitions that don't
+    /// have diagnostics yet.
+    #[cfg(not(feature = "parallel"))]
+    pub fn rebuild(&mut self) {
+        self.reinfer();
+
+        let diagnostics = self
+            .pending()
+            .into_iter()
+            .map(|(definition, is_import)| {
+                (
+                    definition.id(),
+                    self.check_definition(definition, is_import),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        self.finish(diagnostics);
+    }
+
+    fn reinfer(&mut self) {
+        if take(&mut self.unresolved) {
+            self.database.resolve();
+        }
+
+        for definition_id in take(&mut self.stale) {
+            if let Some((_, definition)) = self.definitions.get(&definition_id) {
+                self.database.uninfer(definition);
+                self.database.infer(definition);
+
+                self.graph.unconsume(definition_id);
+
+                for dependency in definition.consumes_inferred(&self.database) {
+                    self.graph.consume(definition_id, dependency);
+                }
+            }
+        }
+    }
+
+    /// Returns the definitions that don't have diagnostics yet, along with
+    /// whether they were imported.
+    fn pending(&self) -> Vec<(&Arc<Definition<T>>, bool)> {
+        self.documents
+            .values()
+            .flat_map(|(document, is_import)| {
+                document
+                    .definitions
+                    .iter()
+                    .map(move |definition| (definition, *is_import))
+            })
+            .filter(|(definition, _)| !self.definition_diagnostics.contains_key(&definition.id()))
+            .collect()
+    }
+
+    fn finish(&mut self, diagnostics: Vec<(DefinitionId, Vec<Diagnostic<Span>>)>) {
+        self.definition_diagnostics.extend(diagnostics);
+
+        self.import_diagnostics.clear();
+
+        for (url, import) in self.database.imports() {
+            if let Some(Err(error)) = self.imports.get(url) {
+                self.import_diagnostics
+                    .entry(import.span.source_id)
+                    .or_default()
+                    .push(Diagnostic::import_failed(
+                        url.to_owned(),
+                        error.to_owned(),
+                        import.span,
+                    ));
+            }
+        }
+    }
+
+    fn check_definition(
+        &self,
+        definition: &Arc<Definition<T>>,
+        is_import: bool,
+    ) -> Vec<Diagnostic<Span>> {
+        let mut diagnostics = check(definition, &self.database);
+
+        if !is_import {
+            diagnostics.extend(lint(definition, &self.database, &self.lints));
+            diagnostics.extend(check_limits(definition, &self.database, &self.limits));
+
+            for rule in self.rules.iter() {
+                diagnostics.extend(rule.check(definition, &self.database));
+            }
+        }
+
+        diagnostics
+    }
+}
+
+fn is_executable_definition<T>(definition: &Definition<T>) -> bool {
+    matches!(definition, Definition::ExecutableDefinition(_))
+}
+
+fn is_fragment<T>(dependency: &Dependency<T>) -> bool {
+    matches!(dependency, Dependency::Fragment(_))
+}
+
+fn operation_definition<T>(definition: &Definition<T>) -> Option<&OperationDefinition<T>> {
+    match definition {
+        Definition::ExecutableDefinition(ExecutableDefinition::OperationDefinition(operation)) => {
+            Some(operation)
+        }
+        _ => None,
+    }
+}
+
+fn implemented_interfaces<T>(definition: &Definition<T>) -> impl Iterator<Item = &T> {
+    let implements_interfaces = match definition {
+        Definition::TypeSystemDefinitionOrExtension(
+            TypeSystemDefinitionOrExtension::TypeSystemDefinition(
+                TypeSystemDefinition::TypeDefinition(definition),
+            ),
+        ) => definition.implements_interfaces(),
+        Definition::TypeSystemDefinitionOrExtension(
+            TypeSystemDefinitionOrExtension::TypeSystemExtension(
+                TypeSystemExtension::TypeExtension(extension),
+            ),
+        ) => extension.implements_interfaces(),
+        _ => None,
+    };
+
+    implements_interfaces
+        .into_iter()
+        .flat_map(|implements| implements.types())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use litho_diagnostics::{Diagnostic, Severity};
+    use litho_language::ast::{Document, FieldDefinition, Visit};
+    use litho_language::lex::{SourceId, SourceMap, Span};
+    use litho_language::{Parse, SchemaCoordinate};
+    use litho_validation::{Limits, Lints, RuleContext};
+
+    use super::Compiler;
+    use crate::{builtins, cost_builtins, federation_builtins, Consumer, Dependency, Producer};
+
+    fn compiler(source_map: &mut SourceMap<&'static str>) -> Compiler<String> {
+        let mut compiler = Compiler::new();
+
+        for (path, text) in builtins() {
+            compiler.add_document(source_map.get_or_insert(path), text, true);
+        }
+
+        compiler
+    }
+
+    fn codes(compiler: &Compiler<String>, source_id: SourceId) -> Vec<&'static str> {
+        let mut codes = compiler
+            .diagnostics(source_id)
+            .map(Diagnostic::<Span>::code)
+            .collect::<Vec<_>>();
+        codes.sort();
+        codes
+    }
+
+    struct ForbiddenFields;
+
+    impl<'a> Visit<'a, String> for ForbiddenFields {
+        type Accumulator = RuleContext<'a, String>;
+
+        fn visit_field_definition(
+            &self,
+            node: &'a Arc<FieldDefinition<String>>,
+            accumulator: &mut Self::Accumulator,
+        ) {
+            if node.name.as_ref() == "forbidden" {
+                accumulator.report(Diagnostic::custom(
+                    "X0001",
+                    "Field is forbidden.",
+                    Severity::Warning,
+                    node.name.span(),
+                    vec![(node.name.span(), "Rename this field.".to_owned())],
+                ));
             }
         }
     }
+
+    #[test]
+    fn test_custom_rules() {
+        let mut source_map = SourceMap::new();
+        let schema = source_map.get_or_insert("schema.graphql");
+        let import = source_map.get_or_insert("import.graphql");
+
+        let mut compiler = Compiler::<String>::new();
+        compiler.add_rule(Arc::new(ForbiddenFields));
+        compiler.add_document(schema, "type Query { forbidden: Query }", false);
+        compiler.add_document(import, "type Import { forbidden: Query }", true);
+        compiler.rebuild();
+
+        assert_eq!(codes(&compiler, schema), vec!["X0001"]);
+        assert_eq!(codes(&compiler, import), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_field_sets() {
+        let mut source_map = SourceMap::new();
+        let schema = source_map.get_or_insert("schema.graphql");
+
+        let mut plain = compiler(&mut source_map);
+        plain.add_document(
+            schema,
+            "directive @key(fields: String!) on OBJECT\ntype Query @key(fields: \"unknown\") { id: ID }",
+            false,
+        );
+        plain.rebuild();
+
+        assert_eq!(codes(&plain, schema), Vec::<&str>::new());
+
+        let mut federation = compiler(&mut source_map);
+
+        for (path, text) in federation_builtins() {
+            federation.add_document(source_map.get_or_insert(path), text, true);
+        }
+
+        federation.add_document(
+            schema,
+            "type Query @key(fields: \"unknown\") { id: ID }",
+            false,
+        );
+        federation.rebuild();
+
+        assert_eq!(codes(&federation, schema), vec!["E0137"]);
+    }
+
+    #[test]
+    fn test_cost_directives() {
+        let mut source_map = SourceMap::new();
+        let schema = source_map.get_or_insert("schema.graphql");
+
+        let mut custom = compiler(&mut source_map);
+        custom.add_document(
+            schema,
+            "directive @cost(complexity: Int!) on FIELD_DEFINITION\ntype Query { id: ID @cost(complexity: 2) }",
+            false,
+        );
+        custom.rebuild();
+
+        assert_eq!(codes(&custom, schema), Vec::<&str>::new());
+
+        let mut builtin = compiler(&mut source_map);
+
+        for (path, text) in cost_builtins() {
+            builtin.add_document(source_map.get_or_insert(path), text, true);
+        }
+
+        builtin.add_document(schema, "type Query { id: ID @cost(weight: 2) }", false);
+        builtin.rebuild();
+
+        assert_eq!(codes(&builtin, schema), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_lint_exemptions() {
+        let mut source_map = SourceMap::new();
+        let schema = source_map.get_or_insert("schema.graphql");
+
+        let mut lints = Lints {
+            pascal_case_type_names: true,
+            camel_case_field_names: true,
+            ..Default::default()
+        };
+
+        let mut compiler = compiler(&mut source_map);
+        compiler.set_lints(lints.clone());
+        compiler.add_document(
+            schema,
+            "type Query { id: ID, user_name: String }\ntype legacy_user { user_id: ID }",
+            false,
+        );
+        compiler.rebuild();
+
+        assert_eq!(codes(&compiler, schema), vec!["L0001", "L0002", "L0002"]);
+
+        lints.exemptions = ["legacy_user", "Query.user_name"]
+            .into_iter()
+            .map(|coordinate| SchemaCoordinate::parse(coordinate).unwrap())
+            .collect();
+        compiler.set_lints(lints);
+        compiler.rebuild();
+
+        assert_eq!(codes(&compiler, schema), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_cost_overrides() {
+        let mut source_map = SourceMap::new();
+        let schema = source_map.get_or_insert("schema.graphql");
+        let query = source_map.get_or_insert("query.graphql");
+
+        let mut limits = Limits {
+            max_cost: Some(20),
+            ..Default::default()
+        };
+
+        let mut compiler = compiler(&mut source_map);
+        compiler.set_limits(limits.clone());
+        compiler.add_document(
+            schema,
+            "type Query { users(first: Int): [User!]! }\ntype User { id: ID! }",
+            false,
+        );
+        compiler.add_document(query, "query Users { users(first: 10) { id } }", false);
+        compiler.rebuild();
+
+        assert_eq!(codes(&compiler, query), Vec::<&str>::new());
+
+        for coordinate in ["Query.users", "Query.users(first:)", "User"] {
+            limits.costs = vec![(SchemaCoordinate::parse(coordinate).unwrap(), 5)];
+            compiler.set_limits(limits.clone());
+            compiler.rebuild();
+
+            assert_eq!(codes(&compiler, query), vec!["E0343"], "{}", coordinate);
+        }
+    }
+
+    #[test]
+    fn test_incremental_rebuilds() {
+        let mut source_map = SourceMap::new();
+        let mut compiler = compiler(&mut source_map);
+        let schema = source_map.get_or_insert("schema.graphql");
+        let query = source_map.get_or_insert("query.graphql");
+        let other = source_map.get_or_insert("other.graphql");
+        let duplicate = source_map.get_or_insert("duplicate.graphql");
+
+        compiler.add_document(
+            schema,
+            "type Query { user: User, version: String }\ntype User { name: String }",
+            false,
+        );
+        compiler.add_document(query, "query Example { user { name } }", false);
+        compiler.add_document(other, "query Other { version }", false);
+        compiler.rebuild();
+
+        assert_eq!(codes(&compiler, query), Vec::<&str>::new());
+
+        let definition_id = |compiler: &Compiler<String>, index: usize| {
+            compiler.document(schema).unwrap().definitions[index].id()
+        };
+
+        let root = definition_id(&compiler, 0);
+        let user = definition_id(&compiler, 1);
+        let unrelated = compiler.document(other).unwrap().definitions[0].id();
+
+        let affected = compiler.replace_document(
+            schema,
+            "type Query { user: User, version: String }\ntype User { id: ID }",
+            false,
+        );
+
+        assert!(affected.contains(&query));
+        assert!(!affected.contains(&other));
+        assert!(compiler.definition_diagnostics(unrelated).is_some());
+
+        compiler.rebuild();
+
+        assert_eq!(definition_id(&compiler, 0), root);
+        assert_ne!(definition_id(&compiler, 1), user);
+        assert_eq!(codes(&compiler, query), vec!["E0303"]);
+
+        let affected = compiler.replace_document(query, "query Example { user { id } }", false);
+
+        assert!(!affected.contains(&other));
+        assert!(compiler.definition_diagnostics(unrelated).is_some());
+
+        compiler.rebuild();
+
+        assert_eq!(codes(&compiler, query), Vec::<&str>::new());
+
+        let affected = compiler.add_document(duplicate, "query Other { version }", false);
+        compiler.rebuild();
+
+        assert!(affected.contains(&other));
+        assert_eq!(
+            [codes(&compiler, other), codes(&compiler, duplicate)].concat(),
+            vec!["E0300"]
+        );
+
+        compiler.remove_document(duplicate);
+        compiler.rebuild();
+
+        assert_eq!(codes(&compiler, other), Vec::<&str>::new());
+
+        compiler.replace_document(query, "query Example { user { name } }", false);
+
+        compiler.replace_document(
+            schema,
+            "type Query { user: User, version: String }\ntype User { id: ID, name: String }",
+            false,
+        );
+        compiler.rebuild();
+
+        assert_eq!(definition_id(&compiler, 0), root);
+        assert_eq!(codes(&compiler, query), Vec::<&str>::new());
+        assert_eq!(
+            compiler
+                .database()
+                .type_definitions_by_name(&"User".to_owned())
+                .count(),
+            1
+        );
+        assert_eq!(
+            compiler
+                .database()
+                .field_definitions(&"User".to_owned())
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_incremental_rebuilds_match_full_rebuilds() {
+        let texts = [
+            "directive @tag(name: String!) on FIELD_DEFINITION\ntype Query { user: User @tag(name: \"user\") }",
+            "type User implements Node { id: ID!, friends(first: Int = 10): [User!]! }",
+            "interface Node { id: ID! }",
+            "query Friends { user { ...Friends } }\nfragment Friends on User { friends { id } }",
+        ];
+
+        let mut source_map = SourceMap::new();
+        let mut incremental = compiler(&mut source_map);
+        let source_ids = ["a.graphql", "b.graphql", "c.graphql", "d.graphql"]
+            .map(|path| source_map.get_or_insert(path));
+
+        for (source_id, text) in source_ids.iter().zip(texts) {
+            incremental.add_document(*source_id, text, false);
+            incremental.rebuild();
+        }
+
+        incremental.replace_document(source_ids[2], "interface Node { uuid: ID! }", false);
+        incremental.rebuild();
+        incremental.replace_document(source_ids[0], "type Query { user: User }", false);
+        incremental.rebuild();
+        incremental.remove_document(source_ids[2]);
+        incremental.rebuild();
+        incremental.add_document(source_ids[2], texts[2], false);
+        incremental.replace_document(source_ids[0], texts[0], false);
+        incremental.rebuild();
+
+        let mut full = compiler(&mut source_map);
+
+        for (source_id, text) in source_ids.iter().zip(texts) {
+            full.add_document(*source_id, text, false);
+        }
+
+        full.rebuild();
+
+        for source_id in source_ids {
+            assert_eq!(codes(&incremental, source_id), codes(&full, source_id));
+        }
+
+        let node = "Node".to_owned();
+
+        assert_eq!(
+            incremental.database().possible_types(&node).count(),
+            full.database().possible_types(&node).count()
+        );
+        assert_eq!(
+            incremental.database().directive_definitions().count(),
+            full.database().directive_definitions().count()
+        );
+    }
+
+    #[test]
+    fn test_incremental_lints() {
+        let mut source_map = SourceMap::new();
+        let schema = source_map.get_or_insert("schema.graphql");
+        let foo = source_map.get_or_insert("foo.graphql");
+
+        let lints = Lints {
+            unused_definitions: true,
+            ..Default::default()
+        };
+
+        let mut incremental = compiler(&mut source_map);
+        incremental.set_lints(lints.clone());
+        incremental.add_document(schema, "type Query { id: ID }", false);
+        incremental.add_document(foo, "type Foo { id: ID }", false);
+        incremental.rebuild();
+
+        assert_eq!(codes(&incremental, foo), vec!["L0013"]);
+
+        let affected =
+            incremental.replace_document(schema, "type Query { id: ID, foo: Foo }", false);
+        incremental.rebuild();
+
+        assert!(affected.contains(&foo));
+
+        let mut full = compiler(&mut source_map);
+        full.set_lints(lints);
+        full.add_document(schema, "type Query { id: ID, foo: Foo }", false);
+        full.add_document(foo, "type Foo { id: ID }", false);
+        full.rebuild();
+
+        assert_eq!(codes(&full, foo), Vec::<&str>::new());
+        assert_eq!(codes(&incremental, foo), codes(&full, foo));
+        assert_eq!(codes(&incremental, schema), codes(&full, schema));
+    }
+
+    #[test]
+    fn test_incremental_local_lints() {
+        let mut source_map = SourceMap::new();
+        let mut compiler = compiler(&mut source_map);
+        let schema = source_map.get_or_insert("schema.graphql");
+        let foo = source_map.get_or_insert("foo.graphql");
+
+        compiler.set_lints(Lints {
+            pascal_case_type_names: true,
+            ..Default::default()
+        });
+        compiler.add_document(schema, "type Query { id: ID }", false);
+        compiler.add_document(foo, "type foo { id: ID }", false);
+        compiler.rebuild();
+
+        assert_eq!(codes(&compiler, foo), vec!["L0001"]);
+
+        let affected =
+            compiler.replace_document(schema, "type Query { id: ID, name: String }", false);
+        compiler.rebuild();
+
+        assert!(!affected.contains(&foo));
+        assert_eq!(codes(&compiler, foo), vec!["L0001"]);
+    }
+
+    #[test]
+    fn test_directive_dependencies() {
+        let mut source_map = SourceMap::new();
+        let mut compiler = compiler(&mut source_map);
+        let directive = source_map.get_or_insert("directive.graphql");
+        let schema = source_map.get_or_insert("schema.graphql");
+        let other = source_map.get_or_insert("other.graphql");
+
+        compiler.add_document(
+            directive,
+            "directive @tag(name: String!) on FIELD_DEFINITION",
+            false,
+        );
+        compiler.add_document(
+            schema,
+            "type Query { user: String @tag(name: \"user\") }",
+            false,
+        );
+        compiler.add_document(other, "type Other { id: ID }", false);
+        compiler.rebuild();
+
+        assert_eq!(codes(&compiler, schema), Vec::<&str>::new());
+
+        let affected = compiler.replace_document(
+            directive,
+            "directive @tag(label: String!) on FIELD_DEFINITION",
+            false,
+        );
+        compiler.rebuild();
+
+        assert!(affected.contains(&directive));
+        assert!(affected.contains(&schema));
+        assert!(!affected.contains(&other));
+        assert_eq!(codes(&compiler, schema), vec!["E0310"]);
+
+        let affected = compiler.replace_document(
+            directive,
+            "directive @tag(name: String!) on FIELD_DEFINITION",
+            false,
+        );
+        compiler.rebuild();
+
+        assert!(affected.contains(&schema));
+        assert_eq!(codes(&compiler, schema), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_fragment_dependencies() {
+        let mut source_map = SourceMap::new();
+        let mut compiler = compiler(&mut source_map);
+        let schema = source_map.get_or_insert("schema.graphql");
+        let query = source_map.get_or_insert("query.graphql");
+        let fragment = source_map.get_or_insert("fragment.graphql");
+
+        compiler.add_document(
+            schema,
+            "type Query { user(id: ID): User }\ntype User { name: String }",
+            false,
+        );
+        compiler.add_document(query, "query Example($id: ID) { ...Root }", false);
+        compiler.add_document(
+            fragment,
+            "fragment Root on Query { user(id: $id) { name } }",
+            false,
+        );
+        compiler.rebuild();
+
+        assert_eq!(codes(&compiler, query), Vec::<&str>::new());
+
+        let affected =
+            compiler.replace_document(fragment, "fragment Root on Query { user { name } }", false);
+        compiler.rebuild();
+
+        assert!(affected.contains(&query));
+        assert_eq!(codes(&compiler, query), vec!["E0326"]);
+
+        let affected = compiler.remove_document(query);
+        compiler.rebuild();
+
+        assert!(affected.contains(&fragment));
+        assert_eq!(codes(&compiler, fragment), vec!["E0315"]);
+    }
+
+    #[test]
+    fn test_dependencies() {
+        let (document, _) = Document::<String>::parse_from_str(
+            Default::default(),
+            "query Example @live { ...Root }",
+        )
+        .unwrap();
+        let definition = &document.definitions[0];
+
+        assert_eq!(definition.product(), None);
+        assert_eq!(
+            definition.consumes(),
+            vec![
+                Dependency::Schema,
+                Dependency::Directive("live".to_owned()),
+                Dependency::Fragment("Root".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_restored_diagnostics() {
+        let mut source_map = SourceMap::new();
+        let schema = source_map.get_or_insert("schema.graphql");
+        let query = source_map.get_or_insert("query.graphql");
+
+        let texts = |compiler: &mut Compiler<String>, schema_text| {
+            compiler.add_document(schema, schema_text, false);
+            compiler.add_document(query, "query Example { user { name } }", false);
+        };
+
+        let mut previous = compiler(&mut source_map);
+        texts(
+            &mut previous,
+            "type Query { user: User }\ntype User { id: ID }",
+        );
+        previous.rebuild();
+
+        let diagnostics = |compiler: &Compiler<String>| {
+            let definition = compiler.document(query).unwrap().definitions[0].id();
+            compiler
+                .definition_diagnostics(definition)
+                .unwrap()
+                .to_vec()
+        };
+
+        assert_eq!(codes(&previous, query), vec!["E0303"]);
+
+        let mut next = compiler(&mut source_map);
+        texts(&mut next, "type Query { user: User }\ntype User { id: ID }");
+        let definition = next.document(query).unwrap().definitions[0].id();
+        next.restore_definition_diagnostics(definition, vec![]);
+        next.rebuild();
+
+        assert!(diagnostics(&next).is_empty());
+
+        let mut next = compiler(&mut source_map);
+        texts(
+            &mut next,
+            "type Query { user: User }\ntype User { name: String }",
+        );
+        next.restore_definition_diagnostics(definition, diagnostics(&previous));
+
+        for definition in previous.document(schema).unwrap().definitions.iter() {
+            next.invalidate_dependents(definition.product(), definition.consumes());
+        }
+
+        next.rebuild();
+
+        assert_eq!(codes(&next, query), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_resolved_imports() {
+        let mut source_map = SourceMap::new();
+        let mut compiler = compiler(&mut source_map);
+        let schema = source_map.get_or_insert("schema.graphql");
+        let query = source_map.get_or_insert("query.graphql");
+        let import = source_map.get_or_insert("imported.graphql");
+
+        compiler.add_document(
+            schema,
+            "schema @litho(url: \"imported.graphql\", headers: []) { query: Query }",
+            false,
+        );
+        compiler.add_document(query, "query Example { user { name } }", false);
+        compiler.rebuild();
+
+        assert_eq!(compiler.imports().len(), 1);
+        assert_eq!(
+            compiler.imports()["imported.graphql"].span.source_id,
+            schema
+        );
+
+        compiler.update_resolved_imports(
+            [("imported.graphql".to_owned(), Err("not found".to_owned()))].into(),
+        );
+        compiler.rebuild();
+
+        assert_eq!(codes(&compiler, schema), vec!["E0100", "E0140"]);
+
+        compiler.add_document(
+            import,
+            "type Query { user: User }\ntype User { name: String }",
+            true,
+        );
+        compiler.update_resolved_imports([("imported.graphql".to_owned(), Ok(import))].into());
+        compiler.rebuild();
+
+        assert_eq!(codes(&compiler, schema), Vec::<&str>::new());
+        assert_eq!(codes(&compiler, query), Vec::<&str>::new());
+    }
 }
@@ -1,6 +1,8 @@
+use std::hash::Hash;
 use std::sync::Arc;
 
 use litho_language::ast::*;
+use litho_types::Database;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Dependency<T> {
@@ -16,39 +18,108 @@ pub trait Producer<T> {
 
 pub trait Consumer<T> {
     fn consumes(&self) -> Vec<Dependency<T>>;
+
+    /// Returns the dependencies of this node, including those that can only be
+    /// inferred: the root operation type, the types of selection sets and
+    /// fields, and the input types of values.
+    fn consumes_inferred(&self, database: &Database<T>) -> Vec<Dependency<T>>
+    where
+        T: Eq + Hash;
 }
 
 impl<T, N> Consumer<T> for N
 where
     N: Producer<T> + Node<T>,
-    T: ToOwned<Owned = T>,
+    T: ToOwned<Owned = T> + Eq + Hash,
 {
     fn consumes(&self) -> Vec<Dependency<T>> {
         let mut consumes = self.product().into_iter().collect();
-        self.traverse(&Tracker, &mut consumes);
+        self.traverse(&Tracker(None), &mut consumes);
+        consumes
+    }
+
+    fn consumes_inferred(&self, database: &Database<T>) -> Vec<Dependency<T>> {
+        let mut consumes = self.product().into_iter().collect();
+        self.traverse(&Tracker(Some(database)), &mut consumes);
         consumes
     }
 }
 
-pub struct Tracker;
+pub struct Tracker<'a, T>(Option<&'a Database<T>>)
+where
+    T: Eq + Hash;
 
-impl<'a, T> Visit<'a, T> for Tracker
+impl<'a, T> Tracker<'a, T>
 where
-    T: ToOwned<Owned = T>,
+    T: ToOwned<Owned = T> + Eq + Hash,
+{
+    fn track(&self, ty: Option<&T>, accumulator: &mut Vec<Dependency<T>>) {
+        if let Some(ty) = ty {
+            accumulator.push(Dependency::Type(ty.to_owned()))
+        }
+    }
+}
+
+impl<'a, 'ast, T> Visit<'ast, T> for Tracker<'a, T>
+where
+    T: ToOwned<Owned = T> + Eq + Hash,
 {
     type Accumulator = Vec<Dependency<T>>;
 
     fn visit_operation_definition(
         &self,
-        _node: &'a Arc<OperationDefinition<T>>,
+        node: &'ast Arc<OperationDefinition<T>>,
         accumulator: &mut Self::Accumulator,
     ) {
-        accumulator.push(Dependency::Schema)
+        accumulator.push(Dependency::Schema);
+
+        if let Some(database) = self.0 {
+            self.track(database.root_operation_type(node.ty.as_ref()), accumulator);
+        }
     }
 
-    fn visit_named_type(&self, node: &'a NamedType<T>, accumulator: &mut Self::Accumulator) {
+    fn visit_selection_set(
+        &self,
+        node: &'ast Arc<SelectionSet<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        if let Some(database) = self.0 {
+            let ty = database.inference.type_by_selection_set.get(node);
+            self.track(ty.map(AsRef::as_ref), accumulator);
+        }
+    }
+
+    fn visit_field(&self, node: &'ast Arc<Field<T>>, accumulator: &mut Self::Accumulator) {
+        if let Some(database) = self.0 {
+            let ty = database.inference.type_for_field(node);
+            self.track(ty.and_then(|ty| ty.name()), accumulator);
+        }
+    }
+
+    fn visit_value(&self, node: &'ast Arc<Value<T>>, accumulator: &mut Self::Accumulator) {
+        if let Some(database) = self.0 {
+            let ty = database.inference.types_for_values.get(node);
+            self.track(ty.and_then(|ty| ty.name()), accumulator);
+        }
+    }
+
+    fn visit_named_type(&self, node: &'ast NamedType<T>, accumulator: &mut Self::Accumulator) {
         accumulator.push(Dependency::Type(node.0.as_ref().to_owned()))
     }
+
+    fn visit_directive(&self, node: &'ast Arc<Directive<T>>, accumulator: &mut Self::Accumulator) {
+        if let Some(name) = node.name.ok() {
+            accumulator.push(Dependency::Directive(name.as_ref().to_owned()))
+        }
+    }
+
+    fn visit_fragment_spread(
+        &self,
+        node: &'ast Arc<FragmentSpread<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        accumulator.push(Dependency::Fragment(node.fragment_name.as_ref().to_owned()))
+    }
 }
 
 impl<T> Producer<T> for Definition<T>
@@ -86,14 +157,9 @@ where
     }
 }
 
-impl<T> Producer<T> for OperationDefinition<T>
-where
-    T: ToOwned<Owned = T>,
-{
+impl<T> Producer<T> for OperationDefinition<T> {
     fn product(&self) -> Option<Dependency<T>> {
-        self.name
-            .as_ref()
-            .map(|name| Dependency::Fragment(name.as_ref().to_owned()))
+        None
     }
 }
 
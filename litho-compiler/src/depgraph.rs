@@ -1,6 +1,5 @@
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
-use std::iter::once;
 
 #[derive(Debug)]
 pub struct DepGraph<K, V>
@@ -9,56 +8,117 @@ where
     V: Eq + Hash,
 {
     producers: HashMap<K, V>,
+    products: HashMap<V, HashSet<K>>,
     consumers: HashMap<V, HashSet<K>>,
+    consumes: HashMap<K, HashSet<V>>,
 }
 
 impl<K, V> DepGraph<K, V>
 where
-    K: Eq + Hash + Copy,
+    K: Eq + Hash,
     V: Eq + Hash,
 {
     pub fn new() -> DepGraph<K, V> {
         DepGraph {
             producers: Default::default(),
+            products: Default::default(),
             consumers: Default::default(),
+            consumes: Default::default(),
         }
     }
+}
 
+impl<K, V> DepGraph<K, V>
+where
+    K: Eq + Hash + Copy,
+    V: Eq + Hash + Clone,
+{
     pub fn produce(&mut self, producer: K, value: V) -> impl Iterator<Item = &K> {
-        let consumers = self.consumers.get(&value).into_iter().flatten();
-        self.producers.insert(producer, value);
-        consumers
+        self.products
+            .entry(value.clone())
+            .or_default()
+            .insert(producer);
+        self.producers.insert(producer, value.clone());
+        self.consumers.get(&value).into_iter().flatten()
+    }
+
+    pub fn producers<'a>(&'a self, value: &V) -> impl Iterator<Item = &'a K> + 'a {
+        self.products.get(value).into_iter().flatten()
+    }
+
+    pub fn consumers<'a>(&'a self, value: &V) -> impl Iterator<Item = &'a K> + 'a {
+        self.consumers.get(value).into_iter().flatten()
+    }
+
+    pub fn dependencies<'a>(&'a self, consumer: &K) -> impl Iterator<Item = &'a V> + 'a {
+        self.consumes.get(consumer).into_iter().flatten()
     }
 
     pub fn consume(&mut self, consumer: K, value: V) {
+        self.consumes
+            .entry(consumer)
+            .or_default()
+            .insert(value.clone());
         self.consumers.entry(value).or_default().insert(consumer);
     }
 
-    pub fn invalidate(&mut self, node: K, accumulator: &mut HashSet<K>) {
-        if accumulator.contains(&node) {
-            return;
+    /// Removes all dependencies of the given node, but keeps its product.
+    pub fn unconsume(&mut self, consumer: K) {
+        for value in self.consumes.remove(&consumer).into_iter().flatten() {
+            if let Some(consumers) = self.consumers.get_mut(&value) {
+                consumers.remove(&consumer);
+
+                if consumers.is_empty() {
+                    self.consumers.remove(&value);
+                }
+            }
         }
+    }
 
-        let consumers = self
-            .producers
-            .get(&node)
-            .and_then(|value| self.consumers.get(value))
-            .cloned()
-            .into_iter()
-            .flatten();
-
-        accumulator.extend(once(node));
-        consumers
-            .into_iter()
-            .for_each(|node| self.invalidate(node, accumulator));
+    /// Collects the given node and its consumers.
+    pub fn invalidate<F>(&self, node: K, accumulator: &mut HashSet<K>, follow: &F)
+    where
+        F: Fn(&V) -> bool,
+    {
+        accumulator.insert(node);
+
+        if let Some(value) = self.producers.get(&node) {
+            self.invalidate_consumers(value, accumulator, follow);
+        }
+    }
+
+    /// Collects the consumers of the given value. The consumers of a consumer
+    /// are only collected if its product is followed (e.g. because its
+    /// consumers depend on more than its syntax).
+    pub fn invalidate_consumers<F>(&self, value: &V, accumulator: &mut HashSet<K>, follow: &F)
+    where
+        F: Fn(&V) -> bool,
+    {
+        for consumer in self.consumers(value) {
+            if accumulator.contains(consumer) {
+                continue;
+            }
+
+            match self.producers.get(consumer) {
+                Some(value) if follow(value) => self.invalidate(*consumer, accumulator, follow),
+                _ => {
+                    accumulator.insert(*consumer);
+                }
+            }
+        }
     }
 
     pub fn remove(&mut self, node: K) {
-        self.producers.remove(&node);
+        if let Some(value) = self.producers.remove(&node) {
+            if let Some(producers) = self.products.get_mut(&value) {
+                producers.remove(&node);
+
+                if producers.is_empty() {
+                    self.products.remove(&value);
+                }
+            }
+        }
 
-        self.consumers.retain(|_, consumers| {
-            consumers.remove(&node);
-            !consumers.is_empty()
-        });
+        self.unconsume(node);
     }
 }
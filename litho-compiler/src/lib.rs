@@ -2,8 +2,11 @@ mod builtins;
 mod compiler;
 mod dependency;
 mod depgraph;
+mod project;
 
-pub use builtins::builtins;
+pub use builtins::{builtins, cost_builtins, federation_builtins, relay_builtins};
 pub use compiler::Compiler;
 pub use dependency::{Consumer, Dependency, Producer};
 pub use depgraph::DepGraph;
+pub use litho_validation::{Complexity, Limits, Lints, Rule, RuleContext};
+pub use project::Project;
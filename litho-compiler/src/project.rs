@@ -0,0 +1,85 @@
+use glob::Pattern;
+
+use super::{builtins, cost_builtins, federation_builtins, relay_builtins};
+
+/// Named project in a workspace with several schemas. Each project has its
+/// own schema, documents and builtins, which are compiled separately from
+/// those of other projects.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize), serde(default))]
+pub struct Project {
+    /// Name of this project.
+    pub name: String,
+
+    /// Glob patterns of the files that contain the schema of this project.
+    pub schema: Vec<String>,
+
+    /// Glob patterns of the files that contain the operations and fragments
+    /// of this project.
+    pub documents: Vec<String>,
+
+    /// Paths of the files that are generated for this project.
+    pub output: Vec<String>,
+
+    /// Enables the builtin `@cost` and `@listSize` directives.
+    pub cost: bool,
+
+    /// Enables the builtin Apollo Federation directives.
+    pub federation: bool,
+
+    /// Enables the builtin Relay directives.
+    pub relay: bool,
+}
+
+impl Project {
+    /// Returns the glob patterns of all files in this project.
+    pub fn patterns(&self) -> impl Iterator<Item = &str> {
+        self.schema
+            .iter()
+            .chain(self.documents.iter())
+            .map(String::as_str)
+    }
+
+    /// Returns a boolean that indicates if the given path (relative to the
+    /// root of the workspace) belongs to this project. Patterns that don't
+    /// contain wildcards also match all files in a directory.
+    pub fn includes(&self, path: &str) -> bool {
+        let path = path.trim_start_matches("./");
+
+        self.patterns().any(|pattern| {
+            let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+
+            path.strip_prefix(pattern)
+                .map(|rest| rest.is_empty() || rest.starts_with('/'))
+                .unwrap_or_default()
+                || Pattern::new(pattern)
+                    .map(|pattern| pattern.matches(path))
+                    .unwrap_or_default()
+        })
+    }
+
+    /// Returns the builtin documents of this project.
+    pub fn builtins(&self) -> impl Iterator<Item = (&'static str, &'static str)> {
+        let cost_builtins = match self.cost {
+            true => cost_builtins(),
+            false => &[],
+        };
+
+        let federation_builtins = match self.federation {
+            true => federation_builtins(),
+            false => &[],
+        };
+
+        let relay_builtins = match self.relay {
+            true => relay_builtins(),
+            false => &[],
+        };
+
+        builtins()
+            .iter()
+            .chain(cost_builtins)
+            .chain(federation_builtins)
+            .chain(relay_builtins)
+            .copied()
+    }
+}
@@ -0,0 +1,59 @@
+use super::{DiagnosticInfo, Severity};
+
+/// Diagnostic that is emitted by a custom validation rule. Unlike the builtin
+/// diagnostics, its code, message and severity are chosen by the rule that
+/// emits it.
+#[derive(Clone, Debug)]
+pub struct Custom<S>
+where
+    S: Copy,
+{
+    /// Code of this diagnostic, which should not collide with the codes of
+    /// builtin diagnostics.
+    pub code: &'static str,
+
+    /// Message of this diagnostic.
+    pub message: &'static str,
+
+    /// Severity of this diagnostic.
+    pub severity: Severity,
+
+    /// Primary span that triggered this diagnostic.
+    pub span: S,
+
+    /// Additional labels: pairs of spans and explanations.
+    pub labels: Vec<(S, String)>,
+}
+
+impl<S> DiagnosticInfo<S> for Custom<S>
+where
+    S: Copy,
+{
+    fn code(&self) -> &'static str {
+        self.code
+    }
+
+    fn message(&self) -> &'static str {
+        self.message
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn span(&self) -> S {
+        self.span
+    }
+
+    fn labels(&self) -> Vec<(S, String)> {
+        self.labels.clone()
+    }
+
+    fn suggestions(&self) -> &[String] {
+        &[]
+    }
+
+    fn is_deprecated(&self) -> bool {
+        false
+    }
+}
@@ -1,3 +1,15 @@
+/// Severity of a diagnostic. Errors prevent the compiler from advancing to the
+/// next phase, whereas warnings are only reported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    /// Diagnostic that must be resolved.
+    Error,
+
+    /// Diagnostic that should be looked at, but doesn't have to be resolved.
+    Warning,
+}
+
 /// Trait implemented by all different diagnostic types that Litho uses.
 pub trait DiagnosticInfo<S> {
     /// Returns the code of this diagnostic. This is usually a letter (e.g. `E`)
@@ -7,6 +19,9 @@ pub trait DiagnosticInfo<S> {
     /// Returns the message of this diagnostic.
     fn message(&self) -> &'static str;
 
+    /// Returns the severity of this diagnostic.
+    fn severity(&self) -> Severity;
+
     /// Returns the primary span that triggered this diagnostic.
     fn span(&self) -> S;
 
@@ -14,6 +29,10 @@ pub trait DiagnosticInfo<S> {
     /// explanations.
     fn labels(&self) -> Vec<(S, String)>;
 
+    /// Returns names that are similar to the (undefined) name that triggered
+    /// this diagnostic, ordered from most to least similar.
+    fn suggestions(&self) -> &[String];
+
     /// Returns a boolean that indicates if this diagnostic is deprecated.
     /// Deprecated diagnostics are no longer returned but still part of the docs
     /// for historic purposes.
@@ -21,30 +40,60 @@ pub trait DiagnosticInfo<S> {
 }
 
 macro_rules! deprecated {
-    (@deprecated) => {
+    (@deprecated $(@$rest:ident)*) => {
         true
     };
+    (@$other:ident $(@$rest:ident)*) => {
+        deprecated!($(@$rest)*)
+    };
     () => {
         false
     };
 }
 
+macro_rules! severity {
+    (@warning $(@$rest:ident)*) => {
+        $crate::Severity::Warning
+    };
+    (@$other:ident $(@$rest:ident)*) => {
+        severity!($(@$rest)*)
+    };
+    () => {
+        $crate::Severity::Error
+    };
+}
+
+macro_rules! suggestions {
+    ($self:expr; $suggestions:ident) => {
+        &$self.$suggestions
+    };
+    ($self:expr;) => {
+        &[]
+    };
+}
+
 macro_rules! diagnostics {
     ($(
-        $code:ident => $name:ident @ $span:ident $(+ $($var:ident),*)? {
+        $code:ident => $name:ident @ $span:ident $(+ $($var:ident),*)? $(? $suggestions:ident)? {
             $message:literal,
             $(
                 $label:literal @ $label_span:ident
             ),*
-        } $(@$directive:ident)?
+        } $(@$directive:ident)*
     ),*) => {
         /// Enum that contains all possible diagnostics that Litho can return.
         #[derive(Clone, Debug)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         pub enum Diagnostic<S> where S: Copy {
             $(
                 #[doc = concat!("(", stringify!($code), ") ", $message)]
                 $name($name<S>),
             )*
+
+            /// Diagnostic that is emitted by a custom validation rule. These
+            /// can't be serialized because their code and message are static.
+            #[cfg_attr(feature = "serde", serde(skip))]
+            Custom($crate::Custom<S>),
         }
 
         impl<S> Diagnostic<S> where S: Copy {
@@ -53,12 +102,14 @@ macro_rules! diagnostics {
                     #[doc = concat!("(", stringify!($code), ") ", $message)]
                     pub fn [<$name:snake>](
                         $($($var: String,)*)?
+                        $($suggestions: Vec<String>,)?
                         $(
                             $label_span: S,
                         )*
                     ) -> Diagnostic<S> {
                         Diagnostic::$name($name {
                             $($($var,)*)?
+                            $($suggestions,)?
                             $(
                                 $label_span,
                             )*
@@ -67,11 +118,67 @@ macro_rules! diagnostics {
                 )*
             }
 
+            /// Diagnostic that is emitted by a custom validation rule, with a
+            /// code, message and severity that are chosen by that rule.
+            pub fn custom(
+                code: &'static str,
+                message: &'static str,
+                severity: Severity,
+                span: S,
+                labels: Vec<(S, String)>,
+            ) -> Diagnostic<S> {
+                Diagnostic::Custom($crate::Custom {
+                    code,
+                    message,
+                    severity,
+                    span,
+                    labels,
+                })
+            }
+
+            /// Returns a copy of this diagnostic with all of its spans mapped by
+            /// the given function.
+            pub fn map<U, F>(&self, mut f: F) -> Diagnostic<U>
+            where
+                U: Copy,
+                F: FnMut(S) -> U,
+            {
+                match self {
+                    $(
+                        Diagnostic::$name(diagnostic) => Diagnostic::$name($name {
+                            $($($var: diagnostic.$var.clone(),)*)?
+                            $($suggestions: diagnostic.$suggestions.clone(),)?
+                            $(
+                                $label_span: f(diagnostic.$label_span),
+                            )*
+                        }),
+                    )*
+                    Diagnostic::Custom(diagnostic) => Diagnostic::Custom($crate::Custom {
+                        code: diagnostic.code,
+                        message: diagnostic.message,
+                        severity: diagnostic.severity,
+                        span: f(diagnostic.span),
+                        labels: diagnostic
+                            .labels
+                            .iter()
+                            .map(|(span, message)| (f(*span), message.clone()))
+                            .collect(),
+                    }),
+                }
+            }
+
+            /// Returns a boolean that indicates if this diagnostic is emitted
+            /// by a custom validation rule.
+            pub fn is_custom(&self) -> bool {
+                matches!(self, Diagnostic::Custom(_))
+            }
+
             /// Returns the code of this diagnostic. This is usually a letter (e.g. `E`)
             /// followed by 4 digits that represent the error number.
             pub fn code(&self) -> &'static str {
                 match self {
                     $(Diagnostic::$name(diagnostic) => diagnostic.code(),)*
+                    Diagnostic::Custom(diagnostic) => diagnostic.code(),
                 }
             }
 
@@ -79,6 +186,15 @@ macro_rules! diagnostics {
             pub fn message(&self) -> &'static str {
                 match self {
                     $(Diagnostic::$name(diagnostic) => diagnostic.message(),)*
+                    Diagnostic::Custom(diagnostic) => diagnostic.message(),
+                }
+            }
+
+            /// Returns the severity of this diagnostic.
+            pub fn severity(&self) -> Severity {
+                match self {
+                    $(Diagnostic::$name(diagnostic) => diagnostic.severity(),)*
+                    Diagnostic::Custom(diagnostic) => diagnostic.severity(),
                 }
             }
 
@@ -86,6 +202,7 @@ macro_rules! diagnostics {
             pub fn span(&self) -> S {
                 match self {
                     $(Diagnostic::$name(diagnostic) => diagnostic.span(),)*
+                    Diagnostic::Custom(diagnostic) => diagnostic.span(),
                 }
             }
 
@@ -94,6 +211,34 @@ macro_rules! diagnostics {
             pub fn labels(&self) -> Vec<(S, String)> {
                 match self {
                     $(Diagnostic::$name(diagnostic) => diagnostic.labels(),)*
+                    Diagnostic::Custom(diagnostic) => diagnostic.labels(),
+                }
+            }
+
+            /// Returns names that are similar to the (undefined) name that
+            /// triggered this diagnostic, ordered from most to least similar.
+            pub fn suggestions(&self) -> &[String] {
+                match self {
+                    $(Diagnostic::$name(diagnostic) => diagnostic.suggestions(),)*
+                    Diagnostic::Custom(diagnostic) => diagnostic.suggestions(),
+                }
+            }
+
+            /// Returns a help message that lists the suggestions of this
+            /// diagnostic (if any).
+            pub fn help(&self) -> Option<String> {
+                let names = self
+                    .suggestions()
+                    .iter()
+                    .map(|name| format!("`{}`", name))
+                    .collect::<Vec<_>>();
+
+                match names.split_last() {
+                    None => None,
+                    Some((last, [])) => Some(format!("Did you mean {}?", last)),
+                    Some((last, rest)) => {
+                        Some(format!("Did you mean {} or {}?", rest.join(", "), last))
+                    }
                 }
             }
         }
@@ -102,11 +247,16 @@ macro_rules! diagnostics {
             #[allow(rustdoc::bare_urls)]
             #[doc = concat!("(", stringify!($code), ") ", $message)]
             #[derive(Clone, Debug)]
+            #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
             pub struct $name<S> where S: Copy {
                 $($(
                     #[doc = concat!("Value of `{", stringify!($var), "}` that is referenced in the message and/or one of the labels.")]
                     pub $var: String,
                 )*)?
+                $(
+                    #[doc = "Names that are similar to the name that triggered this diagnostic."]
+                    pub $suggestions: Vec<String>,
+                )?
                 $(
                     #[doc = $label]
                     pub $label_span: S,
@@ -122,12 +272,16 @@ macro_rules! diagnostics {
                     $message
                 }
 
+                fn severity(&self) -> Severity {
+                    severity!($(@$directive)*)
+                }
+
                 fn span(&self) -> S {
                     self.$span
                 }
 
                 fn labels(&self) -> Vec<(S, String)> {
-                    let $name { $($($var,)*)? $($label_span,)* } = &self;
+                    let $name { $($($var,)*)? $($label_span,)* .. } = &self;
 
                     vec![
                         $(
@@ -139,8 +293,12 @@ macro_rules! diagnostics {
                     ]
                 }
 
+                fn suggestions(&self) -> &[String] {
+                    suggestions!(self; $($suggestions)?)
+                }
+
                 fn is_deprecated(&self) -> bool {
-                    deprecated!($(@$directive)?)
+                    deprecated!($(@$directive)*)
                 }
             }
         )*
@@ -40,8 +40,10 @@
 
 #[macro_use]
 mod dsl;
+mod custom;
 
-pub use dsl::DiagnosticInfo;
+pub use custom::Custom;
+pub use dsl::{DiagnosticInfo, Severity};
 
 diagnostics! {
     E0001 => UnrecognizedTokens @ span {
@@ -288,7 +290,7 @@ diagnostics! {
         "This directive definition here is missing a location." @ span
     },
 
-    E0100 => UnknownNamedType @ span + name {
+    E0100 => UnknownNamedType @ span + name ? suggestions {
         "Named type must exist.",
         "Type `{name}` is referenced to here but never defined." @ span
     },
@@ -404,16 +406,65 @@ diagnostics! {
         "Type `{name}` is first defined here ..." @ first,
         "... and later again here." @ second
     },
-    E0126 => DuplicateDirectiveName @ second + name {
-        "Directive name must be unique.",
-        "Directive `{name}` is first defined here ..." @ first,
-        "... and later again here." @ second
-    },
     E0127 => DifferentExtensionType @ second + name, first_type, second_type {
         "Type extension must extend type of same kind.",
         "Type `{name}` is first defined as `{first_type}` here ..." @ first,
         "... and later again as `{second_type}` here." @ second
     },
+    E0128 => MissingQueryRootOperationType @ span {
+        "Schema must define a query root operation type.",
+        "This schema definition doesn't define a root operation type for `query`." @ span
+    },
+    E0129 => NonObjectRootOperationType @ span + operation, name {
+        "Root operation type must be an object type.",
+        "Type `{name}` is used here as the `{operation}` root operation type, but it is not an object type." @ span
+    },
+    E0130 => DuplicateDirectiveName @ second + name {
+        "Directive name must be unique.",
+        "Directive `{name}` is first defined here ..." @ first,
+        "... and later again here." @ second
+    },
+    E0131 => UndefinedExtendedType @ span + name ? suggestions {
+        "Extended type must exist.",
+        "Type `{name}` is extended here but never defined." @ span
+    },
+    E0132 => DuplicateRootOperationType @ second + operation {
+        "Root operation type must be defined only once.",
+        "Root operation type for `{operation}` is first defined here ..." @ first,
+        "... and later again here." @ second
+    },
+    E0133 => CyclicInterfaceImplementation @ span + name, interface {
+        "Interface implementations must not form cycles.",
+        "Interface `{name}` implements `{interface}` here, which in turn implements `{name}`." @ span
+    },
+    E0134 => NonNullOneOfInputField @ span + ty, name, field_ty {
+        "Fields of oneOf input objects must be nullable.",
+        "Field `{name}` of oneOf input object `{ty}` has non-null type `{field_ty}` here." @ span
+    },
+    E0135 => OneOfInputFieldDefaultValue @ span + ty, name {
+        "Fields of oneOf input objects must not have a default value.",
+        "Field `{name}` of oneOf input object `{ty}` has a default value here." @ span
+    },
+    E0136 => InvalidFieldSet @ span + directive {
+        "Field set must be a valid selection set.",
+        "Field set of directive `@{directive}` here can't be parsed as a selection set." @ span
+    },
+    E0137 => UndefinedFieldSetField @ span + directive, ty, field ? suggestions {
+        "Fields in field set must exist on the owning type.",
+        "Field set of directive `@{directive}` here selects field `{field}`, but type `{ty}` has no such field." @ span
+    },
+    E0138 => MissingFieldSetSelectionSet @ span + directive, ty, field {
+        "Field of composite type in field set must have selection set.",
+        "Field set of directive `@{directive}` here selects field `{field}` of composite type `{ty}` without a selection set." @ span
+    },
+    E0139 => UnexpectedFieldSetSelectionSet @ span + directive, ty, field {
+        "Field of scalar type in field set must not have selection set.",
+        "Field set of directive `@{directive}` here selects field `{field}` of scalar type `{ty}` with a selection set." @ span
+    },
+    E0140 => ImportFailed @ span + url, error {
+        "Imported schema couldn't be loaded.",
+        "Importing `{url}` here failed: {error}" @ span
+    },
     E0200 => ExpectedNonNullValue @ span + ty {
         "Expected a non-null value.",
         "This should be a `{ty}` here." @ span
@@ -446,11 +497,11 @@ diagnostics! {
         "Expected a value for required input field.",
         "This input object here must have a field `{name}` of type `{ty}`." @ span
     },
-    E0208 => UnrecognizedInputField @ span + name, ty {
+    E0208 => UnrecognizedInputField @ span + name, ty ? suggestions {
         "All fields in input object must exist in schema definition.",
         "Value is provided for field `{name}` here, but type `{ty}` has no such field." @ span
     },
-    E0209 => UnrecognizedEnumValue @ span + name, value {
+    E0209 => UnrecognizedEnumValue @ span + name, value ? suggestions {
         "Enum values must exist in schema definition.",
         "Enum value `{value}` here is not a valid value for enum `{name}`." @ span
     },
@@ -458,6 +509,31 @@ diagnostics! {
         "Expected an enum value.",
         "This should be a `{ty}` here." @ span
     },
+    E0211 => DuplicateInputField @ second + name {
+        "Input object fields must be unique.",
+        "Field `{name}` is first provided here ..." @ first,
+        "... and later again here." @ second
+    },
+    E0212 => InvalidOneOfInputFieldCount @ span + ty, count {
+        "OneOf input objects must have exactly one field.",
+        "Input object of oneOf type `{ty}` here has {count} fields." @ span
+    },
+    E0213 => NullOneOfInputField @ span + ty, name {
+        "Field of oneOf input object must not be null.",
+        "Field `{name}` of oneOf input object `{ty}` is null here." @ span
+    },
+    E0214 => IntValueOutOfRange @ span + value {
+        "Int values must be representable as 32-bit signed integers.",
+        "Value `{value}` here is out of range for `Int`." @ span
+    },
+    E0215 => InvalidScalarLiteral @ span + ty, format {
+        "Literals of custom scalars must match their format.",
+        "This should be {format} for scalar `{ty}` here." @ span
+    },
+    E0216 => InvalidScalarFormatPattern @ span + pattern {
+        "Scalar format patterns must be valid regular expressions.",
+        "Pattern `{pattern}` here is not a valid regular expression." @ span
+    },
     E0300 => DuplicateOperationName @ second + name {
         "Operation definitions must be unique.",
         "Operation `{name}` is first defined here ..." @ first,
@@ -467,7 +543,11 @@ diagnostics! {
         "Anonymous operation definitions must be alone.",
         "Anonymous operation defined here must not coexist with named operation definitions." @ span
     },
-    E0303 => UndefinedField @ span + ty, field {
+    E0302 => MultipleSubscriptionRootFields @ span + name {
+        "Subscription operations must have exactly one root field.",
+        "Field `{name}` is selected here in addition to another root field." @ span
+    },
+    E0303 => UndefinedField @ span + ty, field ? suggestions {
         "Queried field does not exist.",
         "Type `{ty}` does not have a field named `{field}`." @ span
     },
@@ -499,7 +579,7 @@ diagnostics! {
         "Field of scalar-like type must not have a selection set.",
         "Field `{field}` of scalar-like type `{ty}` here must not have a selection set." @ span
     },
-    E0310 => UndefinedArgument @ span + name {
+    E0310 => UndefinedArgument @ span + name ? suggestions {
         "Argument must exist in schema.",
         "Argument `{name}` here is not defined in schema." @ span
     },
@@ -525,7 +605,7 @@ diagnostics! {
         "Fragment definitions must be used.",
         "Fragment `{name}` is defined here but never used." @ span
     },
-    E0316 => UndefinedFragment @ span + name {
+    E0316 => UndefinedFragment @ span + name ? suggestions {
         "Fragment definition must be defined.",
         "Fragment `{name}` is used here but never defined." @ span
     },
@@ -537,7 +617,7 @@ diagnostics! {
         "Fragment spread must be possible.",
         "Fragment assumes type `{ty}`, but scope is type `{scope}`." @ span
     },
-    E0319 => UndefinedDirective @ span + name {
+    E0319 => UndefinedDirective @ span + name ? suggestions {
         "Directive must be defined.",
         "Directive `@{name}` is refered to here, but never defined." @ span
     },
@@ -586,27 +666,173 @@ diagnostics! {
     E0329 => UnsupportedOperation @ span + name {
         "Operation must be defined in schema before it can be used.",
         "Schema doesn't have a `{name}` type. You might be interested in using `@litho(url: \"...\")` to automatically import your existing schema. Learn more at https://litho.dev/docs/operations/import-schemas/." @ span
-    }
+    },
+    E0330 => IntrospectionSubscriptionRootField @ span + name {
+        "Subscription root field must not be an introspection field.",
+        "Introspection field `{name}` is selected here as the root field of a subscription." @ span
+    },
+    E0331 => NullableOneOfVariable @ second + name, ty, input {
+        "Variables used for fields of oneOf input objects must be non-null.",
+        "Variable `${name}` is defined here as nullable type `{ty}` ..." @ first,
+        "... but is used here for a field of oneOf input object `{input}`." @ second
+    },
+    E0332 => StreamOnNonListField @ span + name, ty {
+        "Directive `@stream` must only be used on list fields.",
+        "Field `{name}` here is of type `{ty}`, which is not a list." @ span
+    },
+    E0333 => DuplicateDeferStreamLabel @ second + label {
+        "Labels of `@defer` and `@stream` directives must be unique.",
+        "Label `{label}` is first used here ..." @ first,
+        "... and later again here." @ second
+    },
+    E0334 => DeferStreamOnSubscriptionRoot @ span + directive {
+        "Directives `@defer` and `@stream` must not be used on the root of a subscription.",
+        "Directive `@{directive}` is used here on the root selection set of a subscription." @ span
+    },
+    E0335 => InvalidFragmentArgumentDefinition @ span + fragment, name {
+        "Fragment argument definitions must be objects with a valid `type` string.",
+        "Argument `{name}` of fragment `{fragment}` is defined here, but its definition has no `type` string that parses as a type." @ span
+    },
+    E0336 => UndefinedFragmentArgument @ span + fragment, name ? suggestions {
+        "Fragment arguments must be defined.",
+        "Argument `{name}` is passed to fragment `{fragment}` here, but the fragment never defines it in `@argumentDefinitions`." @ span
+    },
+    E0337 => MissingRequiredFragmentArgument @ span + fragment, name, ty {
+        "Required fragment arguments must be passed.",
+        "Fragment `{fragment}` is spread here, but its required argument `{name}` of type `{ty}` is missing." @ span
+    },
+    E0338 => InvalidConnectionField @ span + name, ty, field {
+        "Fields with `@connection` must return a connection type.",
+        "Field `{name}` is annotated with `@connection` here, but its type `{ty}` has no valid `{field}` field." @ span
+    },
+    E0339 => MissingConnectionArguments @ span + name {
+        "Fields with `@connection` must accept pagination arguments.",
+        "Field `{name}` is annotated with `@connection` here, but it accepts neither `first` nor `last` arguments." @ span
+    },
+    E0340 => OperationExceedsMaxDepth @ span + depth, max {
+        "Operation must not exceed the maximum depth.",
+        "This operation has a depth of {depth}, which exceeds the maximum of {max}." @ span
+    },
+    E0341 => OperationExceedsMaxFields @ span + fields, max {
+        "Operation must not exceed the maximum number of fields.",
+        "This operation selects {fields} fields, which exceeds the maximum of {max}." @ span
+    },
+    E0342 => OperationExceedsMaxAliases @ span + aliases, max {
+        "Operation must not exceed the maximum number of aliases.",
+        "This operation uses {aliases} aliases, which exceeds the maximum of {max}." @ span
+    },
+    E0343 => OperationExceedsMaxCost @ span + cost, max {
+        "Operation must not exceed the maximum cost.",
+        "This operation has an estimated cost of {cost}, which exceeds the maximum of {max}." @ span
+    },
+    E0344 => DeferStreamLabelIsVariable @ span + directive, variable {
+        "Labels of `@defer` and `@stream` directives must be static strings.",
+        "Directive `@{directive}` is labeled with variable `${variable}` here, so its label can't be checked for uniqueness." @ span
+    },
+    W0300 => DeprecatedField @ span + name, reason {
+        "Field is deprecated.",
+        "Field `{name}` is deprecated: {reason}" @ span
+    } @warning,
+    W0301 => DeprecatedArgument @ span + name, reason {
+        "Argument is deprecated.",
+        "Argument `{name}` is deprecated: {reason}" @ span
+    } @warning,
+    W0302 => DeprecatedEnumValue @ span + name, value, reason {
+        "Enum value is deprecated.",
+        "Enum value `{value}` of enum `{name}` is deprecated: {reason}" @ span
+    } @warning,
+    W0303 => DeprecatedInputField @ span + name, ty, reason {
+        "Input field is deprecated.",
+        "Input field `{name}` of type `{ty}` is deprecated: {reason}" @ span
+    } @warning,
+
+    L0001 => TypeNameNotPascalCase @ span + name {
+        "Type names should be PascalCase.",
+        "Type `{name}` is not PascalCase." @ span
+    } @warning,
+    L0002 => FieldNameNotCamelCase @ span + name {
+        "Field names should be camelCase.",
+        "Field `{name}` is not camelCase." @ span
+    } @warning,
+    L0003 => ArgumentNameNotCamelCase @ span + name {
+        "Argument names should be camelCase.",
+        "Argument `{name}` is not camelCase." @ span
+    } @warning,
+    L0004 => EnumValueNotScreamingSnakeCase @ span + name {
+        "Enum values should be SCREAMING_SNAKE_CASE.",
+        "Enum value `{name}` is not SCREAMING_SNAKE_CASE." @ span
+    } @warning,
+    L0005 => InputObjectWithoutInputSuffix @ span + name {
+        "Input object names should end with `Input`.",
+        "Input object `{name}` does not end with `Input`." @ span
+    } @warning,
+    L0006 => QueryFieldWithGetPrefix @ span + name {
+        "Query fields should not start with `get`.",
+        "Query field `{name}` starts with `get`." @ span
+    } @warning,
+    L0007 => MissingTypeDescription @ span + name {
+        "Types should have a description.",
+        "Type `{name}` is missing a description." @ span
+    } @warning,
+    L0008 => MissingFieldDescription @ span + name {
+        "Fields should have a description.",
+        "Field `{name}` is missing a description." @ span
+    } @warning,
+    L0009 => MissingConnectionTypeField @ span + ty, field {
+        "Connection types should define the fields required by the Relay Cursor Connections spec.",
+        "Type `{ty}` is missing field `{field}`." @ span
+    } @warning,
+    L0010 => InvalidConnectionTypeField @ span + ty, field, expected {
+        "Fields of connection types should have the types required by the Relay Cursor Connections spec.",
+        "Field `{field}` of type `{ty}` should return {expected}." @ span
+    } @warning,
+    L0011 => MissingPaginationArguments @ span + name, ty {
+        "Fields that return connection types should accept pagination arguments.",
+        "Field `{name}` returns connection type `{ty}`, but accepts neither `first` and `after` nor `last` and `before`." @ span
+    } @warning,
+    L0012 => InvalidPaginationArgument @ span + name, argument, expected {
+        "Pagination arguments should have the types required by the Relay Cursor Connections spec.",
+        "Argument `{argument}` of field `{name}` should be of type {expected}." @ span
+    } @warning,
+    L0013 => UnreachableType @ span + name {
+        "Types should be reachable from a root operation type.",
+        "Type `{name}` is defined here, but can't be reached from any root operation type." @ span
+    } @warning,
+    L0014 => UnusedDirective @ span + name {
+        "Directives should be used.",
+        "Directive `@{name}` is defined here, but never used." @ span
+    } @warning,
+    L0015 => UnimplementedInterface @ span + name {
+        "Interfaces should be implemented.",
+        "Interface `{name}` is defined here, but no type implements it." @ span
+    } @warning
 }
 
 #[cfg(feature = "with-ariadne")]
 mod with_ariadne {
     use ariadne::{Label, Report, ReportKind, Span};
 
-    use super::Diagnostic;
+    use super::{Diagnostic, Severity};
 
     impl<S> Into<Report<S>> for Diagnostic<S>
     where
         S: Copy + Span,
     {
         fn into(self) -> Report<S> {
-            let mut builder = Report::build(
-                ReportKind::Error,
-                self.span().source().to_owned(),
-                self.span().start(),
-            )
-            .with_code(self.code())
-            .with_message(self.message());
+            let kind = match self.severity() {
+                Severity::Error => ReportKind::Error,
+                Severity::Warning => ReportKind::Warning,
+            };
+
+            let mut builder =
+                Report::build(kind, self.span().source().to_owned(), self.span().start())
+                    .with_code(self.code())
+                    .with_message(self.message());
+
+            if let Some(help) = self.help() {
+                builder.set_note(help);
+            }
+
             builder.add_labels(
                 self.labels()
                     .into_iter()
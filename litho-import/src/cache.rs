@@ -0,0 +1,48 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Last successful result of importing a url, which is used to revalidate the
+/// import (using its ETag) and as a fallback when the import fails.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CachedImport {
+    pub url: String,
+    pub etag: Option<String>,
+    pub sdl: String,
+}
+
+/// Directory that stores one cached import per url.
+#[derive(Clone, Debug)]
+pub struct ImportCache {
+    directory: PathBuf,
+}
+
+impl ImportCache {
+    pub fn new(directory: impl Into<PathBuf>) -> ImportCache {
+        ImportCache {
+            directory: directory.into(),
+        }
+    }
+
+    fn path(&self, url: &str) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        url.hash(&mut hasher);
+        self.directory
+            .join(format!("{:016x}.json", hasher.finish()))
+    }
+
+    pub fn get(&self, url: &str) -> Option<CachedImport> {
+        read_to_string(self.path(url))
+            .ok()
+            .and_then(|text| serde_json::from_str::<CachedImport>(&text).ok())
+            .filter(|import| import.url == url)
+    }
+
+    pub fn set(&self, import: &CachedImport) -> std::io::Result<()> {
+        create_dir_all(&self.directory)?;
+        write(self.path(&import.url), serde_json::to_string(import)?)
+    }
+}
@@ -14,18 +14,25 @@ pub struct Data {
 #[derive(Debug, Deserialize)]
 pub struct Schema {
     #[serde(rename = "queryType")]
-    pub query_type: Type,
+    pub query_type: RootType,
 
     #[serde(rename = "mutationType")]
-    pub mutation_type: Option<Type>,
+    pub mutation_type: Option<RootType>,
 
     #[serde(rename = "subscriptionType")]
-    pub subscription_type: Option<Type>,
+    pub subscription_type: Option<RootType>,
 
     pub types: Vec<Type>,
     pub directives: Vec<Directive>,
 }
 
+/// Root operation type, of which most introspection queries (other than ours)
+/// only select the name.
+#[derive(Debug, Deserialize)]
+pub struct RootType {
+    pub name: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Type {
     pub kind: TypeKind,
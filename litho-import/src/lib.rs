@@ -1,39 +1,368 @@
 use std::collections::HashMap;
+use std::env::var;
+use std::fs::read_to_string;
 use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::process::Command;
 
 use litho_language::fmt::Format;
 use litho_language::Document;
 
+mod cache;
 mod introspection;
 
-use introspection::Response;
-use reqwest::header::HeaderMap;
+pub use cache::{CachedImport, ImportCache};
 
+use introspection::{Response, Schema};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ETAG, IF_NONE_MATCH};
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+/// Prefix of urls that import the SDL that is printed by a shell command.
+pub const EXEC_PREFIX: &str = "litho+exec:";
+
+/// Resolves the url of an `@litho` import to the SDL of the imported schema.
 pub trait Importer {
     type Error: ToString;
     type Future<'a, T>: Future<Output = Result<T, Self::Error>> + 'a
     where
-        Self: 'a;
+        Self: 'a,
+        T: 'a;
+
+    fn import<'a, T>(
+        &'a self,
+        url: &'a str,
+        headers: &'a [(String, String)],
+    ) -> Self::Future<'a, T>
+    where
+        T: for<'b> From<&'b str> + Send + 'a;
+}
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, String>> + Send + 'a>>;
+
+/// Imports a schema by sending an introspection query to an HTTP endpoint.
+/// Header values can refer to environment variables with `${env:NAME}`. If a
+/// cache is given, the last successful result of each url is revalidated with
+/// its ETag and used when the endpoint can't be reached.
+#[derive(Default)]
+pub struct HttpImporter {
+    cache: Option<ImportCache>,
+}
+
+impl HttpImporter {
+    pub fn new(cache: Option<PathBuf>) -> HttpImporter {
+        HttpImporter {
+            cache: cache.map(ImportCache::new),
+        }
+    }
+
+    async fn fetch(&self, url: &str, headers: &[(String, String)]) -> Result<String, String> {
+        let headers = headers
+            .iter()
+            .map(|(name, value)| {
+                Ok((
+                    HeaderName::try_from(name.as_str()).map_err(|err| err.to_string())?,
+                    HeaderValue::try_from(interpolate(value)?).map_err(|err| err.to_string())?,
+                ))
+            })
+            .collect::<Result<HeaderMap, String>>()?;
+
+        let cached = self.cache.as_ref().and_then(|cache| cache.get(url));
+        let etag = cached.as_ref().and_then(|cached| cached.etag.as_deref());
+
+        match (fetch(url, headers, etag).await, cached) {
+            (Ok(Fetched::Modified { etag, sdl }), _) => {
+                if let Some(cache) = self.cache.as_ref() {
+                    let _ = cache.set(&CachedImport {
+                        url: url.to_owned(),
+                        etag,
+                        sdl: sdl.clone(),
+                    });
+                }
+
+                Ok(sdl)
+            }
+            (Ok(Fetched::NotModified), Some(cached)) | (Err(_), Some(cached)) => Ok(cached.sdl),
+            (Ok(Fetched::NotModified), None) => {
+                Err("server responded with 304 Not Modified to an uncached import".to_owned())
+            }
+            (Err(error), None) => Err(error),
+        }
+    }
+}
+
+impl Importer for HttpImporter {
+    type Error = String;
+    type Future<'a, T>
+        = BoxFuture<'a, T>
+    where
+        T: 'a;
+
+    fn import<'a, T>(&'a self, url: &'a str, headers: &'a [(String, String)]) -> Self::Future<'a, T>
+    where
+        T: for<'b> From<&'b str> + Send + 'a,
+    {
+        Box::pin(async move { self.fetch(url, headers).await.map(|sdl| T::from(&sdl)) })
+    }
+}
+
+/// Replaces each `${env:NAME}` in the given (header) value with the value of
+/// environment variable `NAME`, so that secrets don't end up in the schema.
+pub fn interpolate(value: &str) -> Result<String, String> {
+    let mut result = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${env:") {
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            return Err(format!("unterminated `${{env:` in `{}`", value));
+        };
+
+        let name = &rest[start + "${env:".len()..end];
+        let variable =
+            var(name).map_err(|_| format!("environment variable `{}` is not set", name))?;
+
+        result.push_str(&rest[..start]);
+        result.push_str(&variable);
+        rest = &rest[end + 1..];
+    }
+
+    result.push_str(rest);
+
+    Ok(result)
+}
+
+/// Imports a schema from a file that contains either SDL or the JSON result
+/// of an introspection query. Relative paths are resolved against the base
+/// directory (usually that of the document that contains the import).
+pub struct FileImporter {
+    base: PathBuf,
+}
+
+impl FileImporter {
+    pub fn new(base: impl Into<PathBuf>) -> FileImporter {
+        FileImporter { base: base.into() }
+    }
+
+    fn read<T>(&self, url: &str) -> Result<T, String>
+    where
+        T: for<'b> From<&'b str>,
+    {
+        let path = self.base.join(url.strip_prefix("file://").unwrap_or(url));
+        let text =
+            read_to_string(&path).map_err(|error| format!("{}: {}", path.display(), error))?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => introspect(&text),
+            _ => Ok(T::from(&text)),
+        }
+    }
+}
+
+impl Importer for FileImporter {
+    type Error = String;
+    type Future<'a, T>
+        = BoxFuture<'a, T>
+    where
+        T: 'a;
+
+    fn import<'a, T>(
+        &'a self,
+        url: &'a str,
+        _headers: &'a [(String, String)],
+    ) -> Self::Future<'a, T>
+    where
+        T: for<'b> From<&'b str> + Send + 'a,
+    {
+        Box::pin(async move { self.read(url) })
+    }
+}
 
-    fn import<'a, T>(&'a self, path: &'a str) -> Self::Future<'a, T>;
+/// Imports a schema from the output of a shell command (e.g. a script that
+/// prints the SDL of a code-first schema), which runs in the base directory.
+pub struct ExecImporter {
+    base: PathBuf,
+}
+
+impl ExecImporter {
+    pub fn new(base: impl Into<PathBuf>) -> ExecImporter {
+        ExecImporter { base: base.into() }
+    }
+
+    fn exec<T>(&self, command: &str) -> Result<T, String>
+    where
+        T: for<'b> From<&'b str>,
+    {
+        let mut shell = shell(command);
+
+        if !self.base.as_os_str().is_empty() {
+            shell.current_dir(&self.base);
+        }
+
+        let output = shell
+            .output()
+            .map_err(|error| format!("`{}`: {}", command, error))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "`{}` exited with {}: {}",
+                command,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(T::from(&String::from_utf8_lossy(&output.stdout)))
+    }
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.args(["/C", command]);
+    shell
+}
+
+#[cfg(not(windows))]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.args(["-c", command]);
+    shell
+}
+
+impl Importer for ExecImporter {
+    type Error = String;
+    type Future<'a, T>
+        = BoxFuture<'a, T>
+    where
+        T: 'a;
+
+    fn import<'a, T>(
+        &'a self,
+        url: &'a str,
+        _headers: &'a [(String, String)],
+    ) -> Self::Future<'a, T>
+    where
+        T: for<'b> From<&'b str> + Send + 'a,
+    {
+        Box::pin(async move { self.exec(url.strip_prefix(EXEC_PREFIX).unwrap_or(url)) })
+    }
+}
+
+/// Importer that picks one of the other importers based on the url: HTTP(S)
+/// urls are introspected, `litho+exec:` urls are executed (only if `exec` is
+/// enabled, since that runs arbitrary commands) and everything else is read
+/// from disk.
+pub struct DefaultImporter {
+    http: HttpImporter,
+    file: FileImporter,
+    exec: Option<ExecImporter>,
+}
+
+impl DefaultImporter {
+    pub fn new(base: impl AsRef<Path>, cache: Option<PathBuf>, exec: bool) -> DefaultImporter {
+        DefaultImporter {
+            http: HttpImporter::new(cache),
+            file: FileImporter::new(base.as_ref()),
+            exec: exec.then(|| ExecImporter::new(base.as_ref())),
+        }
+    }
+}
+
+impl Importer for DefaultImporter {
+    type Error = String;
+    type Future<'a, T>
+        = BoxFuture<'a, T>
+    where
+        T: 'a;
+
+    fn import<'a, T>(&'a self, url: &'a str, headers: &'a [(String, String)]) -> Self::Future<'a, T>
+    where
+        T: for<'b> From<&'b str> + Send + 'a,
+    {
+        if url.starts_with("http://") || url.starts_with("https://") {
+            self.http.import(url, headers)
+        } else if url.starts_with(EXEC_PREFIX) {
+            match self.exec.as_ref() {
+                Some(exec) => exec.import(url, headers),
+                None => Box::pin(async move {
+                    Err(format!(
+                        "`{}` imports run commands and must be enabled explicitly",
+                        EXEC_PREFIX
+                    ))
+                }),
+            }
+        } else {
+            self.file.import(url, headers)
+        }
+    }
+}
+
+/// Converts the JSON result of an introspection query (with or without the
+/// surrounding `data` field) into SDL.
+pub fn introspect<T>(json: &str) -> Result<T, String>
+where
+    T: for<'a> From<&'a str>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Introspection {
+        Response(Response),
+        Data {
+            #[serde(rename = "__schema")]
+            schema: Schema,
+        },
+    }
+
+    let schema = match serde_json::from_str(json).map_err(|err| err.to_string())? {
+        Introspection::Response(response) => response.data.schema,
+        Introspection::Data { schema } => schema,
+    };
+
+    let node: Document<String> = schema.into();
+
+    Ok(T::from(&node.format_to_string(80)))
 }
 
 pub async fn import<T>(url: &str, headers: HeaderMap) -> Result<T, String>
 where
     T: for<'a> From<&'a str>,
 {
+    match fetch(url, headers, None).await? {
+        Fetched::Modified { sdl, .. } => Ok(T::from(&sdl)),
+        Fetched::NotModified => Err("server responded with 304 Not Modified".to_owned()),
+    }
+}
+
+enum Fetched {
+    Modified { etag: Option<String>, sdl: String },
+    NotModified,
+}
+
+async fn fetch(url: &str, headers: HeaderMap, etag: Option<&str>) -> Result<Fetched, String> {
     let mut params = HashMap::new();
     params.insert("query", include_str!("../introspection.graphql"));
     params.insert("operationName", "IntrospectionQuery");
 
     let client = reqwest::Client::new();
-    let response = client
-        .post(url)
-        .headers(headers)
-        .json(&params)
-        .send()
-        .await
-        .map_err(|err| err.to_string())?;
+    let mut request = client.post(url).headers(headers).json(&params);
+
+    if let Some(etag) = etag {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+
+    let response = request.send().await.map_err(|err| err.to_string())?;
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        return Ok(Fetched::NotModified);
+    }
+
+    let response = response.error_for_status().map_err(|err| err.to_string())?;
+    let etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|etag| etag.to_str().ok())
+        .map(ToOwned::to_owned);
     let json = response
         .json::<Response>()
         .await
@@ -41,11 +370,83 @@ where
 
     let node: Document<String> = json.data.schema.into();
 
-    Ok(T::from(&node.format_to_string(80)))
+    Ok(Fetched::Modified {
+        etag,
+        sdl: node.format_to_string(80),
+    })
 }
 
 #[cfg(test)]
 mod tests {
+    use std::env::{set_var, temp_dir};
+    use std::fs::{create_dir_all, remove_dir_all, write};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{spawn, JoinHandle};
+
+    use super::{interpolate, DefaultImporter, ExecImporter, FileImporter, HttpImporter, Importer};
+
+    const INTROSPECTION: &str = r#"{"data":{"__schema":{
+        "queryType":{"name":"Query"},
+        "types":[{"kind":"OBJECT","name":"Query","fields":[{
+            "name":"hello","args":[],"type":{"kind":"SCALAR","name":"String"},"isDeprecated":false
+        }],"interfaces":[]}],
+        "directives":[]
+    }}}"#;
+
+    const NOT_MODIFIED: &str =
+        "HTTP/1.1 304 Not Modified\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+    fn ok(etag: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nETag: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            etag,
+            INTROSPECTION.len(),
+            INTROSPECTION
+        )
+    }
+
+    /// Starts a stub server that sends the given responses (one per request)
+    /// and returns the headers of each request it received.
+    fn serve(responses: Vec<String>) -> (String, JoinHandle<Vec<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+
+        let handle = spawn(move || {
+            responses
+                .into_iter()
+                .map(|response| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut headers = vec![];
+                    let mut length = 0;
+
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        let line = line.trim_end().to_owned();
+
+                        if line.is_empty() {
+                            break;
+                        }
+
+                        if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                            length = value.trim().parse().unwrap();
+                        }
+
+                        headers.push(line);
+                    }
+
+                    reader.take(length).read_to_end(&mut vec![]).unwrap();
+                    stream.write_all(response.as_bytes()).unwrap();
+                    headers
+                })
+                .collect()
+        });
+
+        (url, handle)
+    }
+
     #[tokio::test]
     async fn test_import() {
         let result =
@@ -54,4 +455,118 @@ mod tests {
                 .unwrap();
         eprintln!("{}", result);
     }
+
+    #[tokio::test]
+    async fn test_cached_import() {
+        let cache = temp_dir().join(format!("litho-import-{}", std::process::id()));
+        let importer = HttpImporter::new(Some(cache.clone()));
+
+        let (url, handle) = serve(vec![ok("\"v1\""), NOT_MODIFIED.to_owned()]);
+        let first = importer.import::<String>(&url, &[]).await.unwrap();
+        let second = importer.import::<String>(&url, &[]).await.unwrap();
+        let requests = handle.join().unwrap();
+
+        assert!(first.contains("hello: String"));
+        assert_eq!(first, second);
+        assert!(!requests[0]
+            .iter()
+            .any(|header| header.starts_with("if-none-match")));
+        assert!(requests[1]
+            .iter()
+            .any(|header| header == "if-none-match: \"v1\""));
+
+        // The stub server is gone, so this falls back to the cache.
+        let offline = importer.import::<String>(&url, &[]).await.unwrap();
+        assert_eq!(first, offline);
+
+        let uncached = HttpImporter::new(None).import::<String>(&url, &[]).await;
+        assert!(uncached.is_err());
+
+        remove_dir_all(cache).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_import() {
+        let base = temp_dir().join(format!("litho-import-file-{}", std::process::id()));
+        create_dir_all(&base).unwrap();
+        write(base.join("schema.graphql"), "type Query { hello: String }").unwrap();
+        write(base.join("schema.json"), INTROSPECTION).unwrap();
+
+        let importer = FileImporter::new(&base);
+        let sdl = importer.import::<String>("schema.graphql", &[]).await;
+        let json = importer.import::<String>("schema.json", &[]).await.unwrap();
+        let missing = importer.import::<String>("missing.graphql", &[]).await;
+
+        assert_eq!(sdl.unwrap(), "type Query { hello: String }");
+        assert!(json.contains("hello: String"));
+        assert!(missing.is_err());
+
+        remove_dir_all(base).unwrap();
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn test_exec_import() {
+        let base = temp_dir().join(format!("litho-import-exec-{}", std::process::id()));
+        create_dir_all(&base).unwrap();
+        write(base.join("schema.graphql"), "type Query { hello: String }").unwrap();
+
+        let importer = ExecImporter::new(&base);
+        let sdl = importer
+            .import::<String>("litho+exec:cat schema.graphql", &[])
+            .await;
+        let failed = importer.import::<String>("litho+exec:exit 1", &[]).await;
+
+        assert_eq!(sdl.unwrap(), "type Query { hello: String }");
+        assert!(failed.is_err());
+
+        let disabled = DefaultImporter::new(&base, None, false)
+            .import::<String>("litho+exec:touch executed", &[])
+            .await;
+
+        assert!(disabled.is_err());
+        assert!(!base.join("executed").exists());
+
+        let enabled = DefaultImporter::new(&base, None, true)
+            .import::<String>("litho+exec:touch executed", &[])
+            .await;
+
+        assert!(enabled.is_ok());
+        assert!(base.join("executed").exists());
+
+        remove_dir_all(base).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_interpolated_headers() {
+        set_var("LITHO_IMPORT_TEST_TOKEN", "secret");
+
+        let (url, handle) = serve(vec![ok("\"v1\"")]);
+        let headers = [(
+            "Authorization".to_owned(),
+            "Bearer ${env:LITHO_IMPORT_TEST_TOKEN}".to_owned(),
+        )];
+        HttpImporter::new(None)
+            .import::<String>(&url, &headers)
+            .await
+            .unwrap();
+        let requests = handle.join().unwrap();
+
+        assert!(requests[0]
+            .iter()
+            .any(|header| header == "authorization: Bearer secret"));
+    }
+
+    #[test]
+    fn test_interpolate() {
+        set_var("LITHO_IMPORT_TEST_NAME", "world");
+
+        assert_eq!(
+            interpolate("hello ${env:LITHO_IMPORT_TEST_NAME}!").unwrap(),
+            "hello world!"
+        );
+        assert_eq!(interpolate("no variables").unwrap(), "no variables");
+        assert!(interpolate("${env:LITHO_IMPORT_TEST_MISSING}").is_err());
+        assert!(interpolate("${env:LITHO_IMPORT_TEST_NAME").is_err());
+    }
 }
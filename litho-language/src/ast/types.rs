@@ -148,6 +148,16 @@ pub enum OperationType<T> {
     Subscription(Name<T>),
 }
 
+impl<T> OperationType<T> {
+    pub fn default_type_name(&self) -> &'static str {
+        match self {
+            OperationType::Query(_) => "Query",
+            OperationType::Mutation(_) => "Mutation",
+            OperationType::Subscription(_) => "Subscription",
+        }
+    }
+}
+
 node_enum!(
     OperationType,
     visit_operation_type,
@@ -270,6 +280,27 @@ pub struct FragmentDefinition<T> {
     pub selection_set: Recoverable<Arc<SelectionSet<T>>>,
 }
 
+impl<T> FragmentDefinition<T>
+where
+    T: Borrow<str>,
+{
+    /// Returns the local arguments that this fragment defines with Relay's
+    /// `@argumentDefinitions` directive.
+    pub fn argument_definitions(&self) -> impl Iterator<Item = &Arc<Argument<T>>> {
+        self.directives
+            .as_ref()
+            .and_then(|directives| directives.directive("argumentDefinitions"))
+            .and_then(|directive| directive.arguments.as_ref())
+            .into_iter()
+            .flat_map(|arguments| arguments.items.iter())
+    }
+
+    pub fn argument_definition(&self, name: &str) -> Option<&Arc<Argument<T>>> {
+        self.argument_definitions()
+            .find(|argument| argument.name.as_ref().borrow() == name)
+    }
+}
+
 node!(
     Arc<FragmentDefinition>,
     visit_fragment_definition + post_visit_fragment_definition,
@@ -612,6 +643,26 @@ pub struct Directives<T> {
     pub directives: Vec<Arc<Directive<T>>>,
 }
 
+impl<T> Directives<T>
+where
+    T: Borrow<str>,
+{
+    pub fn defer(&self) -> Option<&Arc<Directive<T>>> {
+        self.directive("defer")
+            .filter(|directive| directive.is_enabled())
+    }
+
+    pub fn directive(&self, name: &str) -> Option<&Arc<Directive<T>>> {
+        self.directives.iter().find(|directive| {
+            directive
+                .name
+                .ok()
+                .map(|directive| directive.as_ref().borrow() == name)
+                .unwrap_or_default()
+        })
+    }
+}
+
 node!(Directives, visit_directives, directives);
 
 #[derive(Clone, Debug)]
@@ -633,6 +684,19 @@ where
                 .find(|arg| arg.name.as_ref().borrow() == name)
         })
     }
+
+    /// Returns `false` if this directive is disabled with a literal `if: false`
+    /// argument (e.g. `@defer(if: false)`).
+    pub fn is_enabled(&self) -> bool {
+        match self
+            .argument("if")
+            .and_then(|argument| argument.value.ok())
+            .map(AsRef::as_ref)
+        {
+            Some(Value::BooleanValue(value)) => value.to_bool(),
+            _ => true,
+        }
+    }
 }
 
 node!(Arc<Directive>, visit_directive, at, name, arguments);
@@ -966,6 +1030,10 @@ impl<T> TypeExtension<T> {
     }
 
     pub fn name(&self) -> Option<&T> {
+        self.named_type().map(|name| name.0.as_ref())
+    }
+
+    pub fn named_type(&self) -> Option<&NamedType<T>> {
         match self {
             TypeExtension::ScalarTypeExtension(extension) => &extension.name,
             TypeExtension::ObjectTypeExtension(extension) => &extension.name,
@@ -975,7 +1043,6 @@ impl<T> TypeExtension<T> {
             TypeExtension::InputObjectTypeExtension(extension) => &extension.name,
         }
         .ok()
-        .map(|name| name.0.as_ref())
     }
 
     pub fn directives(&self) -> Option<&Directives<T>> {
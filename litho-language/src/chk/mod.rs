@@ -1,7 +1,8 @@
 use litho_diagnostics::Diagnostic;
 
 use crate::ast::{Node, Recoverable, Visit};
-use crate::lex::{Span, Token};
+use crate::cst::SyntaxTree;
+use crate::lex::Span;
 
 pub trait Errors<T> {
     fn errors(&self) -> Vec<Diagnostic<Span>>;
@@ -18,19 +19,19 @@ where
     }
 }
 
-pub fn collect_errors<N, T>(ast: &(N, Vec<Token<T>>)) -> Vec<Diagnostic<Span>>
+pub fn collect_errors<N, T>(ast: &(N, SyntaxTree<T>)) -> Vec<Diagnostic<Span>>
 where
     N: Node<T>,
     T: Clone,
 {
     let mut errors = vec![];
     ast.0.traverse(&CollectErrors, &mut errors);
-    match ast.1.first().zip(ast.1.last()) {
-        Some((first, last)) => errors.push(Diagnostic::unrecognized_tokens(
+    let unexpected = ast.1.unexpected();
+    if let Some((first, last)) = unexpected.first().zip(unexpected.last()) {
+        errors.push(Diagnostic::unrecognized_tokens(
             first.span().joined(last.span()),
-        )),
-        None => {}
-    };
+        ));
+    }
     errors
 }
 
@@ -0,0 +1,237 @@
+//! Schema coordinates (e.g. `Type.field(arg:)`), which uniquely identify an
+//! element of a schema, as described in the Schema Coordinates RFC.
+
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SchemaCoordinate<T> {
+    /// `Type`
+    Type(T),
+
+    /// `Type.field`, `Enum.VALUE` or `Input.field`
+    Member(T, T),
+
+    /// `Type.field(argument:)`
+    Argument(T, T, T),
+
+    /// `@directive`
+    Directive(T),
+
+    /// `@directive(argument:)`
+    DirectiveArgument(T, T),
+}
+
+/// Error that is returned when a schema coordinate can't be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidCoordinate {
+    pub offset: usize,
+    pub expected: &'static str,
+}
+
+impl Display for InvalidCoordinate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "expected {} at offset {}", self.expected, self.offset)
+    }
+}
+
+impl std::error::Error for InvalidCoordinate {}
+
+struct Parser<'a> {
+    input: &'a str,
+    offset: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, expected: &'static str) -> InvalidCoordinate {
+        InvalidCoordinate {
+            offset: self.offset,
+            expected,
+        }
+    }
+
+    fn eat(&mut self, punctuator: &'static str) -> bool {
+        match self.input[self.offset..].starts_with(punctuator) {
+            true => {
+                self.offset += punctuator.len();
+                true
+            }
+            false => false,
+        }
+    }
+
+    fn expect(&mut self, punctuator: &'static str) -> std::result::Result<(), InvalidCoordinate> {
+        match self.eat(punctuator) {
+            true => Ok(()),
+            false => Err(self.error(punctuator)),
+        }
+    }
+
+    fn name(&mut self) -> std::result::Result<&'a str, InvalidCoordinate> {
+        let rest = &self.input[self.offset..];
+
+        let length = rest
+            .char_indices()
+            .find(|&(index, char)| match char {
+                '_' | 'A'..='Z' | 'a'..='z' => false,
+                '0'..='9' => index == 0,
+                _ => true,
+            })
+            .map(|(index, _)| index)
+            .unwrap_or(rest.len());
+
+        if length == 0 {
+            return Err(self.error("name"));
+        }
+
+        self.offset += length;
+
+        Ok(&rest[..length])
+    }
+
+    fn argument(&mut self) -> std::result::Result<Option<&'a str>, InvalidCoordinate> {
+        if !self.eat("(") {
+            return Ok(None);
+        }
+
+        let name = self.name()?;
+        self.expect(":")?;
+        self.expect(")")?;
+
+        Ok(Some(name))
+    }
+}
+
+impl<T> SchemaCoordinate<T> {
+    /// Parses a schema coordinate. Coordinates can't contain whitespace or
+    /// any other ignored tokens.
+    pub fn parse<'a>(input: &'a str) -> std::result::Result<SchemaCoordinate<T>, InvalidCoordinate>
+    where
+        T: From<&'a str>,
+    {
+        let mut parser = Parser { input, offset: 0 };
+
+        let coordinate = match parser.eat("@") {
+            true => {
+                let name = parser.name()?;
+
+                match parser.argument()? {
+                    Some(argument) => {
+                        SchemaCoordinate::DirectiveArgument(name.into(), argument.into())
+                    }
+                    None => SchemaCoordinate::Directive(name.into()),
+                }
+            }
+            false => {
+                let ty = parser.name()?;
+
+                match parser.eat(".") {
+                    true => {
+                        let member = parser.name()?;
+
+                        match parser.argument()? {
+                            Some(argument) => SchemaCoordinate::Argument(
+                                ty.into(),
+                                member.into(),
+                                argument.into(),
+                            ),
+                            None => SchemaCoordinate::Member(ty.into(), member.into()),
+                        }
+                    }
+                    false => SchemaCoordinate::Type(ty.into()),
+                }
+            }
+        };
+
+        match parser.offset == input.len() {
+            true => Ok(coordinate),
+            false => Err(parser.error("end of coordinate")),
+        }
+    }
+
+    /// Returns a coordinate with each name mapped by the given function (e.g.
+    /// to convert between string types).
+    pub fn map<U, F>(&self, f: F) -> SchemaCoordinate<U>
+    where
+        F: Fn(&T) -> U,
+    {
+        match self {
+            SchemaCoordinate::Type(ty) => SchemaCoordinate::Type(f(ty)),
+            SchemaCoordinate::Member(ty, member) => SchemaCoordinate::Member(f(ty), f(member)),
+            SchemaCoordinate::Argument(ty, field, argument) => {
+                SchemaCoordinate::Argument(f(ty), f(field), f(argument))
+            }
+            SchemaCoordinate::Directive(name) => SchemaCoordinate::Directive(f(name)),
+            SchemaCoordinate::DirectiveArgument(name, argument) => {
+                SchemaCoordinate::DirectiveArgument(f(name), f(argument))
+            }
+        }
+    }
+}
+
+impl<T> Display for SchemaCoordinate<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            SchemaCoordinate::Type(ty) => write!(f, "{}", ty),
+            SchemaCoordinate::Member(ty, member) => write!(f, "{}.{}", ty, member),
+            SchemaCoordinate::Argument(ty, field, argument) => {
+                write!(f, "{}.{}({}:)", ty, field, argument)
+            }
+            SchemaCoordinate::Directive(name) => write!(f, "@{}", name),
+            SchemaCoordinate::DirectiveArgument(name, argument) => {
+                write!(f, "@{}({}:)", name, argument)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InvalidCoordinate, SchemaCoordinate};
+
+    fn parse(input: &str) -> Result<SchemaCoordinate<String>, InvalidCoordinate> {
+        SchemaCoordinate::parse(input)
+    }
+
+    #[test]
+    fn test_parse() {
+        for input in [
+            "Query",
+            "Query.user",
+            "Query.user(id:)",
+            "@deprecated",
+            "@deprecated(reason:)",
+            "__Type.fields(includeDeprecated:)",
+        ] {
+            assert_eq!(parse(input).unwrap().to_string(), input);
+        }
+
+        assert_eq!(
+            parse("Query.user(id:)"),
+            Ok(SchemaCoordinate::Argument(
+                "Query".to_owned(),
+                "user".to_owned(),
+                "id".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        for (input, offset, expected) in [
+            ("", 0, "name"),
+            ("1Query", 0, "name"),
+            ("Query.", 6, "name"),
+            ("Query user", 5, "end of coordinate"),
+            ("Query.user(id)", 13, ":"),
+            ("Query.user(id:", 14, ")"),
+            ("@", 1, "name"),
+            ("@deprecated.reason", 11, "end of coordinate"),
+            ("Query.user.id", 10, "end of coordinate"),
+        ] {
+            assert_eq!(parse(input), Err(InvalidCoordinate { offset, expected }));
+        }
+    }
+}
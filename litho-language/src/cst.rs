@@ -0,0 +1,428 @@
+//! Lossless concrete syntax tree of a GraphQL document.
+//!
+//! The [`ast`](crate::ast) module only keeps the tokens that are significant
+//! to the meaning of a document. A [`SyntaxTree`] also keeps the trivia
+//! (white space, line terminators, commas and comments) around each token, as
+//! well as tokens that the parser didn't expect, so that it prints back to its
+//! source byte for byte. The AST is a typed view over the same tokens: any
+//! AST node can be used to look up its tokens and trivia in the syntax tree,
+//! or to replace or remove it without touching the rest of the document.
+
+use std::fmt::{Display, Formatter, Result};
+use std::sync::OnceLock;
+
+use crate::ast::Node;
+use crate::lex::{SourceId, Span, Token};
+
+/// Kind of source text that isn't significant to the meaning of a document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriviaKind {
+    ByteOrderMark,
+    WhiteSpace,
+    LineTerminator,
+    Comment,
+    Comma,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub span: Span,
+}
+
+/// Token with the trivia around it. Trivia up to (but not including) the next
+/// line terminator trails the preceding token, all other trivia leads the
+/// next token.
+#[derive(Clone, Debug)]
+pub struct SyntaxToken<T> {
+    pub leading_trivia: Vec<Trivia>,
+    pub token: Token<T>,
+    pub trailing_trivia: Vec<Trivia>,
+    replacement: Option<String>,
+}
+
+impl<T> SyntaxToken<T> {
+    /// Returns `true` if this token was replaced by an edit.
+    pub fn is_replaced(&self) -> bool {
+        self.replacement.is_some()
+    }
+}
+
+/// Lossless syntax tree of a document. The trivia around each token is only
+/// computed once the tokens are first used, so parsing stays cheap for
+/// callers that only need the AST.
+#[derive(Clone, Debug)]
+pub struct SyntaxTree<T> {
+    source_id: SourceId,
+    source: String,
+    lexed: Vec<Token<T>>,
+    unexpected: Vec<Token<T>>,
+    lossless: OnceLock<Lossless<T>>,
+}
+
+impl<T> Default for SyntaxTree<T> {
+    fn default() -> Self {
+        SyntaxTree {
+            source_id: Default::default(),
+            source: Default::default(),
+            lexed: Default::default(),
+            unexpected: Default::default(),
+            lossless: Default::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Lossless<T> {
+    tokens: Vec<SyntaxToken<T>>,
+    trivia: Vec<Trivia>,
+}
+
+fn trivia(source_id: SourceId, source: &str, start: usize, end: usize) -> Vec<Trivia> {
+    let mut trivia = vec![];
+    let mut chars = source[start..end].char_indices().peekable();
+
+    while let Some((offset, char)) = chars.next() {
+        let kind = match char {
+            '\u{feff}' => TriviaKind::ByteOrderMark,
+            '\n' => TriviaKind::LineTerminator,
+            '\r' => {
+                chars.next_if(|&(_, char)| char == '\n');
+                TriviaKind::LineTerminator
+            }
+            ',' => TriviaKind::Comma,
+            '#' => {
+                while chars
+                    .next_if(|&(_, char)| !matches!(char, '\n' | '\r'))
+                    .is_some()
+                {}
+                TriviaKind::Comment
+            }
+            _ => {
+                while chars
+                    .next_if(|&(_, char)| !matches!(char, '\u{feff}' | '\n' | '\r' | ',' | '#'))
+                    .is_some()
+                {}
+                TriviaKind::WhiteSpace
+            }
+        };
+
+        let end = chars.peek().map_or(end, |&(next, _)| start + next);
+
+        trivia.push(Trivia {
+            kind,
+            span: Span {
+                source_id,
+                start: start + offset,
+                end,
+            },
+        });
+    }
+
+    trivia
+}
+
+/// Splits trivia between two tokens into the trivia that trails the first
+/// and the trivia that leads the second.
+fn split(mut trivia: Vec<Trivia>) -> (Vec<Trivia>, Vec<Trivia>) {
+    let index = trivia
+        .iter()
+        .position(|trivia| trivia.kind == TriviaKind::LineTerminator)
+        .unwrap_or(trivia.len());
+    let leading = trivia.split_off(index);
+
+    (trivia, leading)
+}
+
+impl<T> Lossless<T> {
+    fn new(source_id: SourceId, source: &str, tokens: Vec<Token<T>>) -> Lossless<T> {
+        let mut syntax_tokens: Vec<SyntaxToken<T>> = Vec::with_capacity(tokens.len());
+        let mut offset = 0;
+
+        for token in tokens {
+            let span = token.span();
+            let between = trivia(source_id, source, offset, span.start);
+
+            let leading_trivia = match syntax_tokens.last_mut() {
+                Some(previous) => {
+                    let (trailing, leading) = split(between);
+                    previous.trailing_trivia = trailing;
+                    leading
+                }
+                None => between,
+            };
+
+            syntax_tokens.push(SyntaxToken {
+                leading_trivia,
+                token,
+                trailing_trivia: vec![],
+                replacement: None,
+            });
+
+            offset = span.end;
+        }
+
+        let mut trivia = trivia(source_id, source, offset, source.len());
+
+        if let Some(last) = syntax_tokens.last_mut() {
+            let (trailing, rest) = split(trivia);
+            last.trailing_trivia = trailing;
+            trivia = rest;
+        }
+
+        Lossless {
+            tokens: syntax_tokens,
+            trivia,
+        }
+    }
+
+    fn range<N>(&self, node: &N) -> Option<(usize, usize)>
+    where
+        N: Node<T> + ?Sized,
+    {
+        let span = node.span();
+        let start = self
+            .tokens
+            .partition_point(|token| token.token.span().start < span.start);
+        let end = self
+            .tokens
+            .partition_point(|token| token.token.span().end <= span.end);
+
+        match start < end {
+            true => Some((start, end - 1)),
+            false => None,
+        }
+    }
+}
+
+impl<T> SyntaxTree<T> {
+    /// Builds the syntax tree of the given source from all of its tokens and
+    /// the tokens that weren't consumed by the parser.
+    pub fn new(
+        source_id: SourceId,
+        source: &str,
+        tokens: Vec<Token<T>>,
+        unexpected: Vec<Token<T>>,
+    ) -> SyntaxTree<T> {
+        SyntaxTree {
+            source_id,
+            source: source.to_owned(),
+            lexed: tokens,
+            unexpected,
+            lossless: OnceLock::new(),
+        }
+    }
+
+    /// Returns the original source of this tree (without any edits).
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the original source text of the given span.
+    pub fn text(&self, span: Span) -> &str {
+        &self.source[span.start..span.end]
+    }
+
+    /// Returns the tokens that weren't consumed by the parser.
+    pub fn unexpected(&self) -> &[Token<T>] {
+        &self.unexpected
+    }
+
+    fn write_trivia(&self, f: &mut Formatter<'_>, trivia: &[Trivia]) -> Result {
+        trivia
+            .iter()
+            .try_for_each(|trivia| f.write_str(self.text(trivia.span)))
+    }
+}
+
+impl<T> SyntaxTree<T>
+where
+    T: Clone,
+{
+    fn lossless(&self) -> &Lossless<T> {
+        self.lossless
+            .get_or_init(|| Lossless::new(self.source_id, &self.source, self.lexed.clone()))
+    }
+
+    fn lossless_mut(&mut self) -> &mut Lossless<T> {
+        if self.lossless.get().is_none() {
+            let tokens = std::mem::take(&mut self.lexed);
+            self.lossless = Lossless::new(self.source_id, &self.source, tokens).into();
+        }
+
+        self.lossless.get_mut().unwrap()
+    }
+
+    pub fn tokens(&self) -> &[SyntaxToken<T>] {
+        &self.lossless().tokens
+    }
+
+    /// Returns the trivia after the last token.
+    pub fn trailing_trivia(&self) -> &[Trivia] {
+        &self.lossless().trivia
+    }
+
+    /// Returns the tokens of the given node.
+    pub fn node_tokens<N>(&self, node: &N) -> &[SyntaxToken<T>]
+    where
+        N: Node<T> + ?Sized,
+    {
+        let lossless = self.lossless();
+
+        match lossless.range(node) {
+            Some((start, end)) => &lossless.tokens[start..=end],
+            None => &[],
+        }
+    }
+
+    /// Replaces the tokens of the given node (and the trivia between them) by
+    /// the given text, keeping the trivia before and after the node.
+    pub fn replace<N>(&mut self, node: &N, text: &str)
+    where
+        N: Node<T> + ?Sized,
+    {
+        let lossless = self.lossless_mut();
+        let Some((start, end)) = lossless.range(node) else {
+            return;
+        };
+
+        let trailing_trivia = std::mem::take(&mut lossless.tokens[end].trailing_trivia);
+        lossless.tokens.drain(start + 1..=end);
+
+        let token = &mut lossless.tokens[start];
+        token.trailing_trivia = trailing_trivia;
+        token.replacement = Some(text.to_owned());
+    }
+
+    /// Removes the given node along with its trivia, which includes the
+    /// comments on the lines before it and the comment after it.
+    pub fn remove<N>(&mut self, node: &N)
+    where
+        N: Node<T> + ?Sized,
+    {
+        let lossless = self.lossless_mut();
+
+        if let Some((start, end)) = lossless.range(node) {
+            lossless.tokens.drain(start..=end);
+        }
+    }
+}
+
+impl<T> Display for SyntaxTree<T>
+where
+    T: Clone,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let lossless = self.lossless();
+
+        for token in lossless.tokens.iter() {
+            self.write_trivia(f, &token.leading_trivia)?;
+
+            match token.replacement.as_ref() {
+                Some(replacement) => f.write_str(replacement)?,
+                None => f.write_str(self.text(token.token.span()))?,
+            }
+
+            self.write_trivia(f, &token.trailing_trivia)?;
+        }
+
+        self.write_trivia(f, &lossless.trivia)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::*;
+    use crate::Parse;
+
+    use super::TriviaKind;
+
+    fn round_trip(source: &str) {
+        let (_, tree) = Document::<&str>::parse_from_str(Default::default(), source).unwrap();
+        assert_eq!(tree.to_string(), source);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        round_trip("");
+        round_trip("   \n# Only a comment.");
+        round_trip("\u{feff}type Query { a: Int, b: Int }\r\n");
+        round_trip("query($a: Int = 1,,, $b: [String!]! = [\"a\" \"b\"]) { a(b: $a) @c }\n");
+        round_trip("\"\"\"\n  Block\n  string.\n\"\"\"\nscalar Date @specifiedBy(url: \"x\")\r");
+        round_trip("type Query {\n  a: # Missing type.\n}\n} unexpected ?! 01 tokens");
+        round_trip("{\n\ta\r\n\tb # c\r\n}");
+        round_trip("query {\n\ta\r\n\tb # c\r\n}");
+    }
+
+    #[test]
+    fn test_lazy_trivia() {
+        let (_, tree) = Document::<&str>::parse_from_str(Default::default(), "scalar A }").unwrap();
+
+        assert_eq!(tree.unexpected().len(), 1);
+        assert!(tree.lossless.get().is_none());
+
+        assert_eq!(tree.tokens().len(), 3);
+        assert!(tree.lossless.get().is_some());
+    }
+
+    #[test]
+    fn test_trivia() {
+        let source = "# Leading.\nscalar A, # Trailing.\n\nscalar B";
+        let (_, tree) = Document::<&str>::parse_from_str(Default::default(), source).unwrap();
+
+        let tokens = tree.tokens();
+        assert_eq!(tokens.len(), 4);
+
+        let kinds =
+            |trivia: &[super::Trivia]| trivia.iter().map(|trivia| trivia.kind).collect::<Vec<_>>();
+
+        assert_eq!(
+            kinds(&tokens[0].leading_trivia),
+            vec![TriviaKind::Comment, TriviaKind::LineTerminator]
+        );
+        assert_eq!(
+            kinds(&tokens[1].trailing_trivia),
+            vec![
+                TriviaKind::Comma,
+                TriviaKind::WhiteSpace,
+                TriviaKind::Comment
+            ]
+        );
+        assert_eq!(
+            kinds(&tokens[2].leading_trivia),
+            vec![TriviaKind::LineTerminator, TriviaKind::LineTerminator]
+        );
+        assert_eq!(tree.text(tokens[1].trailing_trivia[2].span), "# Trailing.");
+    }
+
+    #[test]
+    fn test_edits() {
+        let source = "query {\n  a(x: 1) # First.\n  # Second.\n  b\n  c\n}\n";
+        let (document, mut tree) =
+            Document::<&str>::parse_from_str(Default::default(), source).unwrap();
+
+        let Definition::ExecutableDefinition(ExecutableDefinition::OperationDefinition(operation)) =
+            document.definitions[0].as_ref()
+        else {
+            unreachable!()
+        };
+
+        let selections = &operation.selection_set.ok().unwrap().selections;
+        let [Selection::Field(a), Selection::Field(b), Selection::Field(c)] = selections.as_slice()
+        else {
+            unreachable!()
+        };
+
+        assert_eq!(tree.node_tokens(a).len(), 6);
+
+        tree.replace(a.arguments.as_ref().unwrap(), "(x: 2, y: 3)");
+        tree.remove(b);
+        tree.replace(c, "d: c");
+
+        assert_eq!(
+            tree.to_string(),
+            "query {\n  a(x: 2, y: 3) # First.\n  d: c\n}\n"
+        );
+        assert_eq!(tree.source(), source);
+    }
+}
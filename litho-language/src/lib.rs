@@ -2,9 +2,12 @@
 
 pub mod ast;
 pub mod chk;
+pub mod coordinate;
+pub mod cst;
 pub mod fmt;
 pub mod lex;
 pub mod syn;
 
 pub use ast::Document;
+pub use coordinate::SchemaCoordinate;
 pub use syn::{parse_from_str, Parse};
@@ -1,5 +1,6 @@
 use nom::Err;
 
+use crate::cst::SyntaxTree;
 use crate::lex::{lexer, SourceId, Token};
 
 use super::{Error, Stream};
@@ -7,15 +8,22 @@ use super::{Error, Stream};
 pub trait Parse<T>: Sized {
     fn parse(stream: Stream<T>) -> Result<(Self, Vec<Token<T>>), Err<Error>>;
 
+    /// Parses the given input and returns the resulting node along with the
+    /// lossless syntax tree of the input. The tree takes over the tokens of
+    /// the input and only computes its trivia once it's used.
     fn parse_from_str<'a>(
         source_id: SourceId,
         input: &'a str,
-    ) -> Result<(Self, Vec<Token<T>>), Err<Error>>
+    ) -> Result<(Self, SyntaxTree<T>), Err<Error>>
     where
         T: From<&'a str>,
     {
         let lexer = lexer(source_id, input).exact();
-        let stream = Stream::from(&lexer);
-        Self::parse(stream)
+        let (node, unexpected) = Self::parse(Stream::from(&lexer))?;
+
+        Ok((
+            node,
+            SyntaxTree::new(source_id, input, lexer.tokens.into(), unexpected),
+        ))
     }
 }
@@ -21,7 +21,10 @@ async fn main() {
 
         let workspace = Workspace::new(sender);
 
-        let (importer, worker) = Importer::new(Arc::downgrade(&workspace));
+        let (importer, worker) = Importer::new(
+            Arc::downgrade(&workspace),
+            dirs::cache_dir().map(|cache| cache.join("litho").join("imports")),
+        );
 
         let server = TowerServer::new(client.clone(), workspace.clone());
 
@@ -165,6 +168,16 @@ mod tower {
                 .await
                 .map_err(|_| Error::invalid_request())
         }
+
+        async fn code_action(
+            &self,
+            params: CodeActionParams,
+        ) -> Result<Option<CodeActionResponse>> {
+            self.server
+                .code_action(params)
+                .await
+                .map_err(|_| Error::invalid_request())
+        }
     }
 
     impl TowerServer {
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use lsp_types::*;
+
+use crate::diagnostic::serialize_diagnostic;
+
+use super::{Document, Workspace};
+
+pub struct CodeActionProvider<'a> {
+    document: &'a Document,
+    workspace: &'a Workspace,
+}
+
+impl CodeActionProvider<'_> {
+    pub fn new<'a>(document: &'a Document, workspace: &'a Workspace) -> CodeActionProvider<'a> {
+        CodeActionProvider {
+            document,
+            workspace,
+        }
+    }
+
+    pub fn code_actions(&self, range: Range) -> Vec<CodeActionOrCommand> {
+        let mut actions = vec![];
+
+        for diagnostic in self
+            .workspace
+            .compiler(self.document.source_id())
+            .diagnostics(self.document.source_id())
+        {
+            let Some(span_range) = self.workspace.span_to_range(diagnostic.span()) else {
+                continue;
+            };
+
+            if span_range.end < range.start || range.end < span_range.start {
+                continue;
+            }
+
+            for (index, suggestion) in diagnostic.suggestions().iter().enumerate() {
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: format!("Change to `{}`", suggestion),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![serialize_diagnostic(diagnostic, self.workspace)]),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(
+                            self.document.url().to_owned(),
+                            vec![TextEdit {
+                                range: span_range,
+                                new_text: suggestion.to_owned(),
+                            }],
+                        )])),
+                        ..Default::default()
+                    }),
+                    is_preferred: Some(index == 0),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        actions
+    }
+}
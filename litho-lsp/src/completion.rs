@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use litho_language::ast::*;
+use litho_types::Database;
 use lsp_types::*;
 use smol_str::SmolStr;
 
@@ -47,7 +48,7 @@ impl CompletionProvider<'_> {
 
         self.document.ast().traverse(
             &CompletionVisitor {
-                workspace: self.workspace,
+                database: self.workspace.database(self.document.source_id()),
                 offset,
             },
             &mut items,
@@ -60,7 +61,7 @@ impl CompletionProvider<'_> {
 }
 
 struct CompletionVisitor<'a> {
-    workspace: &'a Workspace,
+    database: &'a Database<SmolStr>,
     offset: usize,
 }
 
@@ -130,8 +131,7 @@ impl<'a> CompletionVisitor<'a> {
     }
 
     pub fn complete_all_types(&self, input: bool) -> impl Iterator<Item = CompletionItem> + '_ {
-        self.workspace
-            .database()
+        self.database
             .type_definitions()
             .filter(move |def| match input {
                 true => def.is_input(),
@@ -154,8 +154,7 @@ impl<'a> CompletionVisitor<'a> {
     }
 
     pub fn complete_input_fields(&self, ty: &SmolStr) -> impl Iterator<Item = CompletionItem> + '_ {
-        self.workspace
-            .database()
+        self.database
             .input_value_definitions(ty)
             .map(|def| CompletionItem {
                 label: def.name.to_string(),
@@ -198,8 +197,7 @@ impl<'a> CompletionVisitor<'a> {
         };
 
         items.extend(
-            self.workspace
-                .database()
+            self.database
                 .enum_value_definitions(name)
                 .map(|definition| CompletionItem {
                     label: definition.enum_value.0.as_ref().to_string(),
@@ -208,12 +206,7 @@ impl<'a> CompletionVisitor<'a> {
                 }),
         );
 
-        match self
-            .workspace
-            .database()
-            .type_definitions_by_name(name)
-            .next()
-        {
+        match self.database.type_definitions_by_name(name).next() {
             Some(ty) if ty.is_scalar() && name == "String" => {
                 items.push(CompletionItem {
                     label: "\"".to_owned(),
@@ -264,16 +257,9 @@ impl<'a> Visit<'a, SmolStr> for CompletionVisitor<'a> {
         if node.span().contains(self.offset) {
             accumulator.truncate(0);
 
-            if let Some(ty) = self
-                .workspace
-                .database()
-                .inference
-                .type_by_selection_set
-                .get(node)
-            {
+            if let Some(ty) = self.database.inference.type_by_selection_set.get(node) {
                 accumulator.extend(
-                    self.workspace
-                        .database()
+                    self.database
                         .field_definitions(ty)
                         .map(|def| self.complete_field_definition(def)),
                 );
@@ -289,13 +275,7 @@ impl<'a> Visit<'a, SmolStr> for CompletionVisitor<'a> {
         if node.span().contains(self.offset) {
             accumulator.truncate(0);
 
-            if let Some(definition) = self
-                .workspace
-                .database()
-                .inference
-                .definition_for_arguments
-                .get(node)
-            {
+            if let Some(definition) = self.database.inference.definition_for_arguments.get(node) {
                 accumulator.extend(
                     definition
                         .definitions
@@ -383,8 +363,7 @@ impl<'a> Visit<'a, SmolStr> for CompletionVisitor<'a> {
                 accumulator.truncate(0);
 
                 match self
-                    .workspace
-                    .database()
+                    .database
                     .inference
                     .types_for_values
                     .get(node)
@@ -401,8 +380,7 @@ impl<'a> Visit<'a, SmolStr> for CompletionVisitor<'a> {
                 accumulator.truncate(0);
 
                 if let Some(ty) = self
-                    .workspace
-                    .database()
+                    .database
                     .inference
                     .types_for_values
                     .get(node)
@@ -425,15 +403,13 @@ impl<'a> Visit<'a, SmolStr> for CompletionVisitor<'a> {
                     accumulator.truncate(0);
 
                     let field_ty = self
-                        .workspace
-                        .database()
+                        .database
                         .inference
                         .types_for_values
                         .get(node)
                         .and_then(|ty| ty.name())
                         .and_then(|ty| {
-                            self.workspace
-                                .database()
+                            self.database
                                 .input_value_definitions_by_name(ty, field.name.as_ref())
                                 .next()
                         })
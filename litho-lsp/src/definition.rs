@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use litho_language::ast::*;
+use litho_types::Database;
 use lsp_types::*;
 use smol_str::SmolStr;
 
@@ -26,6 +27,7 @@ impl DefinitionProvider<'_> {
         self.document.ast().traverse(
             &DefinitionVisitor {
                 workspace: self.workspace,
+                database: self.workspace.database(self.document.source_id()),
                 offset,
             },
             &mut definition,
@@ -37,6 +39,7 @@ impl DefinitionProvider<'_> {
 
 struct DefinitionVisitor<'a> {
     workspace: &'a Workspace,
+    database: &'a Database<SmolStr>,
     offset: usize,
 }
 
@@ -47,8 +50,7 @@ impl<'a> Visit<'a, SmolStr> for DefinitionVisitor<'a> {
         if let Some(name) = node.name.ok() {
             if name.span().contains(self.offset) {
                 if let Some(definition) = self
-                    .workspace
-                    .database()
+                    .database
                     .inference
                     .field_definitions_by_field
                     .get(&node)
@@ -71,13 +73,7 @@ impl<'a> Visit<'a, SmolStr> for DefinitionVisitor<'a> {
             return;
         }
 
-        let Some(definitions) = self
-            .workspace
-            .database()
-            .inference
-            .definition_for_arguments
-            .get(node)
-        else {
+        let Some(definitions) = self.database.inference.definition_for_arguments.get(node) else {
             return;
         };
 
@@ -103,8 +99,7 @@ impl<'a> Visit<'a, SmolStr> for DefinitionVisitor<'a> {
     fn visit_named_type(&self, node: &'a NamedType<SmolStr>, accumulator: &mut Self::Accumulator) {
         if node.span().contains(self.offset) {
             if let Some(definition) = self
-                .workspace
-                .database()
+                .database
                 .type_definitions_by_name(node.0.as_ref())
                 .next()
             {
@@ -123,15 +118,13 @@ impl<'a> Visit<'a, SmolStr> for DefinitionVisitor<'a> {
         match node.as_ref() {
             Value::EnumValue(value) => {
                 let def = self
-                    .workspace
-                    .database()
+                    .database
                     .inference
                     .types_for_values
                     .get(node)
                     .and_then(|ty| ty.name())
                     .and_then(|ty| {
-                        self.workspace
-                            .database()
+                        self.database
                             .enum_value_definitions_by_name(ty, value.0.as_ref())
                             .next()
                     });
@@ -144,8 +137,7 @@ impl<'a> Visit<'a, SmolStr> for DefinitionVisitor<'a> {
             }
             Value::Variable(_) => {
                 accumulator.replace(GotoDefinitionResponse::Array(
-                    self.workspace
-                        .database()
+                    self.database
                         .inference
                         .definitions_for_variable
                         .get(node)
@@ -1,3 +1,4 @@
+use litho_diagnostics::Severity;
 use litho_language::lex::Span;
 use lsp_types::*;
 
@@ -8,10 +9,16 @@ pub fn serialize_diagnostic(
     workspace: &Workspace,
 ) -> Diagnostic {
     Diagnostic {
-        severity: Some(DiagnosticSeverity::ERROR),
+        severity: Some(match diagnostic.severity() {
+            Severity::Error => DiagnosticSeverity::ERROR,
+            Severity::Warning => DiagnosticSeverity::WARNING,
+        }),
         source: Some("litho".to_owned()),
         code: Some(NumberOrString::String(diagnostic.code().to_owned())),
-        message: diagnostic.message().to_owned(),
+        message: match diagnostic.help() {
+            Some(help) => format!("{} {}", diagnostic.message(), help),
+            None => diagnostic.message().to_owned(),
+        },
         range: workspace
             .span_to_range(diagnostic.span())
             .unwrap_or_default(),
@@ -29,6 +36,15 @@ pub fn serialize_diagnostic(
                 })
                 .collect(),
         ),
+        tags: match diagnostic {
+            litho_diagnostics::Diagnostic::DeprecatedField(_)
+            | litho_diagnostics::Diagnostic::DeprecatedArgument(_)
+            | litho_diagnostics::Diagnostic::DeprecatedEnumValue(_)
+            | litho_diagnostics::Diagnostic::DeprecatedInputField(_) => {
+                Some(vec![DiagnosticTag::DEPRECATED])
+            }
+            _ => None,
+        },
         ..Default::default()
     }
 }
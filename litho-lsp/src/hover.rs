@@ -136,10 +136,35 @@ impl<'a> Visit<'a, SmolStr> for HoverVisitor<'a> {
                     .field_definitions_by_field
                     .get(&node)
                 {
+                    let deferrals = self.database.deferrals(node);
+                    let deferred_in = deferrals
+                        .iter()
+                        .filter(|(_, directive)| directive.is_some())
+                        .map(|(operation, _)| match operation.name.as_ref() {
+                            Some(name) => format!("`{}`", name.as_ref()),
+                            None => "anonymous operation".to_owned(),
+                        })
+                        .collect::<Vec<_>>();
+
+                    let deferred = match self.database.inference.deferred_by_field(node) {
+                        Some(_) => {
+                            "_Deferred: may be absent from the initial response._\n\n".to_owned()
+                        }
+                        None if deferred_in.is_empty() => String::new(),
+                        None if deferred_in.len() == deferrals.len() => {
+                            "_Deferred: may be absent from the initial response._\n\n".to_owned()
+                        }
+                        None => format!(
+                            "_Deferred in {}: may be absent from the initial response._\n\n",
+                            deferred_in.join(", ")
+                        ),
+                    };
+
                     accumulator.replace(Hover {
                         contents: HoverContents::Scalar(MarkedString::String(format!(
-                            "```\n{}\n```\n\n---\n\n{}",
+                            "```\n{}\n```\n\n---\n\n{}{}",
                             Printer::pretty_print_field(definition),
+                            deferred,
                             definition
                                 .description
                                 .as_ref()
@@ -44,7 +44,9 @@ where
                 } => {
                     (self.diagnostics)(url, diagnostics, version).await;
                 }
-                WorkspaceUpdate::Imports(imports) => self.importer.update(imports).await,
+                WorkspaceUpdate::Imports { imports, exec } => {
+                    self.importer.update(imports, exec).await
+                }
             }
         }
     }
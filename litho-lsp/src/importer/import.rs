@@ -1,24 +1,32 @@
 use std::ops::Deref;
+use std::path::PathBuf;
 use std::sync::{Arc, Weak};
 use std::time::Instant;
 
 use futures::channel::mpsc::{channel, Receiver, Sender};
 use futures::future::{select, Either};
 use futures::{SinkExt, StreamExt};
-use litho_import::import;
+use litho_import::{DefaultImporter, Importer};
 use litho_types::Import;
+use lsp_types::Url;
 use smol_str::SmolStr;
 use tokio::sync::Mutex;
 use tokio::time::sleep_until;
 
 pub struct ImportTracker {
-    import: Import,
+    import: (Import, Option<Url>),
     result: Arc<Mutex<Option<Result<SmolStr, String>>>>,
-    sender: Sender<Import>,
+    sender: Sender<(Import, Option<Url>)>,
 }
 
 impl ImportTracker {
-    pub fn new(url: String, import: Import, refresh: Sender<()>) -> (ImportTracker, ImportWorker) {
+    pub fn new(
+        url: String,
+        import: (Import, Option<Url>),
+        cache: Option<PathBuf>,
+        exec: bool,
+        refresh: Sender<()>,
+    ) -> (ImportTracker, ImportWorker) {
         let (sender, receiver) = channel(1024);
         let tracker = ImportTracker {
             import: import.clone(),
@@ -28,6 +36,8 @@ impl ImportTracker {
         let worker = ImportWorker::new(
             url,
             import,
+            cache,
+            exec,
             Arc::downgrade(&tracker.result),
             receiver,
             refresh,
@@ -39,7 +49,7 @@ impl ImportTracker {
         self.result.lock().await
     }
 
-    pub async fn update(&mut self, import: Import) {
+    pub async fn update(&mut self, import: (Import, Option<Url>)) {
         if self.import != import {
             self.import = import.clone();
             let _ = self.sender.send(import).await;
@@ -49,24 +59,30 @@ impl ImportTracker {
 
 pub struct ImportWorker {
     url: String,
-    import: Import,
+    import: (Import, Option<Url>),
+    cache: Option<PathBuf>,
+    exec: bool,
     last_updated: Option<Instant>,
     result: Weak<Mutex<Option<Result<SmolStr, String>>>>,
-    receiver: Receiver<Import>,
+    receiver: Receiver<(Import, Option<Url>)>,
     refresh: Sender<()>,
 }
 
 impl ImportWorker {
     pub fn new(
         url: String,
-        import: Import,
+        import: (Import, Option<Url>),
+        cache: Option<PathBuf>,
+        exec: bool,
         result: Weak<Mutex<Option<Result<SmolStr, String>>>>,
-        receiver: Receiver<Import>,
+        receiver: Receiver<(Import, Option<Url>)>,
         refresh: Sender<()>,
     ) -> ImportWorker {
         ImportWorker {
             url,
             import,
+            cache,
+            exec,
             last_updated: None,
             result,
             receiver,
@@ -76,7 +92,7 @@ impl ImportWorker {
 
     pub fn next_refresh(&self) -> Instant {
         match self.last_updated {
-            Some(last_updated) => last_updated + self.import.refresh,
+            Some(last_updated) => last_updated + self.import.0.refresh,
             None => Instant::now(),
         }
     }
@@ -91,20 +107,20 @@ impl ImportWorker {
 
             match result {
                 Either::Left(_) => {
-                    let result = import::<SmolStr>(
-                        &self.url,
-                        self.import
-                            .headers
-                            .iter()
-                            .flat_map(|header| {
-                                Some((
-                                    header.name.clone().try_into().ok()?,
-                                    header.value.clone().try_into().ok()?,
-                                ))
-                            })
-                            .collect(),
-                    )
-                    .await;
+                    let (import, source) = &self.import;
+                    let base = source
+                        .as_ref()
+                        .and_then(|source| source.to_file_path().ok())
+                        .and_then(|path| path.parent().map(ToOwned::to_owned))
+                        .unwrap_or_default();
+                    let headers = import
+                        .headers
+                        .iter()
+                        .map(|header| (header.name.clone(), header.value.clone()))
+                        .collect::<Vec<_>>();
+                    let result = DefaultImporter::new(base, self.cache.clone(), self.exec)
+                        .import::<SmolStr>(&self.url, &headers)
+                        .await;
                     self.last_updated = Some(Instant::now());
 
                     let Some(mutex) = self.result.upgrade() else {
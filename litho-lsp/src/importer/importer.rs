@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::sync::Weak;
 use std::time::Duration;
 
@@ -14,53 +15,60 @@ use crate::{Imports, Workspace};
 
 pub struct Update {
     imports: Imports,
+    exec: bool,
     immediately: bool,
 }
 
 pub struct Importer(Sender<Update>);
 
 impl Importer {
-    pub fn new(workspace: Weak<Mutex<Workspace>>) -> (Importer, ImporterWorker) {
+    /// Creates a new importer. If a cache directory is given, the last
+    /// successful result of each remote import is stored there.
+    pub fn new(
+        workspace: Weak<Mutex<Workspace>>,
+        cache: Option<PathBuf>,
+    ) -> (Importer, ImporterWorker) {
         let mpsc = channel(1024);
 
-        (Importer(mpsc.0), ImporterWorker(workspace, mpsc.1))
+        (Importer(mpsc.0), ImporterWorker(workspace, cache, mpsc.1))
     }
 
-    pub async fn update(&mut self, imports: Imports) -> () {
+    pub async fn update(&mut self, imports: Imports, exec: bool) -> () {
         let _ = self
             .0
             .send(Update {
                 imports,
+                exec,
                 immediately: false,
             })
             .await;
     }
 }
 
-pub struct ImporterWorker(Weak<Mutex<Workspace>>, Receiver<Update>);
+pub struct ImporterWorker(Weak<Mutex<Workspace>>, Option<PathBuf>, Receiver<Update>);
 
 impl ImporterWorker {
     pub async fn work(mut self) {
-        let (mut state, state_worker) = ImporterState::new(self.0);
+        let (mut state, state_worker) = ImporterState::new(self.0, self.1);
 
-        let (mut sender, receiver) = channel::<Imports>(16);
+        let (mut sender, receiver) = channel::<(Imports, bool)>(16);
         let mut debounced_receiver = debounced(receiver, Duration::from_secs(1)).fuse();
 
         join(state_worker.work(), async move {
             loop {
                 select! {
                     imports = debounced_receiver.next() => {
-                        if let Some(imports) = imports {
-                            state.update(imports).await;
+                        if let Some((imports, exec)) = imports {
+                            state.update(imports, exec).await;
                         }
                     }
-                    update = self.1.next() => {
+                    update = self.2.next() => {
                         let Some(update) = update else { return };
 
                         match update.immediately {
-                            true => state.update(update.imports).await,
+                            true => state.update(update.imports, update.exec).await,
                             false => {
-                                let _ = sender.send(update.imports).await;
+                                let _ = sender.send((update.imports, update.exec)).await;
                             }
                         }
                     }
@@ -1,5 +1,6 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::{Arc, Weak};
 
 use futures::channel::mpsc::{channel, Receiver, Sender};
@@ -15,10 +16,15 @@ pub struct ImporterState {
     imports: Arc<Mutex<HashMap<String, ImportTracker>>>,
     sender: Sender<ImportWorker>,
     refresh: Sender<()>,
+    cache: Option<PathBuf>,
+    exec: bool,
 }
 
 impl ImporterState {
-    pub fn new(workspace: Weak<Mutex<Workspace>>) -> (ImporterState, ImporterStateWorker) {
+    pub fn new(
+        workspace: Weak<Mutex<Workspace>>,
+        cache: Option<PathBuf>,
+    ) -> (ImporterState, ImporterStateWorker) {
         let (sender, receiver) = channel(1024);
         let (refresh_sender, refresh_receiver) = channel(1024);
 
@@ -30,6 +36,8 @@ impl ImporterState {
                 imports,
                 sender,
                 refresh: refresh_sender,
+                cache,
+                exec: false,
             },
             ImporterStateWorker {
                 imports: weak_imports,
@@ -40,9 +48,15 @@ impl ImporterState {
         )
     }
 
-    pub async fn update(&mut self, imports: Imports) {
+    pub async fn update(&mut self, imports: Imports, exec: bool) {
         let mut self_imports = self.imports.lock().await;
 
+        // Imports are resolved again if commands are enabled or disabled.
+        if self.exec != exec {
+            self.exec = exec;
+            self_imports.clear();
+        }
+
         self_imports.retain(|url, _| imports.contains_key(url));
 
         for (url, import) in imports.into_iter() {
@@ -52,8 +66,13 @@ impl ImporterState {
                 }
                 Entry::Vacant(entry) => {
                     entry.insert({
-                        let (tracker, worker) =
-                            ImportTracker::new(url, import, self.refresh.clone());
+                        let (tracker, worker) = ImportTracker::new(
+                            url,
+                            import,
+                            self.cache.clone(),
+                            self.exec,
+                            self.refresh.clone(),
+                        );
                         let _ = self.sender.send(worker).await;
                         tracker
                     });
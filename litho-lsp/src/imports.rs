@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
 use litho_types::Import;
+use lsp_types::Url;
 use smol_str::SmolStr;
 
-pub type Imports = HashMap<String, Import>;
+/// Imports of all projects, along with the url of the document that declares
+/// each import (which relative paths are resolved against).
+pub type Imports = HashMap<String, (Import, Option<Url>)>;
 pub type ResolvedImports = HashMap<String, Result<SmolStr, String>>;
@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
 use litho_language::ast::*;
+use litho_language::SchemaCoordinate;
 use litho_types::Database;
+use litho_validation::Complexity;
 use lsp_types::*;
 use smol_str::SmolStr;
 
@@ -10,11 +12,20 @@ use super::{Document, Workspace};
 pub struct InlayHintProvider<'a> {
     document: &'a Document,
     database: &'a Database<SmolStr>,
+    costs: &'a [(SchemaCoordinate<String>, usize)],
 }
 
 impl<'a> InlayHintProvider<'a> {
-    pub fn new(document: &'a Document, database: &'a Database<SmolStr>) -> InlayHintProvider<'a> {
-        InlayHintProvider { document, database }
+    pub fn new(
+        document: &'a Document,
+        database: &'a Database<SmolStr>,
+        costs: &'a [(SchemaCoordinate<String>, usize)],
+    ) -> InlayHintProvider<'a> {
+        InlayHintProvider {
+            document,
+            database,
+            costs,
+        }
     }
 
     pub fn inlay_hints(&self) -> impl Iterator<Item = InlayHint> {
@@ -24,6 +35,7 @@ impl<'a> InlayHintProvider<'a> {
             &InlayHintVisitor {
                 document: self.document,
                 database: self.database,
+                costs: self.costs,
             },
             &mut hints,
         );
@@ -35,11 +47,43 @@ impl<'a> InlayHintProvider<'a> {
 pub struct InlayHintVisitor<'a> {
     document: &'a Document,
     database: &'a Database<SmolStr>,
+    costs: &'a [(SchemaCoordinate<String>, usize)],
 }
 
 impl<'a> Visit<'a, SmolStr> for InlayHintVisitor<'a> {
     type Accumulator = Vec<InlayHint>;
 
+    fn visit_operation_definition(
+        &self,
+        node: &'a Arc<OperationDefinition<SmolStr>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(selection_set) = node.selection_set.ok() else {
+            return;
+        };
+
+        let complexity = Complexity::with_costs(node, self.database, self.costs);
+
+        let (index, padding_left) = match node.name.as_ref() {
+            Some(name) => (name.span().end, true),
+            None => (selection_set.braces.0.span().start, false),
+        };
+
+        accumulator.push(InlayHint {
+            data: None,
+            kind: None,
+            label: InlayHintLabel::String(format!("cost: {}", complexity.cost)),
+            padding_left: Some(padding_left),
+            padding_right: Some(true),
+            position: Workspace::index_to_position(self.document.text(), index),
+            text_edits: None,
+            tooltip: Some(InlayHintTooltip::String(format!(
+                "Depth: {}, fields: {}, aliases: {}, estimated cost: {}",
+                complexity.depth, complexity.fields, complexity.aliases, complexity.cost
+            ))),
+        })
+    }
+
     fn visit_selection_set(
         &self,
         node: &'a Arc<SelectionSet<SmolStr>>,
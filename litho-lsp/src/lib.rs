@@ -1,3 +1,4 @@
+mod code_action;
 mod completion;
 mod definition;
 mod diagnostic;
@@ -14,6 +15,7 @@ mod store;
 mod text_document_content;
 mod workspace;
 
+use code_action::CodeActionProvider;
 use completion::CompletionProvider;
 use definition::DefinitionProvider;
 use document::Document;
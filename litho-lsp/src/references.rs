@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use litho_language::ast::*;
+use litho_types::Database;
 use lsp_types::*;
 use smol_str::SmolStr;
 
@@ -26,6 +27,7 @@ impl ReferencesProvider<'_> {
         self.document.ast().traverse(
             &ReferencesVisitor {
                 workspace: self.workspace,
+                database: self.workspace.database(self.document.source_id()),
                 offset,
             },
             &mut locations,
@@ -37,6 +39,7 @@ impl ReferencesProvider<'_> {
 
 struct ReferencesVisitor<'a> {
     workspace: &'a Workspace,
+    database: &'a Database<SmolStr>,
     offset: usize,
 }
 
@@ -53,8 +56,7 @@ impl<'a> Visit<'a, SmolStr> for ReferencesVisitor<'a> {
         }
 
         accumulator.extend(
-            self.workspace
-                .database()
+            self.database
                 .usages
                 .fragments
                 .usages(node)
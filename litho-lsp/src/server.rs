@@ -1,11 +1,14 @@
 use std::sync::Arc;
 
 use futures::lock::Mutex;
+use litho_compiler::{Limits, Project};
+use litho_language::SchemaCoordinate;
 use lsp_types::*;
+use serde::Deserialize;
 
 use super::{
-    CompletionProvider, DefinitionProvider, FormattingProvider, HoverProvider, InlayHintProvider,
-    ReferencesProvider, SourceRoot, TextDocumentContentParams, Workspace,
+    CodeActionProvider, CompletionProvider, DefinitionProvider, FormattingProvider, HoverProvider,
+    InlayHintProvider, ReferencesProvider, SourceRoot, TextDocumentContentParams, Workspace,
 };
 
 type Result<T> = std::result::Result<T, ()>;
@@ -50,8 +53,96 @@ where
         let mut workspace = self.workspace.lock().await;
         workspace
             .mutate(|workspace| {
+                let cost = params
+                    .initialization_options
+                    .as_ref()
+                    .and_then(|options| options.get("cost"))
+                    .and_then(|cost| cost.as_bool())
+                    .unwrap_or_default();
+
+                let federation = params
+                    .initialization_options
+                    .as_ref()
+                    .and_then(|options| options.get("federation"))
+                    .and_then(|federation| federation.as_bool())
+                    .unwrap_or_default();
+
+                let relay = params
+                    .initialization_options
+                    .as_ref()
+                    .and_then(|options| options.get("relay"))
+                    .and_then(|relay| relay.as_bool())
+                    .unwrap_or_default();
+
+                let projects = params
+                    .initialization_options
+                    .as_ref()
+                    .and_then(|options| options.get("projects"))
+                    .and_then(|projects| Vec::<Project>::deserialize(projects).ok())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|project| Project {
+                        cost: project.cost || cost,
+                        federation: project.federation || federation,
+                        relay: project.relay || relay,
+                        ..project
+                    })
+                    .collect();
+
+                workspace.set_projects(
+                    Project {
+                        cost,
+                        federation,
+                        relay,
+                        ..Default::default()
+                    },
+                    projects,
+                    params.root_uri.clone(),
+                );
                 workspace.populate_builtins();
 
+                let limits = params
+                    .initialization_options
+                    .as_ref()
+                    .and_then(|options| options.get("limits"));
+
+                let limit = |name: &str| {
+                    limits
+                        .and_then(|limits| limits.get(name))
+                        .and_then(|limit| limit.as_u64())
+                        .map(|limit| limit as usize)
+                };
+
+                let costs = limits
+                    .and_then(|limits| limits.get("costs"))
+                    .and_then(|costs| costs.as_object())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|(coordinate, weight)| {
+                        Some((
+                            SchemaCoordinate::parse(coordinate).ok()?,
+                            weight.as_u64()? as usize,
+                        ))
+                    })
+                    .collect();
+
+                let exec = params
+                    .initialization_options
+                    .as_ref()
+                    .and_then(|options| options.get("allowExec"))
+                    .and_then(|exec| exec.as_bool())
+                    .unwrap_or_default();
+
+                workspace.set_exec(exec);
+
+                workspace.set_limits(Limits {
+                    max_depth: limit("maxDepth"),
+                    max_fields: limit("maxFields"),
+                    max_aliases: limit("maxAliases"),
+                    max_cost: limit("maxCost"),
+                    costs,
+                });
+
                 if let Some(root_uri) = params.root_uri {
                     let _ = self.populate_root(workspace, root_uri);
                 }
@@ -74,6 +165,7 @@ where
                 inlay_hint_provider: Some(OneOf::Left(true)),
                 references_provider: Some(OneOf::Left(true)),
                 document_formatting_provider: Some(OneOf::Left(true)),
+                code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
                 ..Default::default()
             },
             ..Default::default()
@@ -154,8 +246,10 @@ where
             return Ok(None);
         };
 
-        Ok(HoverProvider::new(document, workspace.database())
-            .hover(params.text_document_position_params.position))
+        Ok(
+            HoverProvider::new(document, workspace.database(document.source_id()))
+                .hover(params.text_document_position_params.position),
+        )
     }
 
     pub async fn goto_definition(
@@ -211,15 +305,33 @@ where
         ))
     }
 
+    pub async fn code_action(
+        &self,
+        params: CodeActionParams,
+    ) -> Result<Option<CodeActionResponse>> {
+        let workspace = self.workspace.lock().await;
+        let Some(document) = workspace.document(&params.text_document.uri) else {
+            return Ok(None);
+        };
+
+        Ok(Some(
+            CodeActionProvider::new(document, &workspace).code_actions(params.range),
+        ))
+    }
+
     pub async fn inlay_hint(&self, params: InlayHintParams) -> Result<Vec<InlayHint>> {
         let workspace = self.workspace.lock().await;
         let Some(document) = workspace.document(&params.text_document.uri) else {
             return Ok(vec![]);
         };
 
-        Ok(InlayHintProvider::new(document, workspace.database())
-            .inlay_hints()
-            .collect())
+        let compiler = workspace.compiler(document.source_id());
+
+        Ok(
+            InlayHintProvider::new(document, compiler.database(), &compiler.limits().costs)
+                .inlay_hints()
+                .collect(),
+        )
     }
 
     pub async fn text_document_content(&self, params: TextDocumentContentParams) -> Result<String> {
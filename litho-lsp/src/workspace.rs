@@ -1,10 +1,11 @@
 use std::collections::{HashMap, HashSet};
+use std::iter::once;
 use std::sync::Arc;
 
 use futures::channel::mpsc::Sender;
 use futures::lock::Mutex;
 use futures::SinkExt;
-use litho_compiler::{builtins, Compiler};
+use litho_compiler::{Compiler, Limits, Lints, Project, Rule};
 use litho_language::lex::{SourceId, SourceMap, Span};
 use litho_types::Database;
 use lsp_types::*;
@@ -20,17 +21,25 @@ pub enum WorkspaceUpdate {
         diagnostics: Vec<Diagnostic>,
         version: Option<i32>,
     },
-    Imports(Imports),
+    /// Imports that should be resolved. Commands are only run for
+    /// `litho+exec:` imports if `exec` is enabled.
+    Imports { imports: Imports, exec: bool },
 }
 
 pub struct Workspace {
     sink: Sender<WorkspaceUpdate>,
     store: Store,
     pub source_map: SourceMap<Url>,
-    compiler: Compiler<SmolStr>,
+    projects: Vec<(Project, Compiler<SmolStr>)>,
+    members: HashMap<SourceId, Vec<usize>>,
+    root: Option<Url>,
     invalid: HashSet<SourceId>,
     last_imports: ResolvedImports,
     imports: HashMap<Url, SmolStr>,
+    limits: Limits,
+    lints: Lints,
+    rules: Vec<Arc<dyn Rule<SmolStr>>>,
+    exec: bool,
 }
 
 impl Workspace {
@@ -39,13 +48,72 @@ impl Workspace {
             sink,
             store: Store::new(),
             source_map: SourceMap::new(),
-            compiler: Compiler::new(),
+            projects: vec![(Project::default(), Compiler::new())],
+            members: HashMap::new(),
+            root: None,
             invalid: HashSet::new(),
             last_imports: ResolvedImports::new(),
             imports: HashMap::new(),
+            limits: Limits::default(),
+            lints: Lints::default(),
+            rules: vec![],
+            exec: false,
         }))
     }
 
+    /// Returns a new compiler with the limits, lints and rules of this
+    /// workspace.
+    fn new_compiler(&self) -> Compiler<SmolStr> {
+        let mut compiler = Compiler::new();
+        compiler.set_limits(self.limits.clone());
+        compiler.set_lints(self.lints.clone());
+
+        for rule in self.rules.iter() {
+            compiler.add_rule(rule.clone());
+        }
+
+        compiler
+    }
+
+    /// Configures the projects of this workspace, each of which gets its own
+    /// compiler. The first project is used for files that aren't part of any
+    /// other project. Should be called before any files are populated.
+    pub fn set_projects(&mut self, default: Project, projects: Vec<Project>, root: Option<Url>) {
+        self.projects = once(default)
+            .chain(projects)
+            .map(|project| (project, self.new_compiler()))
+            .collect();
+        self.root = root;
+    }
+
+    /// Returns the indices of the projects that the file at the given url
+    /// belongs to. Paths are matched relative to the root of the workspace.
+    fn route(&self, url: &Url) -> Vec<usize> {
+        let path = self
+            .root
+            .as_ref()
+            .and_then(|root| {
+                url.as_str()
+                    .strip_prefix(root.as_str().trim_end_matches('/'))
+            })
+            .unwrap_or(url.path())
+            .trim_start_matches('/');
+
+        let projects = self
+            .projects
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, (project, _))| project.includes(path))
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+
+        match projects.is_empty() {
+            true => vec![0],
+            false => projects,
+        }
+    }
+
     pub async fn update_imports(&mut self, imports: ResolvedImports) {
         if self.last_imports == imports {
             return;
@@ -80,20 +148,48 @@ impl Workspace {
 
         let imports = imports
             .into_iter()
-            .map(|(url, (source, result))| match result {
-                Ok(text) if self.imports.get(&url) == Some(&text) => {
-                    (source, Ok(self.source_map.get_or_insert(url)))
-                }
-                Ok(text) => {
-                    self.populate_file_contents(url.clone(), None, true, text.to_string());
-                    self.imports.insert(url.clone(), text);
-                    (source, Ok(self.source_map.get_or_insert(url)))
-                }
-                Err(error) => (source, Err(error)),
+            .map(|(url, (source, result))| {
+                let projects = self
+                    .projects
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (_, compiler))| compiler.imports().contains_key(&source))
+                    .map(|(index, _)| index)
+                    .collect::<Vec<_>>();
+
+                let result = match result {
+                    Ok(text)
+                        if self.imports.get(&url) == Some(&text)
+                            && self
+                                .source_map
+                                .get(&url)
+                                .and_then(|id| self.members.get(&id))
+                                == Some(&projects) =>
+                    {
+                        Ok(self.source_map.get_or_insert(url))
+                    }
+                    Ok(text) => {
+                        self.populate(url.clone(), None, true, text.to_string(), projects);
+                        self.imports.insert(url.clone(), text);
+                        Ok(self.source_map.get_or_insert(url))
+                    }
+                    Err(error) => Err(error),
+                };
+
+                (source, result)
             })
-            .collect();
+            .collect::<HashMap<_, _>>();
+
+        for (_, compiler) in self.projects.iter_mut() {
+            let resolved = imports
+                .iter()
+                .filter(|(source, _)| compiler.imports().contains_key(*source))
+                .map(|(source, result)| (source.clone(), result.clone()))
+                .collect();
+
+            compiler.update_resolved_imports(resolved);
+        }
 
-        self.compiler.update_resolved_imports(imports);
         self.rebuild().await;
     }
 
@@ -105,15 +201,69 @@ impl Workspace {
         self.document_by_id(self.source_map.get(url)?)
     }
 
+    /// Returns the diagnostics of a source in all projects that contain it.
+    /// Diagnostics that are reported by several projects (e.g. in a shared
+    /// schema) are only returned once.
     pub fn diagnostics(&self, source_id: SourceId) -> impl Iterator<Item = Diagnostic> + '_ {
-        self.compiler
-            .diagnostics(source_id)
+        let mut seen = HashSet::new();
+
+        self.members
+            .get(&source_id)
             .into_iter()
+            .flatten()
+            .flat_map(move |&index| self.projects[index].1.diagnostics(source_id))
+            .filter(move |diagnostic| {
+                let span = diagnostic.span();
+                seen.insert((diagnostic.code(), span.source_id, span.start, span.end))
+            })
             .map(|diagnostic| serialize_diagnostic(diagnostic, self))
     }
 
-    pub fn database(&self) -> &Database<SmolStr> {
-        self.compiler.database()
+    /// Returns the compiler of the (first) project that contains the given
+    /// source.
+    pub fn compiler(&self, source_id: SourceId) -> &Compiler<SmolStr> {
+        let index = self
+            .members
+            .get(&source_id)
+            .and_then(|projects| projects.first())
+            .copied()
+            .unwrap_or_default();
+
+        &self.projects[index].1
+    }
+
+    pub fn database(&self, source_id: SourceId) -> &Database<SmolStr> {
+        self.compiler(source_id).database()
+    }
+
+    pub fn set_limits(&mut self, limits: Limits) {
+        for (_, compiler) in self.projects.iter_mut() {
+            compiler.set_limits(limits.clone())
+        }
+
+        self.limits = limits;
+    }
+
+    pub fn set_lints(&mut self, lints: Lints) {
+        for (_, compiler) in self.projects.iter_mut() {
+            compiler.set_lints(lints.clone())
+        }
+
+        self.lints = lints;
+    }
+
+    /// Enables imports that run commands (`litho+exec:`), which are disabled
+    /// by default because they run whatever command a schema contains.
+    pub fn set_exec(&mut self, exec: bool) {
+        self.exec = exec;
+    }
+
+    pub fn add_rule(&mut self, rule: Arc<dyn Rule<SmolStr>>) {
+        self.rules.push(rule.clone());
+
+        for (_, compiler) in self.projects.iter_mut() {
+            compiler.add_rule(rule.clone())
+        }
     }
 
     pub async fn mutate<F, O>(&mut self, mutation: F) -> O
@@ -126,8 +276,22 @@ impl Workspace {
     }
 
     pub fn populate_builtins(&mut self) {
-        for (path, source) in builtins().into_iter().copied() {
-            self.populate_file_contents(Url::parse(path).unwrap(), None, true, source.to_owned())
+        let mut builtins = HashMap::<_, Vec<_>>::new();
+
+        for (index, (project, _)) in self.projects.iter().enumerate() {
+            for builtin in project.builtins() {
+                builtins.entry(builtin).or_default().push(index);
+            }
+        }
+
+        for ((path, source), projects) in builtins {
+            self.populate(
+                Url::parse(path).unwrap(),
+                None,
+                true,
+                source.to_owned(),
+                projects,
+            )
         }
     }
 
@@ -137,17 +301,36 @@ impl Workspace {
         version: Option<i32>,
         internal: bool,
         text: String,
+    ) {
+        let projects = self.route(&url);
+        self.populate(url, version, internal, text, projects)
+    }
+
+    fn populate(
+        &mut self,
+        url: Url,
+        version: Option<i32>,
+        internal: bool,
+        text: String,
+        projects: Vec<usize>,
     ) {
         let id = self.source_map.get_or_insert(url.to_owned());
 
-        self.invalid
-            .extend(self.compiler.replace_document(id, &text, internal));
+        for index in self.members.remove(&id).into_iter().flatten() {
+            if !projects.contains(&index) {
+                self.invalid
+                    .extend(self.projects[index].1.remove_document(id));
+            }
+        }
 
+        for &index in projects.iter() {
+            self.invalid
+                .extend(self.projects[index].1.replace_document(id, &text, internal));
+        }
+
+        self.members.insert(id, projects);
         self.store.insert(id, url, version, internal, text);
-        self.store
-            .get_mut(&id)
-            .into_iter()
-            .for_each(|doc| doc.ast = self.compiler.document(id).cloned());
+        self.update_ast(id);
     }
 
     pub fn update_file_contents<F>(&mut self, url: Url, version: Option<i32>, update: F)
@@ -156,15 +339,28 @@ impl Workspace {
     {
         let id = self.source_map.get_or_insert(url.to_owned());
 
-        let text = self.store.update(id, url, version, update);
+        let text = self.store.update(id, url.clone(), version, update);
 
-        self.invalid
-            .extend(self.compiler.replace_document(id, &text, false));
+        let projects = match self.members.get(&id) {
+            Some(projects) => projects.clone(),
+            None => self.route(&url),
+        };
 
-        self.store
-            .get_mut(&id)
-            .into_iter()
-            .for_each(|doc| doc.ast = self.compiler.document(id).cloned());
+        for &index in projects.iter() {
+            self.invalid
+                .extend(self.projects[index].1.replace_document(id, &text, false));
+        }
+
+        self.members.insert(id, projects);
+        self.update_ast(id);
+    }
+
+    fn update_ast(&mut self, id: SourceId) {
+        let ast = self.compiler(id).document(id).cloned();
+
+        if let Some(document) = self.store.get_mut(&id) {
+            document.ast = ast;
+        }
     }
 
     pub fn remove_file(&mut self, url: &Url) {
@@ -172,15 +368,31 @@ impl Workspace {
             return;
         };
 
-        self.invalid.extend(self.compiler.remove_document(id));
+        for index in self.members.remove(&id).into_iter().flatten() {
+            self.invalid
+                .extend(self.projects[index].1.remove_document(id));
+        }
+
         self.store.remove(&id);
     }
 
     pub async fn rebuild(&mut self) {
-        self.compiler.rebuild();
+        let mut imports = Imports::new();
+
+        for (_, compiler) in self.projects.iter_mut() {
+            compiler.rebuild();
+            imports.extend(compiler.imports().iter().map(|(url, import)| {
+                let source = self.source_map.get_id(&import.span.source_id).cloned();
+                (url.clone(), (import.clone(), source))
+            }));
+        }
+
         let _ = self
             .sink
-            .send(WorkspaceUpdate::Imports(self.compiler.imports().clone()))
+            .send(WorkspaceUpdate::Imports {
+                imports,
+                exec: self.exec,
+            })
             .await;
         self.check_all().await;
     }
@@ -266,3 +478,71 @@ impl Workspace {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use futures::channel::mpsc::channel;
+    use futures::executor::block_on;
+    use litho_compiler::{Project, Rule};
+    use litho_diagnostics::{Diagnostic, Severity};
+    use litho_language::ast::{Definition, Node};
+    use litho_language::lex::Span;
+    use litho_types::Database;
+    use lsp_types::{NumberOrString, Url};
+    use smol_str::SmolStr;
+
+    use super::Workspace;
+
+    struct Everything;
+
+    impl Rule<SmolStr> for Everything {
+        fn check<'a>(
+            &self,
+            definition: &'a Arc<Definition<SmolStr>>,
+            _database: &'a Database<SmolStr>,
+        ) -> Vec<Diagnostic<Span>> {
+            vec![Diagnostic::custom(
+                "X0001",
+                "Definition is forbidden.",
+                Severity::Warning,
+                definition.span(),
+                vec![],
+            )]
+        }
+    }
+
+    #[test]
+    fn test_rules_before_initialize() {
+        let (sender, _receiver) = channel(16);
+        let workspace = Workspace::new(sender);
+
+        block_on(async {
+            let mut workspace = workspace.lock().await;
+            workspace.add_rule(Arc::new(Everything));
+            workspace.set_projects(Project::default(), vec![], None);
+            workspace.populate_builtins();
+
+            let url = Url::parse("file:///schema.graphql").unwrap();
+            workspace.populate_file_contents(
+                url.clone(),
+                None,
+                false,
+                "type Query { id: ID }".to_owned(),
+            );
+            workspace.rebuild().await;
+
+            let source_id = workspace.source_map.get(&url).unwrap();
+            let codes = workspace
+                .diagnostics(source_id)
+                .map(|diagnostic| diagnostic.code)
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                codes,
+                vec![Some(NumberOrString::String("X0001".to_owned()))]
+            );
+        });
+    }
+}
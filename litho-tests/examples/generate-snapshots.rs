@@ -6,7 +6,7 @@ use litho_language::chk::collect_errors;
 use litho_language::lex::{SourceId, Span};
 use litho_language::{Document, Parse};
 use litho_types::Database;
-use litho_validation::check;
+use litho_validation::{check, check_limits, lint, Limits, Lints};
 
 struct SingleSource(Source);
 
@@ -20,6 +20,18 @@ impl Cache<SourceId> for SingleSource {
     }
 }
 
+/// Returns the limit with the given name that a test configures with a comment
+/// (e.g. `# max_depth: 2`).
+fn limit(source: &str, name: &str) -> Option<usize> {
+    source.lines().find_map(|line| {
+        line.strip_prefix("# ")?
+            .strip_prefix(name)?
+            .strip_prefix(": ")?
+            .parse()
+            .ok()
+    })
+}
+
 pub fn main() {
     for entry in read_dir("tests").unwrap() {
         let entry = entry.unwrap();
@@ -47,6 +59,20 @@ pub fn main() {
         let database = Database::from_iter([&builtins.0, &ast.0]);
         errors.extend(check(&ast.0, &database));
 
+        if entry.file_name().to_string_lossy().starts_with('L') {
+            errors.extend(lint(&ast.0, &database, &Lints::all()));
+        }
+
+        let limits = Limits {
+            max_depth: limit(&source, "max_depth"),
+            max_fields: limit(&source, "max_fields"),
+            max_aliases: limit(&source, "max_aliases"),
+            max_cost: limit(&source, "max_cost"),
+            ..Default::default()
+        };
+
+        errors.extend(check_limits(&ast.0, &database, &limits));
+
         let output = errors
             .into_iter()
             .map(|err| err.into())
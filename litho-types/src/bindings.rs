@@ -4,6 +4,7 @@ use std::sync::Arc;
 use litho_language::ast::*;
 use multimap::MultiMap;
 
+use super::map::subtract;
 use super::Named;
 
 #[derive(Debug)]
@@ -15,10 +16,38 @@ where
     pub input_value_definitions: Named<T, InputValueDefinition<T>>,
     pub enum_value_definitions: Named<T, EnumValueDefinition<T>>,
     pub union_member_types: Named<T, NamedType<T>>,
+    pub root_operation_types: Vec<RootOperationTypeDefinition<T>>,
     pub schema_directives: Vec<Arc<Directive<T>>>,
     pub type_directives: MultiMap<T, Arc<Directive<T>>>,
 }
 
+impl<T> Bindings<T>
+where
+    T: Eq + Hash + ToOwned<Owned = T>,
+{
+    pub fn subtract(&mut self, other: &Bindings<T>) {
+        self.field_definitions.subtract(&other.field_definitions);
+        self.input_value_definitions
+            .subtract(&other.input_value_definitions);
+        self.enum_value_definitions
+            .subtract(&other.enum_value_definitions);
+        self.union_member_types.subtract(&other.union_member_types);
+        self.root_operation_types.retain(|definition| {
+            !other
+                .root_operation_types
+                .iter()
+                .any(|other| other.span() == definition.span())
+        });
+        self.schema_directives.retain(|directive| {
+            !other
+                .schema_directives
+                .iter()
+                .any(|other| Arc::ptr_eq(other, directive))
+        });
+        subtract(&mut self.type_directives, &other.type_directives);
+    }
+}
+
 impl<T> Default for Bindings<T>
 where
     T: Eq + Hash,
@@ -29,6 +58,7 @@ where
             input_value_definitions: Default::default(),
             enum_value_definitions: Default::default(),
             union_member_types: Default::default(),
+            root_operation_types: Default::default(),
             schema_directives: Default::default(),
             type_directives: Default::default(),
         }
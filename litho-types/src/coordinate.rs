@@ -0,0 +1,343 @@
+//! Resolution of schema coordinates (e.g. `Type.field(arg:)`) to the elements
+//! of a schema that they identify.
+
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::iter::once;
+use std::sync::Arc;
+
+use litho_language::ast::{self, Node, Visit};
+use litho_language::lex::Span;
+use litho_language::SchemaCoordinate;
+
+use crate::schema::{Argument, Directive, EnumValue, Field, InputField, Type, TypeKind};
+use crate::Database;
+
+/// Element of a schema that is identified by a schema coordinate.
+pub enum Element<'a, T>
+where
+    T: Eq + Hash,
+{
+    Type(Type<'a, T>),
+    Field(Field<'a, T>),
+    Argument(Field<'a, T>, Argument<'a, T>),
+    InputField(Type<'a, T>, InputField<'a, T>),
+    EnumValue(Type<'a, T>, EnumValue<'a, T>),
+    Directive(Directive<'a, T>),
+    DirectiveArgument(Directive<'a, T>, Argument<'a, T>),
+}
+
+impl<'a, T> Element<'a, T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    /// Returns the span of the name of this element's definition.
+    pub fn span(&self) -> Span {
+        match self {
+            Element::Type(ty) => ty.span(),
+            Element::Field(field) => field.span(),
+            Element::Argument(_, argument) => argument.span(),
+            Element::InputField(_, field) => field.span(),
+            Element::EnumValue(_, value) => value.span(),
+            Element::Directive(directive) => directive.span(),
+            Element::DirectiveArgument(_, argument) => argument.span(),
+        }
+    }
+
+    /// Returns the spans of this element's definition and (for a type) its
+    /// extensions, which contain the definitions of its members.
+    pub fn definition_spans(&self, database: &Database<T>) -> Vec<Span> {
+        match self {
+            Element::Type(ty) => once(ty.definition().span())
+                .chain(
+                    database
+                        .type_extensions_by_name(ty.name())
+                        .map(|extension| extension.span()),
+                )
+                .collect(),
+            Element::Field(field) => vec![field.definition().span()],
+            Element::Argument(_, argument) => vec![argument.definition().span()],
+            Element::InputField(_, field) => vec![field.definition().span()],
+            Element::EnumValue(_, value) => vec![value.definition().span()],
+            Element::Directive(directive) => vec![directive.definition().span()],
+            Element::DirectiveArgument(_, argument) => vec![argument.definition().span()],
+        }
+    }
+
+    /// Returns the spans of all usages of this element in the given node
+    /// (e.g. a document): references to a type, selections of a field, enum
+    /// values and input fields in values, arguments and directives.
+    pub fn usages<'b, N>(&self, database: &'a Database<T>, node: &'b N) -> Vec<Span>
+    where
+        N: Node<T>,
+    {
+        let mut usages = vec![];
+
+        node.traverse(
+            &Usages {
+                database,
+                element: self,
+            },
+            &mut usages,
+        );
+
+        usages
+    }
+}
+
+struct Usages<'a, 'b, T>
+where
+    T: Eq + Hash,
+{
+    database: &'a Database<T>,
+    element: &'b Element<'a, T>,
+}
+
+impl<'a, 'b, 'ast, T> Visit<'ast, T> for Usages<'a, 'b, T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    type Accumulator = Vec<Span>;
+
+    fn visit_named_type(&self, node: &'ast ast::NamedType<T>, accumulator: &mut Self::Accumulator) {
+        if let Element::Type(ty) = self.element {
+            if node.0.as_ref() == ty.name() {
+                accumulator.push(node.span());
+            }
+        }
+    }
+
+    fn visit_field(&self, node: &'ast Arc<ast::Field<T>>, accumulator: &mut Self::Accumulator) {
+        let Element::Field(field) = self.element else {
+            return;
+        };
+
+        let definition = self.database.inference.field_definitions_by_field.get(node);
+
+        if matches!(definition, Some(definition) if Arc::ptr_eq(definition, field.definition())) {
+            accumulator.push(node.name.span());
+        }
+    }
+
+    fn visit_argument(
+        &self,
+        node: &'ast Arc<ast::Argument<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let (Element::Argument(_, argument) | Element::DirectiveArgument(_, argument)) =
+            self.element
+        else {
+            return;
+        };
+
+        let definition = self.database.inference.definitions_for_arguments.get(node);
+
+        if matches!(definition, Some(definition) if Arc::ptr_eq(definition, argument.definition()))
+        {
+            accumulator.push(node.name.span());
+        }
+    }
+
+    fn visit_directive(
+        &self,
+        node: &'ast Arc<ast::Directive<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Element::Directive(directive) = self.element else {
+            return;
+        };
+
+        let definition = self.database.inference.definition_for_directive(node);
+
+        if matches!(definition, Some(definition) if Arc::ptr_eq(definition, directive.definition()))
+        {
+            accumulator.push(node.name.span());
+        }
+    }
+
+    fn visit_value(&self, node: &'ast Arc<ast::Value<T>>, accumulator: &mut Self::Accumulator) {
+        let ty = self
+            .database
+            .inference
+            .types_for_values
+            .get(node)
+            .and_then(|ty| ty.name());
+
+        match (self.element, node.as_ref()) {
+            (Element::EnumValue(parent, value), ast::Value::EnumValue(node))
+                if ty == Some(parent.name()) && node.0.as_ref() == value.name() =>
+            {
+                accumulator.push(node.span())
+            }
+            (Element::InputField(parent, field), ast::Value::ObjectValue(node))
+                if ty == Some(parent.name()) =>
+            {
+                accumulator.extend(
+                    node.object_fields
+                        .iter()
+                        .filter(|object_field| object_field.name.as_ref() == field.name())
+                        .map(|object_field| object_field.name.span()),
+                )
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<T> Database<T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    /// Returns the element of the schema that the given coordinate refers to,
+    /// or `None` if it doesn't exist.
+    pub fn resolve_coordinate(&self, coordinate: &SchemaCoordinate<T>) -> Option<Element<'_, T>> {
+        let schema = self.schema();
+
+        Some(match coordinate {
+            SchemaCoordinate::Type(name) => Element::Type(schema.ty(name)?),
+            SchemaCoordinate::Member(ty, member) => {
+                let ty = schema.ty(ty)?;
+
+                match ty.kind() {
+                    TypeKind::Object | TypeKind::Interface => {
+                        Element::Field(ty.as_object_type()?.field(member)?)
+                    }
+                    TypeKind::Enum => Element::EnumValue(ty, ty.enum_value(member)?),
+                    TypeKind::InputObject => Element::InputField(ty, ty.input_field(member)?),
+                    _ => return None,
+                }
+            }
+            SchemaCoordinate::Argument(ty, field, argument) => {
+                let field = schema.object_type(ty)?.field(field)?;
+                Element::Argument(field, field.argument(argument.borrow())?)
+            }
+            SchemaCoordinate::Directive(name) => Element::Directive(schema.directive(name)?),
+            SchemaCoordinate::DirectiveArgument(name, argument) => {
+                let directive = schema.directive(name)?;
+                Element::DirectiveArgument(directive, directive.argument(argument.borrow())?)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use litho_language::ast::Document;
+    use litho_language::lex::Span;
+    use litho_language::{Parse, SchemaCoordinate};
+
+    use crate::Database;
+
+    const DOCUMENT: &str = r#"
+        type Query {
+            user(id: ID!, role: Role): User
+            users(filter: UserFilter): [User!]! @cached(ttl: 10)
+        }
+
+        type User {
+            id: ID!
+            role: Role
+        }
+
+        enum Role {
+            ADMIN
+            MEMBER
+        }
+
+        input UserFilter {
+            role: Role
+            name: String
+        }
+
+        directive @cached(ttl: Int) on FIELD_DEFINITION
+
+        query {
+            user(id: "1", role: ADMIN) {
+                id
+                role
+            }
+
+            users(filter: { role: MEMBER, name: "Ada" }) {
+                id
+            }
+        }
+    "#;
+
+    fn usages(coordinate: &str) -> Option<(&'static str, Vec<&'static str>)> {
+        let (document, _) =
+            Document::<String>::parse_from_str(Default::default(), DOCUMENT).unwrap();
+        let database = Database::from_iter([&document]);
+
+        let coordinate = SchemaCoordinate::parse(coordinate).unwrap();
+        let element = database.resolve_coordinate(&coordinate)?;
+        let text = |span: Span| &DOCUMENT[span.start..span.end];
+
+        Some((
+            text(element.span()),
+            element
+                .usages(&database, &document)
+                .into_iter()
+                .map(text)
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn test_resolve_type() {
+        assert_eq!(usages("Role"), Some(("Role", vec!["Role", "Role", "Role"])));
+        assert_eq!(
+            usages("UserFilter"),
+            Some(("UserFilter", vec!["UserFilter"]))
+        );
+    }
+
+    #[test]
+    fn test_resolve_field() {
+        assert_eq!(usages("Query.user"), Some(("user", vec!["user"])));
+        assert_eq!(usages("User.id"), Some(("id", vec!["id", "id"])));
+        assert_eq!(usages("User.role"), Some(("role", vec!["role"])));
+    }
+
+    #[test]
+    fn test_resolve_field_argument() {
+        assert_eq!(usages("Query.user(id:)"), Some(("id", vec!["id"])));
+        assert_eq!(usages("Query.user(role:)"), Some(("role", vec!["role"])));
+        assert_eq!(
+            usages("Query.users(filter:)"),
+            Some(("filter", vec!["filter"]))
+        );
+    }
+
+    #[test]
+    fn test_resolve_directive_argument() {
+        assert_eq!(usages("@cached"), Some(("cached", vec!["cached"])));
+        assert_eq!(usages("@cached(ttl:)"), Some(("ttl", vec!["ttl"])));
+    }
+
+    #[test]
+    fn test_resolve_enum_value() {
+        assert_eq!(usages("Role.ADMIN"), Some(("ADMIN", vec!["ADMIN"])));
+        assert_eq!(usages("Role.MEMBER"), Some(("MEMBER", vec!["MEMBER"])));
+    }
+
+    #[test]
+    fn test_resolve_input_field() {
+        assert_eq!(usages("UserFilter.role"), Some(("role", vec!["role"])));
+        assert_eq!(usages("UserFilter.name"), Some(("name", vec!["name"])));
+    }
+
+    #[test]
+    fn test_resolve_unknown_coordinate() {
+        assert!(usages("Unknown").is_none());
+        assert!(usages("Unknown.field").is_none());
+        assert!(usages("Query.unknown").is_none());
+        assert!(usages("Query.user(unknown:)").is_none());
+        assert!(usages("User.id(unknown:)").is_none());
+        assert!(usages("Role.UNKNOWN").is_none());
+        assert!(usages("Role.ADMIN(id:)").is_none());
+        assert!(usages("UserFilter.unknown").is_none());
+        assert!(usages("@unknown").is_none());
+        assert!(usages("@unknown(ttl:)").is_none());
+        assert!(usages("@cached(unknown:)").is_none());
+    }
+}
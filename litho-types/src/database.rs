@@ -1,5 +1,5 @@
 use std::borrow::Borrow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::sync::Arc;
@@ -9,9 +9,17 @@ use litho_language::ast::*;
 use multimap::MultiMap;
 
 use super::indexer::Indexer;
-use super::inferencer::{InferenceState, Inferencer};
+use super::inferencer::{InferenceState, Inferencer, Invalidator};
+use super::map::subtract;
 use super::{Bindings, Fragments, Import, Inference, Operations, Usages};
 
+/// Operation that selects a field, along with the `@defer` directive that
+/// makes the field possibly absent from its initial response (if any).
+pub type Deferral<'a, T> = (
+    &'a Arc<OperationDefinition<T>>,
+    Option<&'a Arc<Directive<T>>>,
+);
+
 #[derive(Debug)]
 pub struct Database<T>
 where
@@ -25,6 +33,9 @@ where
     pub usages: Usages<T>,
     pub interface_implementations: MultiMap<T, T>,
     pub imports: HashMap<String, Import>,
+    pub(crate) schema_definitions: usize,
+    pub(crate) root_operation_types: HashMap<&'static str, T>,
+    pub(crate) reachable_types: HashSet<T>,
     pub(crate) directive_definitions_by_name: MultiMap<T, Arc<DirectiveDefinition<T>>>,
     pub(crate) type_definitions_by_name: MultiMap<T, Arc<TypeDefinition<T>>>,
     pub(crate) type_extensions_by_name: MultiMap<T, Arc<TypeExtension<T>>>,
@@ -44,6 +55,9 @@ where
             usages: Default::default(),
             interface_implementations: Default::default(),
             imports: Default::default(),
+            schema_definitions: 0,
+            root_operation_types: Default::default(),
+            reachable_types: Default::default(),
             directive_definitions_by_name: Default::default(),
             type_definitions_by_name: Default::default(),
             type_extensions_by_name: Default::default(),
@@ -78,7 +92,100 @@ where
             document.traverse(&Indexer, &mut database);
         }
 
-        let directives = database
+        database.resolve_imports();
+
+        for url in database.imports.keys().cloned().collect::<Vec<_>>() {
+            let Some(Ok(document)) = imports.get(&url) else {
+                continue;
+            };
+
+            document.traverse(&Indexer, &mut database);
+        }
+
+        database.resolve_root_operation_types();
+        database.resolve_reachable_types();
+
+        for document in docs.iter() {
+            document.traverse(&Inferencer, &mut InferenceState::new(&mut database));
+        }
+
+        database
+    }
+}
+
+impl<T> Database<T>
+where
+    T: Borrow<str> + Clone + Eq + From<&'static str> + Hash + ToString,
+{
+    /// Adds the bindings of a single definition. Definitions that depend on it
+    /// must be inferred again, after `resolve` has been called.
+    pub fn index(&mut self, definition: &Arc<Definition<T>>) {
+        definition.traverse(&Indexer, self);
+    }
+
+    /// Removes the bindings of a single definition that was previously indexed.
+    pub fn unindex(&mut self, definition: &Arc<Definition<T>>) {
+        let mut index = Database::new();
+        definition.traverse(&Indexer, &mut index);
+        self.subtract(&index);
+    }
+
+    pub fn infer(&mut self, definition: &Arc<Definition<T>>) {
+        definition.traverse(&Inferencer, &mut InferenceState::new(self));
+    }
+
+    pub fn uninfer(&mut self, definition: &Arc<Definition<T>>) {
+        definition.traverse(&Invalidator, self);
+    }
+
+    /// Resolves the imports, root operation types and reachable types, which
+    /// depend on all definitions at once.
+    pub fn resolve(&mut self) {
+        self.resolve_imports();
+        self.resolve_root_operation_types();
+        self.resolve_reachable_types();
+    }
+
+    fn subtract(&mut self, other: &Database<T>) {
+        self.definitions.subtract(&other.definitions);
+        self.extensions.subtract(&other.extensions);
+        self.operations.subtract(&other.operations);
+        self.fragments.subtract(&other.fragments);
+        self.schema_definitions -= other.schema_definitions;
+
+        for (interface, types) in other.interface_implementations.iter_all() {
+            let Some(implementations) = self.interface_implementations.get_vec_mut::<T>(interface)
+            else {
+                continue;
+            };
+
+            for ty in types {
+                if let Some(index) = implementations.iter().position(|other| other == ty) {
+                    implementations.remove(index);
+                }
+            }
+
+            if implementations.is_empty() {
+                self.interface_implementations.remove::<T>(interface);
+            }
+        }
+
+        subtract(
+            &mut self.directive_definitions_by_name,
+            &other.directive_definitions_by_name,
+        );
+        subtract(
+            &mut self.type_definitions_by_name,
+            &other.type_definitions_by_name,
+        );
+        subtract(
+            &mut self.type_extensions_by_name,
+            &other.type_extensions_by_name,
+        );
+    }
+
+    fn resolve_imports(&mut self) {
+        self.imports = self
             .schema_directives()
             .flat_map(|directive| {
                 let Some(name) = directive.name.ok() else {
@@ -118,29 +225,104 @@ where
                 };
 
                 let import = Import {
+                    span: directive.span(),
                     headers,
                     refresh: Duration::from_secs(60),
                 };
 
                 Some((url.to_string(), import))
             })
-            .collect::<HashMap<_, _>>();
+            .collect();
+    }
+}
+
+impl<T> Database<T>
+where
+    T: Clone + Eq + From<&'static str> + Hash,
+{
+    fn resolve_root_operation_types(&mut self) {
+        let mut root_operation_types = HashMap::new();
 
-        for url in directives.keys() {
-            let Some(Ok(document)) = imports.get(url) else {
+        for definition in self.root_operation_type_definitions() {
+            let Some(ty) = definition.named_type.ok() else {
                 continue;
             };
 
-            document.traverse(&Indexer, &mut database);
+            root_operation_types
+                .entry(definition.operation_type.default_type_name())
+                .or_insert_with(|| ty.0.as_ref().clone());
         }
 
-        database.imports = directives;
+        if !self.has_schema_definition() {
+            for name in ["Query", "Mutation", "Subscription"] {
+                root_operation_types
+                    .entry(name)
+                    .or_insert_with(|| T::from(name));
+            }
+        }
 
-        for document in docs.iter() {
-            document.traverse(&Inferencer, &mut InferenceState::new(&mut database));
+        self.root_operation_types = root_operation_types;
+    }
+
+    /// Walks the schema from the root operation types (and the arguments of
+    /// directives) through fields, arguments, interfaces, possible types and
+    /// input fields to find all types that can be reached.
+    fn resolve_reachable_types(&mut self) {
+        let mut stack = self
+            .root_operation_types
+            .values()
+            .filter(|ty| self.type_exists(ty))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if !stack.is_empty() {
+            stack.extend(
+                self.directive_definitions()
+                    .flat_map(|definition| definition.arguments_definition.iter())
+                    .flat_map(|definition| definition.definitions.iter())
+                    .filter_map(|definition| definition.ty.ok()?.name())
+                    .cloned(),
+            );
         }
 
-        database
+        let mut reachable_types = HashSet::new();
+
+        while let Some(ty) = stack.pop() {
+            if reachable_types.contains(&ty) || !self.type_exists(&ty) {
+                continue;
+            }
+
+            let fields = self.field_definitions(&ty).flat_map(|field| {
+                field.ty.ok().and_then(|ty| ty.name()).into_iter().chain(
+                    field
+                        .arguments_definition
+                        .iter()
+                        .flat_map(|definition| definition.definitions.iter())
+                        .filter_map(|definition| definition.ty.ok()?.name()),
+                )
+            });
+
+            let input_fields = self
+                .input_value_definitions(&ty)
+                .filter_map(|definition| definition.ty.ok()?.name());
+
+            let interfaces = self
+                .implemented_interfaces(&ty)
+                .map(|interface| interface.0.as_ref());
+
+            stack.extend(
+                fields
+                    .chain(input_fields)
+                    .chain(interfaces)
+                    .chain(self.possible_types(&ty))
+                    .cloned()
+                    .collect::<Vec<_>>(),
+            );
+
+            reachable_types.insert(ty);
+        }
+
+        self.reachable_types = reachable_types;
     }
 }
 
@@ -189,6 +371,13 @@ where
             .iter()
     }
 
+    pub fn directive_definitions(&self) -> impl Iterator<Item = &DirectiveDefinition<T>> {
+        self.directive_definitions_by_name
+            .iter_all()
+            .flat_map(|(_, defs)| defs)
+            .map(AsRef::as_ref)
+    }
+
     pub fn directive_definitions_by_name(
         &self,
         name: &T,
@@ -215,6 +404,11 @@ where
             .any(|definition| definition.is_object_type())
     }
 
+    pub fn is_input_object_type(&self, name: &T) -> bool {
+        self.type_definitions_by_name(name)
+            .any(|definition| definition.is_input_object_type())
+    }
+
     pub fn is_composite_type(&self, name: &T) -> bool {
         self.type_definitions_by_name(name)
             .any(|definition| definition.is_composite())
@@ -363,6 +557,140 @@ where
             .chain(self.extensions.schema_directives.iter())
     }
 
+    /// Returns the types that can be reached from the root operation types,
+    /// which is empty if none of the root operation types exist.
+    pub fn reachable_types(&self) -> &HashSet<T> {
+        &self.reachable_types
+    }
+
+    pub fn is_reachable_type(&self, ty: &T) -> bool {
+        self.reachable_types.contains(ty)
+    }
+
+    pub fn has_schema_definition(&self) -> bool {
+        self.schema_definitions > 0
+    }
+
+    pub fn root_operation_type_definitions(
+        &self,
+    ) -> impl Iterator<Item = &RootOperationTypeDefinition<T>> {
+        self.definitions
+            .root_operation_types
+            .iter()
+            .chain(self.extensions.root_operation_types.iter())
+    }
+
+    /// Returns the root type of the given operation type (or query if omitted),
+    /// falling back to the default names if there is no schema definition.
+    pub fn root_operation_type(&self, operation_type: Option<&OperationType<T>>) -> Option<&T> {
+        self.root_operation_types.get(
+            operation_type
+                .map(OperationType::default_type_name)
+                .unwrap_or("Query"),
+        )
+    }
+
+    /// Returns the operations that select the given field (directly or through
+    /// fragments). A field of a fragment may be deferred in some operations
+    /// but not in others, and it isn't deferred in an operation that also
+    /// selects it without `@defer`.
+    pub fn deferrals<'a>(&'a self, field: &Arc<Field<T>>) -> Vec<Deferral<'a, T>>
+    where
+        T: Borrow<str>,
+    {
+        let operations = self
+            .operations
+            .by_name
+            .keys()
+            .flat_map(|name| self.operations.by_name(name))
+            .chain(self.operations.nameless());
+
+        operations
+            .filter_map(|operation| {
+                let mut deferral = None;
+
+                self.find_deferral(
+                    operation.selection_set.ok()?,
+                    field,
+                    None,
+                    &mut vec![],
+                    &mut deferral,
+                );
+
+                Some((operation, deferral?))
+            })
+            .collect()
+    }
+
+    fn find_deferral<'a>(
+        &'a self,
+        set: &'a Arc<SelectionSet<T>>,
+        field: &Arc<Field<T>>,
+        deferred: Option<&'a Arc<Directive<T>>>,
+        visited: &mut Vec<&'a T>,
+        deferral: &mut Option<Option<&'a Arc<Directive<T>>>>,
+    ) where
+        T: Borrow<str>,
+    {
+        let defer = |directives: Option<&'a Directives<T>>| {
+            directives.and_then(Directives::defer).or(deferred)
+        };
+
+        for selection in set.selections.iter() {
+            match selection {
+                Selection::Field(selected) => {
+                    if Arc::ptr_eq(selected, field) {
+                        *deferral = Some(match *deferral {
+                            Some(previous) => previous.and(deferred),
+                            None => deferred,
+                        });
+                    }
+
+                    if let Some(set) = selected.selection_set.as_ref() {
+                        self.find_deferral(set, field, deferred, visited, deferral);
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.fragment_name.as_ref();
+
+                    if visited.contains(&name) {
+                        continue;
+                    }
+
+                    let Some(set) = self
+                        .fragments
+                        .by_name(name)
+                        .next()
+                        .and_then(|fragment| fragment.selection_set.ok())
+                    else {
+                        continue;
+                    };
+
+                    visited.push(name);
+                    self.find_deferral(
+                        set,
+                        field,
+                        defer(spread.directives.as_ref()),
+                        visited,
+                        deferral,
+                    );
+                    visited.pop();
+                }
+                Selection::InlineFragment(fragment) => {
+                    if let Some(set) = fragment.selection_set.ok() {
+                        self.find_deferral(
+                            set,
+                            field,
+                            defer(fragment.directives.as_ref()),
+                            visited,
+                            deferral,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
     fn both<'a, F, O>(&'a self, apply: F) -> impl Iterator<Item = O::Item> + 'a
     where
         F: Fn(&'a Bindings<T>) -> O,
@@ -374,4 +702,99 @@ where
     pub fn type_directives<'a>(&'a self, ty: &'a T) -> impl Iterator<Item = &Arc<Directive<T>>> {
         self.both(move |bindings| bindings.type_directives.get_vec(ty).into_iter().flatten())
     }
+
+    pub fn is_one_of_input_object(&self, ty: &T) -> bool
+    where
+        T: Borrow<str>,
+    {
+        self.is_input_object_type(ty)
+            && self.type_directives(ty).any(|directive| {
+                directive
+                    .name
+                    .ok()
+                    .map(|name| name.as_ref().borrow() == "oneOf")
+                    .unwrap_or_default()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use litho_language::ast::*;
+    use litho_language::Parse;
+
+    use super::Database;
+
+    #[test]
+    fn test_deferrals() {
+        let (document, _) = Document::<String>::parse_from_str(
+            Default::default(),
+            r#"
+            type Query { user: User }
+            type User { id: ID, name: String }
+
+            query Deferred { user { ...Name @defer } }
+            query Eager { user { ...Name } }
+            query Mixed { user { ...Name ... @defer { ...Name } } }
+            query Inline { user { ... @defer { ...Name } } }
+
+            fragment Name on User { name ... @defer { id } }
+            "#,
+        )
+        .unwrap();
+        let database = Database::from_iter([&document]);
+
+        let Definition::ExecutableDefinition(ExecutableDefinition::FragmentDefinition(fragment)) =
+            document.definitions.last().unwrap().as_ref()
+        else {
+            unreachable!()
+        };
+
+        let selections = &fragment.selection_set.ok().unwrap().selections;
+        let [Selection::Field(name), Selection::InlineFragment(inline)] = selections.as_slice()
+        else {
+            unreachable!()
+        };
+        let Selection::Field(id) = &inline.selection_set.ok().unwrap().selections[0] else {
+            unreachable!()
+        };
+
+        let deferrals = |field: &Arc<Field<String>>| {
+            let mut deferrals = database
+                .deferrals(field)
+                .into_iter()
+                .map(|(operation, directive)| {
+                    (
+                        operation.name.as_ref().unwrap().as_ref().as_str(),
+                        directive.is_some(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            deferrals.sort();
+            deferrals
+        };
+
+        assert_eq!(
+            deferrals(name),
+            vec![
+                ("Deferred", true),
+                ("Eager", false),
+                ("Inline", true),
+                ("Mixed", false)
+            ]
+        );
+        assert_eq!(
+            deferrals(id),
+            vec![
+                ("Deferred", true),
+                ("Eager", true),
+                ("Inline", true),
+                ("Mixed", true)
+            ]
+        );
+        assert!(database.inference.deferred_by_field(name).is_none());
+        assert!(database.inference.deferred_by_field(id).is_some());
+    }
 }
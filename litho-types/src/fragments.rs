@@ -20,6 +20,14 @@ where
     pub fn by_name(&self, name: &T) -> impl Iterator<Item = &Arc<FragmentDefinition<T>>> {
         self.by_name.get(name)
     }
+
+    pub fn names(&self) -> impl Iterator<Item = &T> {
+        self.by_name.keys()
+    }
+
+    pub fn subtract(&mut self, other: &Fragments<T>) {
+        self.by_name.subtract(&other.by_name);
+    }
 }
 
 impl<T> Default for Fragments<T>
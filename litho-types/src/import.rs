@@ -1,3 +1,4 @@
+use litho_language::lex::Span;
 use serde::Deserialize;
 
 use std::time::Duration;
@@ -10,6 +11,8 @@ pub struct ImportHeader {
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Import {
+    /// Span of the `@litho` directive that declares this import.
+    pub span: Span,
     pub headers: Vec<ImportHeader>,
     pub refresh: Duration,
 }
@@ -30,12 +30,23 @@ where
         node: &'ast SchemaDefinition<T>,
         accumulator: &mut Self::Accumulator,
     ) {
+        accumulator.schema_definitions += 1;
+
         accumulator.definitions.schema_directives.extend(
             node.directives
                 .iter()
                 .flat_map(|directives| directives.directives.iter())
                 .cloned(),
-        )
+        );
+
+        accumulator.definitions.root_operation_types.extend(
+            node.type_definitions
+                .ok()
+                .and_then(|definitions| definitions.definitions.ok())
+                .into_iter()
+                .flatten()
+                .cloned(),
+        );
     }
 
     fn visit_schema_extension(
@@ -48,7 +59,16 @@ where
                 .iter()
                 .flat_map(|directives| directives.directives.iter())
                 .cloned(),
-        )
+        );
+
+        accumulator.extensions.root_operation_types.extend(
+            node.type_definitions
+                .as_ref()
+                .and_then(|definitions| definitions.definitions.ok())
+                .into_iter()
+                .flatten()
+                .cloned(),
+        );
     }
 
     fn visit_type_definition(
@@ -14,6 +14,7 @@ pub struct Inference<T> {
     pub types_for_values: Inferred<Value<T>, Type<T>>,
     pub default_value_for_values: Inferred<Value<T>, Value<T>>,
     pub definitions_for_variable: InferredMany<Value<T>, VariableDefinition<T>>,
+    pub deferred_fields: Inferred<Field<T>, Directive<T>>,
 }
 
 impl<T> Inference<T> {
@@ -34,6 +35,14 @@ impl<T> Inference<T> {
             .as_ref()
     }
 
+    /// Returns the `@defer` directive of the operation or fragment that
+    /// contains the given field, which defers it wherever it is selected. See
+    /// `Database::deferrals` for fields of fragments that are deferred by some
+    /// of their spreads.
+    pub fn deferred_by_field(&self, field: &Arc<Field<T>>) -> Option<&Arc<Directive<T>>> {
+        self.deferred_fields.get(field)
+    }
+
     pub fn type_for_field(&self, field: &Arc<Field<T>>) -> Option<&Arc<Type<T>>> {
         self.field_definitions_by_field.get(field)?.ty.ok()
     }
@@ -50,6 +59,7 @@ impl<T> Default for Inference<T> {
             types_for_values: Default::default(),
             default_value_for_values: Default::default(),
             definitions_for_variable: Default::default(),
+            deferred_fields: Default::default(),
         }
     }
 }
@@ -1,3 +1,4 @@
+use std::borrow::Borrow;
 use std::hash::Hash;
 use std::sync::Arc;
 
@@ -13,6 +14,7 @@ where
     var_scopes: Vec<Option<&'a VariableDefinitions<T>>>,
     stack: Vec<Option<T>>,
     value_type: Vec<Option<Arc<Type<T>>>>,
+    deferred: Vec<Option<Arc<Directive<T>>>>,
 }
 
 impl<'a, T> InferenceState<'a, T>
@@ -25,6 +27,7 @@ where
             var_scopes: vec![],
             stack: vec![],
             value_type: vec![],
+            deferred: vec![],
         }
     }
 }
@@ -33,7 +36,7 @@ pub struct Inferencer;
 
 impl<'ast, T> Visit<'ast, T> for Inferencer
 where
-    T: From<&'static str> + Clone + Eq + Hash + 'ast,
+    T: From<&'static str> + Borrow<str> + Clone + Eq + Hash + 'ast,
 {
     type Accumulator = InferenceState<'ast, T>;
 
@@ -42,6 +45,11 @@ where
         node: &'ast Arc<OperationDefinition<T>>,
         accumulator: &mut Self::Accumulator,
     ) {
+        accumulator
+            .var_scopes
+            .push(node.variable_definitions.as_ref());
+        accumulator.deferred.push(None);
+
         let selection_set = match node.selection_set.ok() {
             Some(set) => set,
             None => {
@@ -51,11 +59,15 @@ where
             }
         };
 
-        let name = T::from(match node.ty {
-            Some(OperationType::Query(_)) | None => "Query",
-            Some(OperationType::Mutation(_)) => "Mutation",
-            Some(OperationType::Subscription(_)) => "Subscription",
-        });
+        let Some(name) = accumulator
+            .database
+            .root_operation_type(node.ty.as_ref())
+            .cloned()
+        else {
+            accumulator.stack.push(None);
+
+            return;
+        };
 
         accumulator
             .database
@@ -64,10 +76,6 @@ where
             .insert(selection_set, &Arc::new(name.to_owned()));
 
         accumulator.stack.push(Some(name));
-
-        accumulator
-            .var_scopes
-            .push(node.variable_definitions.as_ref());
     }
 
     fn post_visit_operation_definition(
@@ -77,6 +85,7 @@ where
     ) {
         accumulator.stack.pop();
         accumulator.var_scopes.pop();
+        accumulator.deferred.pop();
     }
 
     fn visit_fragment_definition(
@@ -99,6 +108,8 @@ where
                 .type_by_selection_set
                 .insert(selection_set, &Arc::new(name.to_owned()));
         }
+
+        accumulator.deferred.push(None);
     }
 
     fn post_visit_fragment_definition(
@@ -107,6 +118,7 @@ where
         accumulator: &mut Self::Accumulator,
     ) {
         accumulator.stack.pop();
+        accumulator.deferred.pop();
     }
 
     fn visit_inline_fragment(
@@ -138,6 +150,13 @@ where
                 );
             }
         }
+
+        let deferred = match node.directives.as_ref().and_then(Directives::defer) {
+            Some(directive) => Some(directive.clone()),
+            None => accumulator.deferred.last().cloned().flatten(),
+        };
+
+        accumulator.deferred.push(deferred);
     }
 
     fn post_visit_inline_fragment(
@@ -146,9 +165,18 @@ where
         accumulator: &mut Self::Accumulator,
     ) {
         accumulator.stack.pop();
+        accumulator.deferred.pop();
     }
 
     fn visit_field(&self, node: &'ast Arc<Field<T>>, accumulator: &mut Self::Accumulator) {
+        if let Some(Some(directive)) = accumulator.deferred.last() {
+            accumulator
+                .database
+                .inference
+                .deferred_fields
+                .insert(node, directive);
+        }
+
         let ty = accumulator.stack.last().into_iter().flatten().next();
         let name = node.name.ok();
 
@@ -409,6 +437,12 @@ where
             .definition_for_directives
             .insert(node, &definition);
 
+        accumulator
+            .database
+            .usages
+            .directives
+            .track(&definition, node);
+
         if let Some((arguments, definition)) = node
             .arguments
             .as_ref()
@@ -422,3 +456,58 @@ where
         }
     }
 }
+
+/// Removes the inference results (and tracked usages) of every node that it
+/// visits, so that a definition can be inferred again after its dependencies
+/// have changed.
+pub struct Invalidator;
+
+impl<'ast, T> Visit<'ast, T> for Invalidator
+where
+    T: Eq + Hash + 'ast,
+{
+    type Accumulator = Database<T>;
+
+    fn visit_selection_set(
+        &self,
+        node: &'ast Arc<SelectionSet<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        accumulator.inference.type_by_selection_set.remove(node);
+    }
+
+    fn visit_field(&self, node: &'ast Arc<Field<T>>, accumulator: &mut Self::Accumulator) {
+        accumulator
+            .inference
+            .field_definitions_by_field
+            .remove(node);
+        accumulator.inference.deferred_fields.remove(node);
+    }
+
+    fn visit_arguments(&self, node: &'ast Arc<Arguments<T>>, accumulator: &mut Self::Accumulator) {
+        accumulator.inference.definition_for_arguments.remove(node);
+    }
+
+    fn visit_argument(&self, node: &'ast Arc<Argument<T>>, accumulator: &mut Self::Accumulator) {
+        accumulator.inference.definitions_for_arguments.remove(node);
+    }
+
+    fn visit_value(&self, node: &'ast Arc<Value<T>>, accumulator: &mut Self::Accumulator) {
+        accumulator.inference.types_for_values.remove(node);
+        accumulator.inference.default_value_for_values.remove(node);
+        accumulator.inference.definitions_for_variable.remove(node);
+    }
+
+    fn visit_fragment_spread(
+        &self,
+        node: &'ast Arc<FragmentSpread<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        accumulator.usages.fragments.untrack(node);
+    }
+
+    fn visit_directive(&self, node: &'ast Arc<Directive<T>>, accumulator: &mut Self::Accumulator) {
+        accumulator.inference.definition_for_directives.remove(node);
+        accumulator.usages.directives.untrack(node);
+    }
+}
@@ -20,6 +20,10 @@ impl<K, V> Inferred<K, V> {
     pub fn insert(&mut self, node: &Arc<K>, value: &Arc<V>) {
         self.map.insert(self.key(node), value.to_owned());
     }
+
+    pub fn remove(&mut self, node: &Arc<K>) {
+        self.map.remove(&self.key(node));
+    }
 }
 
 impl<K, V> Default for Inferred<K, V> {
@@ -52,6 +56,10 @@ impl<K, V> InferredMany<K, V> {
             .or_default()
             .push(value.to_owned());
     }
+
+    pub fn remove(&mut self, node: &Arc<K>) {
+        self.map.remove(&self.key(node));
+    }
 }
 
 impl<K, V> Default for InferredMany<K, V> {
@@ -1,4 +1,5 @@
 mod bindings;
+pub mod coordinate;
 mod database;
 mod directives;
 mod fragments;
@@ -11,10 +12,12 @@ mod map;
 mod named;
 mod operations;
 mod references;
+pub mod schema;
+pub mod selection;
 mod usages;
 
 pub use bindings::Bindings;
-pub use database::Database;
+pub use database::{Database, Deferral};
 pub use directives::{DirectiveLocationKind, DirectiveTarget};
 pub use fragments::Fragments;
 pub use import::Import;
@@ -27,9 +27,47 @@ where
         self.0.get_vec(key).map(Vec::as_slice).into_iter().flatten()
     }
 
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.0.keys()
+    }
+
     pub fn len(&self) -> usize {
         self.0.iter().count()
     }
+
+    pub fn subtract(&mut self, other: &Map<K, V>) {
+        subtract(&mut self.0, &other.0)
+    }
+}
+
+/// Removes the given value (compared by pointer) from the values of a key.
+pub(crate) fn remove<K, V>(map: &mut MultiMap<K, Arc<V>>, key: &K, value: &Arc<V>)
+where
+    K: Eq + Hash,
+{
+    let Some(values) = map.get_vec_mut(key) else {
+        return;
+    };
+
+    if let Some(index) = values.iter().position(|other| Arc::ptr_eq(other, value)) {
+        values.remove(index);
+    }
+
+    if values.is_empty() {
+        map.remove(key);
+    }
+}
+
+/// Removes all values of the other map (compared by pointer) from this map.
+pub(crate) fn subtract<K, V>(map: &mut MultiMap<K, Arc<V>>, other: &MultiMap<K, Arc<V>>)
+where
+    K: Eq + Hash,
+{
+    for (key, values) in other.iter_all() {
+        for value in values {
+            remove(map, key, value);
+        }
+    }
 }
 
 impl<K, V> Default for Map<K, V>
@@ -4,6 +4,8 @@ use std::sync::Arc;
 
 use multimap::MultiMap;
 
+use super::map::subtract;
+
 #[derive(Debug)]
 pub struct Named<T, V>(HashMap<T, MultiMap<T, Arc<V>>>)
 where
@@ -47,6 +49,20 @@ where
             .or_default()
             .insert(name.to_owned(), value.to_owned());
     }
+
+    pub fn subtract(&mut self, other: &Named<T, V>) {
+        for (ty, other) in other.0.iter() {
+            let Some(map) = self.0.get_mut(ty) else {
+                continue;
+            };
+
+            subtract(map, other);
+
+            if map.is_empty() {
+                self.0.remove(ty);
+            }
+        }
+    }
 }
 
 impl<T, V> Default for Named<T, V>
@@ -29,6 +29,16 @@ where
     pub fn len(&self) -> usize {
         self.by_name.len() + self.nameless.len()
     }
+
+    pub fn subtract(&mut self, other: &Operations<T>) {
+        self.by_name.subtract(&other.by_name);
+        self.nameless.retain(|operation| {
+            !other
+                .nameless
+                .iter()
+                .any(|other| Arc::ptr_eq(other, operation))
+        });
+    }
 }
 
 impl<T> Default for Operations<T>
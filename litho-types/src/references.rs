@@ -1,26 +1,48 @@
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
 use multimap::MultiMap;
 
 #[derive(Debug)]
-pub struct References<K, V>(MultiMap<usize, Arc<V>>, PhantomData<K>);
+pub struct References<K, V> {
+    usages: MultiMap<usize, Arc<V>>,
+    owners: HashMap<usize, usize>,
+    phantom: PhantomData<K>,
+}
 
 impl<K, V> References<K, V> {
     pub fn new() -> References<K, V> {
         Default::default()
     }
 
-    fn key(node: &Arc<K>) -> usize {
+    fn key<N>(node: &Arc<N>) -> usize {
         Arc::as_ptr(node) as usize
     }
 
     pub fn track(&mut self, node: &Arc<K>, usage: &Arc<V>) {
-        self.0.insert(Self::key(node), usage.to_owned());
+        self.usages.insert(Self::key(node), usage.to_owned());
+        self.owners.insert(Self::key(usage), Self::key(node));
+    }
+
+    pub fn untrack(&mut self, usage: &Arc<V>) {
+        let Some(key) = self.owners.remove(&Self::key(usage)) else {
+            return;
+        };
+
+        let Some(usages) = self.usages.get_vec_mut(&key) else {
+            return;
+        };
+
+        usages.retain(|other| !Arc::ptr_eq(other, usage));
+
+        if usages.is_empty() {
+            self.usages.remove(&key);
+        }
     }
 
     pub fn usages(&self, node: &Arc<K>) -> impl Iterator<Item = &Arc<V>> {
-        self.0
+        self.usages
             .get_vec(&Self::key(node))
             .into_iter()
             .flat_map(Vec::as_slice)
@@ -29,6 +51,10 @@ impl<K, V> References<K, V> {
 
 impl<K, V> Default for References<K, V> {
     fn default() -> Self {
-        References(Default::default(), Default::default())
+        References {
+            usages: Default::default(),
+            owners: Default::default(),
+            phantom: Default::default(),
+        }
     }
 }
@@ -0,0 +1,947 @@
+//! Typed, read-only view of the schema in a [`Database`](crate::Database).
+//!
+//! The handles in this module merge type extensions into their definitions,
+//! resolve type references and skip over nodes that failed to parse, so that
+//! tools (e.g. codegen, hover or custom lints) don't have to piece that
+//! together from the raw AST lookups themselves. Each handle still exposes
+//! its AST node (with `definition()`) and span for diagnostics.
+
+use std::borrow::Borrow;
+use std::fmt::{Display, Formatter, Result};
+use std::hash::Hash;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use litho_language::ast::{self, Node};
+use litho_language::lex::Span;
+
+use crate::Database;
+
+macro_rules! handle {
+    ($name:ident) => {
+        impl<'a, T> Clone for $name<'a, T>
+        where
+            T: Eq + Hash,
+        {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<'a, T> Copy for $name<'a, T> where T: Eq + Hash {}
+    };
+}
+
+macro_rules! annotated {
+    ($name:ident) => {
+        impl<'a, T> $name<'a, T>
+        where
+            T: Eq + Hash + Borrow<str>,
+        {
+            pub fn description(&self) -> Option<String> {
+                self.definition
+                    .description
+                    .as_ref()
+                    .map(|description| description.to_string())
+            }
+
+            pub fn directives(&self) -> impl Iterator<Item = &'a Arc<ast::Directive<T>>> {
+                let definition: &'a Arc<_> = self.definition;
+
+                definition
+                    .directives
+                    .iter()
+                    .flat_map(|directives| directives.directives.iter())
+            }
+
+            pub fn directive(&self, name: &str) -> Option<&'a Arc<ast::Directive<T>>> {
+                self.directives()
+                    .find(|directive| is_named(directive, name))
+            }
+
+            pub fn is_deprecated(&self) -> bool {
+                self.directive("deprecated").is_some()
+            }
+
+            /// Returns the reason of `@deprecated` (or its default reason if
+            /// none is given), or `None` if this isn't deprecated.
+            pub fn deprecation_reason(&self) -> Option<String> {
+                deprecation_reason(self.database, self.directive("deprecated")?)
+            }
+        }
+    };
+}
+
+fn is_named<T>(directive: &ast::Directive<T>, name: &str) -> bool
+where
+    T: Borrow<str>,
+{
+    directive
+        .name
+        .ok()
+        .map(|directive| directive.as_ref().borrow() == name)
+        .unwrap_or_default()
+}
+
+fn deprecation_reason<T>(database: &Database<T>, directive: &ast::Directive<T>) -> Option<String>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    let reason = directive
+        .argument("reason")
+        .and_then(|argument| argument.value.ok())
+        .or_else(|| {
+            database
+                .directive_definitions()
+                .find(|definition| {
+                    definition
+                        .name
+                        .ok()
+                        .map(|name| name.as_ref().borrow() == "deprecated")
+                        .unwrap_or_default()
+                })?
+                .arguments_definition
+                .as_ref()?
+                .definitions
+                .iter()
+                .find(|definition| definition.name.as_ref().borrow() == "reason")?
+                .default_value
+                .as_ref()?
+                .value
+                .ok()
+        });
+
+    match reason.map(AsRef::as_ref) {
+        Some(ast::Value::StringValue(reason)) => Some(reason.to_string()),
+        Some(_) | None => Some("No longer supported".to_owned()),
+    }
+}
+
+impl<T> Database<T>
+where
+    T: Eq + Hash,
+{
+    pub fn schema(&self) -> Schema<'_, T> {
+        Schema { database: self }
+    }
+}
+
+pub struct Schema<'a, T>
+where
+    T: Eq + Hash,
+{
+    database: &'a Database<T>,
+}
+
+handle!(Schema);
+
+impl<'a, T> Schema<'a, T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    pub fn database(&self) -> &'a Database<T> {
+        self.database
+    }
+
+    fn root_operation_type(&self, name: &str) -> Option<ObjectType<'a, T>> {
+        self.object_type(self.database.root_operation_types.get(name)?)
+    }
+
+    pub fn query_type(&self) -> Option<ObjectType<'a, T>> {
+        self.root_operation_type("Query")
+    }
+
+    pub fn mutation_type(&self) -> Option<ObjectType<'a, T>> {
+        self.root_operation_type("Mutation")
+    }
+
+    pub fn subscription_type(&self) -> Option<ObjectType<'a, T>> {
+        self.root_operation_type("Subscription")
+    }
+
+    /// Returns all named types. If a type is defined more than once, only
+    /// its first definition is returned.
+    pub fn types(&self) -> impl Iterator<Item = Type<'a, T>> {
+        let database = self.database;
+
+        database
+            .type_definitions_by_name
+            .iter()
+            .map(move |(name, definition)| Type {
+                database,
+                name,
+                definition,
+            })
+    }
+
+    pub fn ty(&self, name: &T) -> Option<Type<'a, T>> {
+        let definition = self.database.type_definitions_by_name(name).next()?;
+
+        Some(Type {
+            database: self.database,
+            name: definition.name().ok()?.as_ref(),
+            definition,
+        })
+    }
+
+    /// Returns the object or interface type with the given name.
+    pub fn object_type(&self, name: &T) -> Option<ObjectType<'a, T>> {
+        self.ty(name)?.as_object_type()
+    }
+
+    /// Returns all directive definitions (including builtin directives if
+    /// those are part of the database).
+    pub fn directives(&self) -> impl Iterator<Item = Directive<'a, T>> {
+        let database = self.database;
+
+        database
+            .directive_definitions_by_name
+            .iter()
+            .map(move |(_, definition)| Directive {
+                database,
+                definition,
+            })
+    }
+
+    pub fn directive(&self, name: &T) -> Option<Directive<'a, T>> {
+        Some(Directive {
+            database: self.database,
+            definition: self.database.directive_definitions_by_name(name).next()?,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypeKind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+}
+
+/// Named type of any kind.
+pub struct Type<'a, T>
+where
+    T: Eq + Hash,
+{
+    database: &'a Database<T>,
+    name: &'a T,
+    definition: &'a Arc<ast::TypeDefinition<T>>,
+}
+
+handle!(Type);
+
+impl<'a, T> Type<'a, T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    pub fn name(&self) -> &'a T {
+        self.name
+    }
+
+    pub fn kind(&self) -> TypeKind {
+        match self.definition.as_ref() {
+            ast::TypeDefinition::ScalarTypeDefinition(_) => TypeKind::Scalar,
+            ast::TypeDefinition::ObjectTypeDefinition(_) => TypeKind::Object,
+            ast::TypeDefinition::InterfaceTypeDefinition(_) => TypeKind::Interface,
+            ast::TypeDefinition::UnionTypeDefinition(_) => TypeKind::Union,
+            ast::TypeDefinition::EnumTypeDefinition(_) => TypeKind::Enum,
+            ast::TypeDefinition::InputObjectTypeDefinition(_) => TypeKind::InputObject,
+        }
+    }
+
+    pub fn definition(&self) -> &'a Arc<ast::TypeDefinition<T>> {
+        self.definition
+    }
+
+    /// Returns the span of the name of this type's definition.
+    pub fn span(&self) -> Span {
+        match self.definition.name().ok() {
+            Some(name) => name.span(),
+            None => self.definition.span(),
+        }
+    }
+
+    pub fn description(&self) -> Option<String> {
+        self.definition
+            .description()
+            .map(|description| description.to_string())
+    }
+
+    /// Returns the directives of this type's definition and extensions.
+    pub fn directives(&self) -> impl Iterator<Item = &'a Arc<ast::Directive<T>>> {
+        self.database.type_directives(self.name)
+    }
+
+    pub fn directive(&self, name: &str) -> Option<&'a Arc<ast::Directive<T>>> {
+        self.directives()
+            .find(|directive| is_named(directive, name))
+    }
+
+    pub fn is_reachable(&self) -> bool {
+        self.database.is_reachable_type(self.name)
+    }
+
+    pub fn as_object_type(&self) -> Option<ObjectType<'a, T>> {
+        match self.kind() {
+            TypeKind::Object | TypeKind::Interface => Some(ObjectType { ty: *self }),
+            _ => None,
+        }
+    }
+
+    /// Returns the values of this enum type (including extensions), in
+    /// declaration order.
+    pub fn enum_values(&self) -> impl Iterator<Item = EnumValue<'a, T>> {
+        let database = self.database;
+
+        let definition = match self.definition.as_ref() {
+            ast::TypeDefinition::EnumTypeDefinition(definition) => {
+                definition.values_definition.as_ref()
+            }
+            _ => None,
+        };
+
+        let extensions = self.extensions().flat_map(|extension| match extension {
+            ast::TypeExtension::EnumTypeExtension(extension) => {
+                extension.values_definition.as_ref()
+            }
+            _ => None,
+        });
+
+        definition
+            .into_iter()
+            .chain(extensions)
+            .flat_map(|values| values.definitions.iter())
+            .map(move |definition| EnumValue {
+                database,
+                definition,
+            })
+    }
+
+    pub fn enum_value(&self, name: &T) -> Option<EnumValue<'a, T>> {
+        Some(EnumValue {
+            database: self.database,
+            definition: self
+                .database
+                .enum_value_definitions_by_name(self.name, name)
+                .next()?,
+        })
+    }
+
+    /// Returns the fields of this input object type (including extensions),
+    /// in declaration order.
+    pub fn input_fields(&self) -> impl Iterator<Item = InputField<'a, T>> {
+        let database = self.database;
+
+        let definition = match self.definition.as_ref() {
+            ast::TypeDefinition::InputObjectTypeDefinition(definition) => {
+                definition.fields_definition.as_ref()
+            }
+            _ => None,
+        };
+
+        let extensions = self.extensions().flat_map(|extension| match extension {
+            ast::TypeExtension::InputObjectTypeExtension(extension) => {
+                extension.fields_definition.as_ref()
+            }
+            _ => None,
+        });
+
+        definition
+            .into_iter()
+            .chain(extensions)
+            .flat_map(|fields| fields.definitions.iter())
+            .map(move |definition| InputField {
+                database,
+                definition,
+            })
+    }
+
+    pub fn input_field(&self, name: &T) -> Option<InputField<'a, T>> {
+        Some(InputField {
+            database: self.database,
+            definition: self
+                .database
+                .input_value_definitions_by_name(self.name, name)
+                .next()?,
+        })
+    }
+
+    /// Returns the member types of this union type (including extensions).
+    pub fn member_types(&self) -> impl Iterator<Item = ObjectType<'a, T>> {
+        let schema = self.database.schema();
+
+        let definition = match self.definition.as_ref() {
+            ast::TypeDefinition::UnionTypeDefinition(definition) => {
+                definition.member_types.as_ref()
+            }
+            _ => None,
+        };
+
+        let extensions = self.extensions().flat_map(|extension| match extension {
+            ast::TypeExtension::UnionTypeExtension(extension) => extension.member_types.as_ref(),
+            _ => None,
+        });
+
+        definition
+            .into_iter()
+            .chain(extensions)
+            .flat_map(|member_types| member_types.named_types())
+            .flat_map(move |ty| schema.object_type(ty.0.as_ref()))
+    }
+
+    /// Returns the extensions of this type, in the order in which they were
+    /// indexed.
+    fn extensions(&self) -> impl Iterator<Item = &'a ast::TypeExtension<T>> {
+        self.database
+            .type_extensions_by_name(self.name)
+            .map(AsRef::as_ref)
+    }
+
+    /// Returns the object types that this type can resolve to: the type
+    /// itself, the members of a union or the implementations of an interface
+    /// (in declaration order).
+    pub fn possible_types(&self) -> impl Iterator<Item = ObjectType<'a, T>> {
+        let schema = self.database.schema();
+
+        let types = match self.kind() {
+            TypeKind::Object => self.as_object_type().into_iter().collect(),
+            TypeKind::Interface => self
+                .database
+                .interface_implementations(self.name)
+                .flat_map(|ty| schema.object_type(ty))
+                .collect(),
+            TypeKind::Union => self.member_types().collect(),
+            _ => vec![],
+        };
+
+        types
+            .into_iter()
+            .filter(|ty: &ObjectType<'a, T>| ty.kind() == TypeKind::Object)
+    }
+}
+
+/// Object or interface type, i.e. a named type that has fields.
+pub struct ObjectType<'a, T>
+where
+    T: Eq + Hash,
+{
+    ty: Type<'a, T>,
+}
+
+handle!(ObjectType);
+
+impl<'a, T> Deref for ObjectType<'a, T>
+where
+    T: Eq + Hash,
+{
+    type Target = Type<'a, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.ty
+    }
+}
+
+impl<'a, T> ObjectType<'a, T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    pub fn is_interface(&self) -> bool {
+        self.kind() == TypeKind::Interface
+    }
+
+    /// Returns the fields of this type (including extensions), in declaration
+    /// order.
+    pub fn fields(&self) -> impl Iterator<Item = Field<'a, T>> {
+        let database = self.database;
+        let parent = *self;
+
+        self.definition
+            .fields_definition()
+            .into_iter()
+            .chain(
+                self.extensions()
+                    .flat_map(|extension| extension.fields_definition()),
+            )
+            .flat_map(|fields| fields.definitions.iter())
+            .map(move |definition| Field {
+                database,
+                parent,
+                definition,
+            })
+    }
+
+    pub fn field(&self, name: &T) -> Option<Field<'a, T>> {
+        Some(Field {
+            database: self.database,
+            parent: *self,
+            definition: self
+                .database
+                .field_definitions_by_name(self.name, name)
+                .next()?,
+        })
+    }
+
+    /// Returns the interfaces that this type implements (including
+    /// extensions), skipping interfaces that don't exist.
+    pub fn interfaces(&self) -> impl Iterator<Item = ObjectType<'a, T>> {
+        let schema = self.database.schema();
+
+        self.database
+            .implemented_interfaces(self.name)
+            .flat_map(move |interface| schema.object_type(interface.0.as_ref()))
+    }
+}
+
+pub struct Field<'a, T>
+where
+    T: Eq + Hash,
+{
+    database: &'a Database<T>,
+    parent: ObjectType<'a, T>,
+    definition: &'a Arc<ast::FieldDefinition<T>>,
+}
+
+handle!(Field);
+
+impl<'a, T> Field<'a, T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    pub fn name(&self) -> &'a T {
+        self.definition.name.as_ref()
+    }
+
+    /// Returns the object or interface type that declares this field.
+    pub fn parent(&self) -> ObjectType<'a, T> {
+        self.parent
+    }
+
+    pub fn definition(&self) -> &'a Arc<ast::FieldDefinition<T>> {
+        self.definition
+    }
+
+    pub fn span(&self) -> Span {
+        self.definition.name.span()
+    }
+
+    /// Returns the type of this field, or `None` if it's missing.
+    pub fn ty(&self) -> Option<TypeRef<'a, T>> {
+        Some(TypeRef {
+            database: self.database,
+            ty: self.definition.ty.ok()?,
+        })
+    }
+
+    pub fn arguments(&self) -> impl Iterator<Item = Argument<'a, T>> {
+        let database = self.database;
+        let definition: &'a Arc<_> = self.definition;
+
+        definition
+            .arguments_definition
+            .iter()
+            .flat_map(|arguments| arguments.definitions.iter())
+            .map(move |definition| Argument {
+                database,
+                definition,
+            })
+    }
+
+    pub fn argument(&self, name: &str) -> Option<Argument<'a, T>> {
+        self.arguments()
+            .find(|argument| argument.name().borrow() == name)
+    }
+}
+
+annotated!(Field);
+
+/// Shared implementation of arguments and input fields, which are both input
+/// value definitions.
+macro_rules! input_value {
+    ($name:ident) => {
+        pub struct $name<'a, T>
+        where
+            T: Eq + Hash,
+        {
+            database: &'a Database<T>,
+            definition: &'a Arc<ast::InputValueDefinition<T>>,
+        }
+
+        handle!($name);
+
+        impl<'a, T> $name<'a, T>
+        where
+            T: Eq + Hash + Borrow<str>,
+        {
+            pub fn name(&self) -> &'a T {
+                self.definition.name.as_ref()
+            }
+
+            pub fn definition(&self) -> &'a Arc<ast::InputValueDefinition<T>> {
+                self.definition
+            }
+
+            pub fn span(&self) -> Span {
+                self.definition.name.span()
+            }
+
+            /// Returns the type of this value, or `None` if it's missing.
+            pub fn ty(&self) -> Option<TypeRef<'a, T>> {
+                Some(TypeRef {
+                    database: self.database,
+                    ty: self.definition.ty.ok()?,
+                })
+            }
+
+            pub fn default_value(&self) -> Option<&'a Arc<ast::Value<T>>> {
+                let definition: &'a Arc<_> = self.definition;
+
+                definition.default_value.as_ref()?.value.ok()
+            }
+
+            /// Returns `true` if this has a non-null type without a default
+            /// value.
+            pub fn is_required(&self) -> bool {
+                self.definition.is_required()
+            }
+        }
+
+        annotated!($name);
+    };
+}
+
+input_value!(Argument);
+input_value!(InputField);
+
+pub struct EnumValue<'a, T>
+where
+    T: Eq + Hash,
+{
+    database: &'a Database<T>,
+    definition: &'a Arc<ast::EnumValueDefinition<T>>,
+}
+
+handle!(EnumValue);
+
+impl<'a, T> EnumValue<'a, T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    pub fn name(&self) -> &'a T {
+        self.definition.enum_value.0.as_ref()
+    }
+
+    pub fn definition(&self) -> &'a Arc<ast::EnumValueDefinition<T>> {
+        self.definition
+    }
+
+    pub fn span(&self) -> Span {
+        self.definition.enum_value.0.span()
+    }
+}
+
+annotated!(EnumValue);
+
+/// Directive definition.
+pub struct Directive<'a, T>
+where
+    T: Eq + Hash,
+{
+    database: &'a Database<T>,
+    definition: &'a Arc<ast::DirectiveDefinition<T>>,
+}
+
+handle!(Directive);
+
+impl<'a, T> Directive<'a, T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    /// Returns the name of this directive (without `@`), or `None` if it's
+    /// missing.
+    pub fn name(&self) -> Option<&'a T> {
+        let definition: &'a Arc<_> = self.definition;
+
+        definition.name.ok().map(AsRef::as_ref)
+    }
+
+    pub fn definition(&self) -> &'a Arc<ast::DirectiveDefinition<T>> {
+        self.definition
+    }
+
+    pub fn span(&self) -> Span {
+        match self.definition.name.ok() {
+            Some(name) => name.span(),
+            None => self.definition.span(),
+        }
+    }
+
+    pub fn description(&self) -> Option<String> {
+        self.definition
+            .description
+            .as_ref()
+            .map(|description| description.to_string())
+    }
+
+    pub fn is_repeatable(&self) -> bool {
+        self.definition.repeatable.is_some()
+    }
+
+    pub fn arguments(&self) -> impl Iterator<Item = Argument<'a, T>> {
+        let database = self.database;
+        let definition: &'a Arc<_> = self.definition;
+
+        definition
+            .arguments_definition
+            .iter()
+            .flat_map(|arguments| arguments.definitions.iter())
+            .map(move |definition| Argument {
+                database,
+                definition,
+            })
+    }
+
+    pub fn argument(&self, name: &str) -> Option<Argument<'a, T>> {
+        self.arguments()
+            .find(|argument| argument.name().borrow() == name)
+    }
+
+    pub fn locations(&self) -> impl Iterator<Item = &'a ast::DirectiveLocation<T>> {
+        let definition: &'a Arc<_> = self.definition;
+
+        definition
+            .locations
+            .ok()
+            .into_iter()
+            .flat_map(|locations| locations.locations())
+    }
+}
+
+/// Reference to a (possibly wrapped) type, e.g. the type of a field.
+pub struct TypeRef<'a, T>
+where
+    T: Eq + Hash,
+{
+    database: &'a Database<T>,
+    ty: &'a Arc<ast::Type<T>>,
+}
+
+handle!(TypeRef);
+
+impl<'a, T> TypeRef<'a, T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    pub fn span(&self) -> Span {
+        self.ty.span()
+    }
+
+    pub fn is_non_null(&self) -> bool {
+        matches!(self.ty.as_ref(), ast::Type::NonNull(_))
+    }
+
+    pub fn is_list(&self) -> bool {
+        matches!(self.ty.as_ref(), ast::Type::List(_))
+    }
+
+    /// Returns the type wrapped by a list or non-null type.
+    pub fn of_type(&self) -> Option<TypeRef<'a, T>> {
+        let ty: &'a Arc<_> = self.ty;
+
+        let ty = match ty.as_ref() {
+            ast::Type::Named(_) => return None,
+            ast::Type::List(ty) => ty.ty.ok()?,
+            ast::Type::NonNull(ty) => &ty.ty,
+        };
+
+        Some(TypeRef {
+            database: self.database,
+            ty,
+        })
+    }
+
+    /// Returns the name of the innermost named type.
+    pub fn name(&self) -> Option<&'a T> {
+        let ty: &'a Arc<_> = self.ty;
+
+        ty.name()
+    }
+
+    /// Resolves the innermost named type, or returns `None` if it doesn't
+    /// exist.
+    pub fn named_type(&self) -> Option<Type<'a, T>> {
+        self.database.schema().ty(self.name()?)
+    }
+}
+
+impl<'a, T> Display for TypeRef<'a, T>
+where
+    T: Eq + Hash + ToString,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.ty.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use litho_language::ast::Document;
+    use litho_language::Parse;
+
+    use super::TypeKind;
+    use crate::Database;
+
+    const SCHEMA: &str = r#"
+        "The root query type."
+        type Query implements Node {
+            id: ID!
+            user(id: ID!, legacy: Boolean = false @deprecated(reason: "Unused.")): User
+        }
+
+        extend type Query @cached {
+            users: [User!]! @deprecated
+        }
+
+        interface Node {
+            id: ID!
+        }
+
+        type User implements Node {
+            id: ID!
+            role: Role
+        }
+
+        enum Role {
+            ADMIN
+            USER @deprecated(reason: "Use `MEMBER`.")
+        }
+
+        extend enum Role {
+            MEMBER
+        }
+
+        union Entity = User
+
+        extend union Entity = Query
+
+        directive @cached(ttl: Int = 60) repeatable on OBJECT
+
+        directive @deprecated(
+            reason: String = "No longer supported"
+        ) on FIELD_DEFINITION | ARGUMENT_DEFINITION | INPUT_FIELD_DEFINITION | ENUM_VALUE
+    "#;
+
+    fn database() -> Database<String> {
+        let (document, _) = Document::<String>::parse_from_str(Default::default(), SCHEMA).unwrap();
+
+        Database::from_iter([&document])
+    }
+
+    #[test]
+    fn test_types() {
+        let database = database();
+        let schema = database.schema();
+
+        let query = schema.query_type().unwrap();
+        assert_eq!(query.name(), "Query");
+        assert_eq!(query.kind(), TypeKind::Object);
+        assert_eq!(query.description().as_deref(), Some("The root query type."));
+        assert!(query.directive("cached").is_some());
+        assert!(schema.mutation_type().is_none());
+
+        let fields = query
+            .fields()
+            .map(|field| field.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(fields, vec!["id", "user", "users"]);
+
+        let interfaces = query
+            .interfaces()
+            .map(|interface| interface.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(interfaces, vec!["Node"]);
+
+        let node = schema.object_type(&"Node".to_owned()).unwrap();
+        assert!(node.is_interface());
+        let mut possible_types = node
+            .possible_types()
+            .map(|ty| ty.name().as_str())
+            .collect::<Vec<_>>();
+        possible_types.sort();
+        assert_eq!(possible_types, vec!["Query", "User"]);
+
+        let entity = schema.ty(&"Entity".to_owned()).unwrap();
+        let member_types = entity
+            .member_types()
+            .map(|ty| ty.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(member_types, vec!["User", "Query"]);
+
+        let role = schema.ty(&"Role".to_owned()).unwrap();
+        assert!(role.as_object_type().is_none());
+        let values = role
+            .enum_values()
+            .map(|value| (value.name().as_str(), value.deprecation_reason()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                ("ADMIN", None),
+                ("USER", Some("Use `MEMBER`.".to_owned())),
+                ("MEMBER", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fields() {
+        let database = database();
+        let query = database.schema().query_type().unwrap();
+
+        let user = query.field(&"user".to_owned()).unwrap();
+        assert_eq!(user.parent().name(), "Query");
+        assert!(!user.is_deprecated());
+
+        let ty = user.ty().unwrap();
+        assert_eq!(ty.to_string(), "User");
+        assert!(!ty.is_non_null());
+        assert_eq!(ty.named_type().unwrap().kind(), TypeKind::Object);
+
+        let id = user.argument("id").unwrap();
+        assert!(id.is_required());
+        assert!(id.default_value().is_none());
+
+        let legacy = user.argument("legacy").unwrap();
+        assert!(!legacy.is_required());
+        assert!(legacy.default_value().is_some());
+        assert_eq!(legacy.deprecation_reason().as_deref(), Some("Unused."));
+
+        let users = query.field(&"users".to_owned()).unwrap();
+        assert_eq!(
+            users.deprecation_reason().as_deref(),
+            Some("No longer supported")
+        );
+
+        let ty = users.ty().unwrap();
+        assert_eq!(ty.to_string(), "[User!]!");
+        assert!(ty.is_non_null());
+        assert!(ty.of_type().unwrap().is_list());
+        assert_eq!(ty.name().map(String::as_str), Some("User"));
+    }
+
+    #[test]
+    fn test_directives() {
+        let database = database();
+        let cached = database.schema().directive(&"cached".to_owned()).unwrap();
+
+        assert_eq!(cached.name().map(String::as_str), Some("cached"));
+        assert!(cached.is_repeatable());
+        assert_eq!(cached.locations().count(), 1);
+        assert_eq!(
+            cached.argument("ttl").unwrap().ty().unwrap().to_string(),
+            "Int"
+        );
+    }
+}
@@ -0,0 +1,634 @@
+//! Normalized selection trees of executable operations.
+//!
+//! A selection tree resolves fragment spreads and inline fragments into the
+//! fields that are selected on each possible (concrete object) type, merges
+//! fields with the same response key and records the `@skip` and `@include`
+//! conditions under which each field is included. Each field is annotated with
+//! its definition and output type, so that tools (e.g. codegen or complexity
+//! analysis) don't have to re-implement field collection themselves.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_language::ast;
+
+use crate::Database;
+
+/// Condition that must hold for a selection to be included.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Condition<'a, T> {
+    /// `@skip(if: $variable)`, i.e. the variable must be `false`.
+    Skip(&'a T),
+
+    /// `@include(if: $variable)`, i.e. the variable must be `true`.
+    Include(&'a T),
+
+    /// `@skip(if: true)` or `@include(if: false)`.
+    Never,
+}
+
+impl<'a, T> Clone for Condition<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for Condition<'a, T> {}
+
+fn conditions<'a, T>(directives: Option<&'a ast::Directives<T>>) -> Vec<Condition<'a, T>>
+where
+    T: Borrow<str>,
+{
+    let mut conditions = vec![];
+
+    for (name, skip) in [("skip", true), ("include", false)] {
+        let value = directives
+            .and_then(|directives| directives.directive(name))
+            .and_then(|directive| directive.argument("if"))
+            .and_then(|argument| argument.value.ok())
+            .map(AsRef::as_ref);
+
+        match value {
+            Some(ast::Value::Variable(variable)) => conditions.push(match skip {
+                true => Condition::Skip(variable.name.as_ref()),
+                false => Condition::Include(variable.name.as_ref()),
+            }),
+            Some(ast::Value::BooleanValue(value)) if value.to_bool() == skip => {
+                conditions.push(Condition::Never)
+            }
+            _ => {}
+        }
+    }
+
+    conditions
+}
+
+/// Conditions under which a field is included, in disjunctive normal form: the
+/// field is included if all conditions of at least one alternative hold.
+#[derive(PartialEq, Eq)]
+pub struct Conditions<'a, T> {
+    alternatives: Vec<Vec<Condition<'a, T>>>,
+}
+
+impl<'a, T> Conditions<'a, T>
+where
+    T: Eq,
+{
+    pub fn is_unconditional(&self) -> bool {
+        self.alternatives.iter().any(Vec::is_empty)
+    }
+
+    pub fn alternatives(&self) -> &[Vec<Condition<'a, T>>] {
+        &self.alternatives
+    }
+
+    fn or(&mut self, alternative: Vec<Condition<'a, T>>) {
+        // Alternatives that require all conditions of another alternative (and
+        // possibly more) are redundant.
+        let implies = |a: &[Condition<'a, T>], b: &[Condition<'a, T>]| {
+            b.iter().all(|condition| a.contains(condition))
+        };
+
+        if self
+            .alternatives
+            .iter()
+            .any(|other| implies(&alternative, other))
+        {
+            return;
+        }
+
+        self.alternatives
+            .retain(|other| !implies(other, &alternative));
+        self.alternatives.push(alternative);
+    }
+}
+
+impl<'a, T> Default for Conditions<'a, T> {
+    fn default() -> Self {
+        Conditions {
+            alternatives: vec![],
+        }
+    }
+}
+
+impl<'a, T> Debug for Conditions<'a, T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.alternatives.iter()).finish()
+    }
+}
+
+/// Field that was collected from a selection set or one of its fragments.
+pub struct CollectedField<'a, T> {
+    /// Type of the selection set that directly contains this field.
+    pub parent_type: &'a T,
+    pub field: &'a Arc<ast::Field<T>>,
+
+    /// Conditions of this field and of the fragments that contain it.
+    pub conditions: Vec<Condition<'a, T>>,
+}
+
+/// Fields of one or more selection sets (including those of their fragments),
+/// grouped by response key in order of first appearance.
+pub struct CollectedFields<'a, T> {
+    keys: HashMap<&'a T, usize>,
+    fields: Vec<(&'a T, Vec<CollectedField<'a, T>>)>,
+}
+
+impl<'a, T> Default for CollectedFields<'a, T> {
+    fn default() -> Self {
+        CollectedFields {
+            keys: Default::default(),
+            fields: Default::default(),
+        }
+    }
+}
+
+impl<'a, T> CollectedFields<'a, T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    /// Collects the fields of the given selection set. If an object type is
+    /// given, fragments with a type condition that doesn't apply to that type
+    /// are skipped. Fragments that (indirectly) spread themselves are only
+    /// visited once.
+    pub fn collect(
+        &mut self,
+        database: &'a Database<T>,
+        set: &'a Arc<ast::SelectionSet<T>>,
+        object_type: Option<&T>,
+    ) {
+        self.collect_with(database, set, object_type, &[], &mut vec![])
+    }
+
+    fn collect_with(
+        &mut self,
+        database: &'a Database<T>,
+        set: &'a Arc<ast::SelectionSet<T>>,
+        object_type: Option<&T>,
+        inherited: &[Condition<'a, T>],
+        visited: &mut Vec<&'a T>,
+    ) {
+        let Some(ty) = database.inference.type_by_selection_set.get(set) else {
+            return;
+        };
+
+        let applies = |condition: Option<&T>| match (object_type, condition) {
+            (Some(object_type), Some(condition)) => database
+                .possible_types(condition)
+                .any(|ty| ty == object_type),
+            _ => true,
+        };
+
+        for selection in set.selections.iter() {
+            match selection {
+                ast::Selection::Field(field) => {
+                    let key = field
+                        .alias
+                        .as_ref()
+                        .map(|alias| &alias.name)
+                        .or(field.name.ok())
+                        .map(AsRef::as_ref);
+
+                    if let Some(key) = key {
+                        let conditions = with(inherited, conditions(field.directives.as_ref()));
+
+                        self.push(
+                            key,
+                            CollectedField {
+                                parent_type: ty,
+                                field,
+                                conditions,
+                            },
+                        );
+                    }
+                }
+                ast::Selection::FragmentSpread(spread) => {
+                    let name = spread.fragment_name.as_ref();
+
+                    if visited.contains(&name) {
+                        continue;
+                    }
+
+                    let Some(fragment) = database.fragments.by_name(name).next() else {
+                        continue;
+                    };
+
+                    let condition = fragment
+                        .type_condition
+                        .ok()
+                        .and_then(|condition| condition.named_type.ok())
+                        .map(|ty| ty.0.as_ref());
+
+                    if let (true, Some(set)) = (applies(condition), fragment.selection_set.ok()) {
+                        let conditions = with(inherited, conditions(spread.directives.as_ref()));
+
+                        visited.push(name);
+                        self.collect_with(database, set, object_type, &conditions, visited);
+                        visited.pop();
+                    }
+                }
+                ast::Selection::InlineFragment(fragment) => {
+                    let condition = fragment
+                        .type_condition
+                        .as_ref()
+                        .and_then(|condition| condition.named_type.ok())
+                        .map(|ty| ty.0.as_ref());
+
+                    if let (true, Some(set)) = (applies(condition), fragment.selection_set.ok()) {
+                        let conditions = with(inherited, conditions(fragment.directives.as_ref()));
+
+                        self.collect_with(database, set, object_type, &conditions, visited);
+                    }
+                }
+            }
+        }
+    }
+
+    fn push(&mut self, key: &'a T, field: CollectedField<'a, T>) {
+        let index = *self.keys.entry(key).or_insert_with(|| {
+            self.fields.push((key, vec![]));
+            self.fields.len() - 1
+        });
+
+        self.fields[index].1.push(field);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'a T, &[CollectedField<'a, T>])> {
+        self.fields
+            .iter()
+            .map(|(key, fields)| (*key, fields.as_slice()))
+    }
+}
+
+fn with<'a, T>(inherited: &[Condition<'a, T>], own: Vec<Condition<'a, T>>) -> Vec<Condition<'a, T>>
+where
+    T: Eq,
+{
+    let mut conditions = inherited.to_vec();
+
+    for condition in own {
+        if !conditions.contains(&condition) {
+            conditions.push(condition);
+        }
+    }
+
+    conditions
+}
+
+/// Selection set together with the conditions that it inherits.
+type ConditionalSet<'a, T> = (&'a Arc<ast::SelectionSet<T>>, Vec<Condition<'a, T>>);
+
+/// Normalized selection tree of an operation.
+pub struct SelectionTree<'a, T> {
+    pub operation: &'a Arc<ast::OperationDefinition<T>>,
+    pub root_type: &'a T,
+    pub selections: Selections<'a, T>,
+}
+
+/// Fields that are selected on each of the possible (concrete object) types
+/// of a selection set, in the order in which they appear.
+pub struct Selections<'a, T> {
+    pub by_type: Vec<(&'a T, Vec<SelectedField<'a, T>>)>,
+}
+
+impl<'a, T> Default for Selections<'a, T> {
+    fn default() -> Self {
+        Selections { by_type: vec![] }
+    }
+}
+
+impl<'a, T> Selections<'a, T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    fn build(
+        database: &'a Database<T>,
+        ty: &T,
+        sets: &[ConditionalSet<'a, T>],
+    ) -> Selections<'a, T> {
+        let schema = database.schema();
+
+        let by_type = schema
+            .ty(ty)
+            .into_iter()
+            .flat_map(|ty| ty.possible_types())
+            .map(|object_type| {
+                let object_type = object_type.name();
+                let mut collected = CollectedFields::default();
+
+                for (set, conditions) in sets {
+                    collected.collect_with(
+                        database,
+                        set,
+                        Some(object_type),
+                        conditions,
+                        &mut vec![],
+                    );
+                }
+
+                let fields = collected
+                    .fields
+                    .into_iter()
+                    .flat_map(|(key, fields)| {
+                        SelectedField::build(database, object_type, key, fields)
+                    })
+                    .collect();
+
+                (object_type, fields)
+            })
+            .collect();
+
+        Selections { by_type }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_type.iter().all(|(_, fields)| fields.is_empty())
+    }
+
+    /// Returns the fields that are selected on the given object type, which
+    /// is empty if that type isn't one of the possible types.
+    pub fn fields(&self, ty: &T) -> &[SelectedField<'a, T>] {
+        self.by_type
+            .iter()
+            .find(|(other, _)| *other == ty)
+            .map(|(_, fields)| fields.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// Field of one or more merged selections with the same response key.
+pub struct SelectedField<'a, T> {
+    pub response_key: &'a T,
+    pub name: &'a T,
+
+    /// Fields (of the operation and its fragments) that were merged into this
+    /// one, excluding those that are never included.
+    pub fields: Vec<&'a Arc<ast::Field<T>>>,
+    pub definition: Option<&'a Arc<ast::FieldDefinition<T>>>,
+    pub ty: Option<&'a Arc<ast::Type<T>>>,
+    pub conditions: Conditions<'a, T>,
+    pub selections: Selections<'a, T>,
+}
+
+impl<'a, T> SelectedField<'a, T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    fn build(
+        database: &'a Database<T>,
+        object_type: &T,
+        response_key: &'a T,
+        fields: Vec<CollectedField<'a, T>>,
+    ) -> Option<SelectedField<'a, T>> {
+        let fields = fields
+            .into_iter()
+            .filter(|field| !field.conditions.contains(&Condition::Never))
+            .collect::<Vec<_>>();
+
+        let first = fields.first()?.field;
+        let name = first.name.ok()?.as_ref();
+        let definition = database
+            .field_definitions_by_name(object_type, name)
+            .next()
+            .or_else(|| database.inference.field_definitions_by_field.get(first));
+        let ty = definition.and_then(|definition| definition.ty.ok());
+
+        let mut conditions = Conditions::default();
+
+        for field in fields.iter() {
+            conditions.or(field.conditions.clone());
+        }
+
+        // Nested fields only need to repeat the conditions of the field that
+        // selects them if the merged fields have different conditions.
+        let uniform = fields
+            .windows(2)
+            .all(|pair| pair[0].conditions == pair[1].conditions);
+
+        let sets = fields
+            .iter()
+            .flat_map(|field| {
+                let conditions = match uniform {
+                    true => vec![],
+                    false => field.conditions.clone(),
+                };
+
+                Some((field.field.selection_set.as_ref()?, conditions))
+            })
+            .collect::<Vec<_>>();
+
+        let selections = match ty.and_then(|ty| ty.name()) {
+            Some(named) if !sets.is_empty() => Selections::build(database, named, &sets),
+            _ => Default::default(),
+        };
+
+        Some(SelectedField {
+            response_key,
+            name,
+            fields: fields.into_iter().map(|field| field.field).collect(),
+            definition,
+            ty,
+            conditions,
+            selections,
+        })
+    }
+}
+
+impl<T> Database<T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    /// Returns the normalized selection tree of the given operation, or `None`
+    /// if its root operation type or selection set is missing.
+    pub fn selection_tree<'a>(
+        &'a self,
+        operation: &'a Arc<ast::OperationDefinition<T>>,
+    ) -> Option<SelectionTree<'a, T>> {
+        let root_type = self.root_operation_type(operation.ty.as_ref())?;
+        let set = operation.selection_set.ok()?;
+
+        Some(SelectionTree {
+            operation,
+            root_type,
+            selections: Selections::build(self, root_type, &[(set, vec![])]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use litho_language::ast::Document;
+    use litho_language::Parse;
+
+    use super::{Condition, SelectedField, Selections};
+    use crate::Database;
+
+    const DOCUMENT: &str = r#"
+        type Query {
+            pet: Pet
+            node: Node
+        }
+
+        interface Node {
+            id: ID!
+        }
+
+        type Cat implements Node {
+            id: ID!
+            name: String!
+            lives: Int
+        }
+
+        type Dog implements Node {
+            id: ID!
+            name: String!
+            barks: Boolean
+        }
+
+        union Pet = Cat | Dog
+
+        query Pets($withLives: Boolean!, $skipName: Boolean!) {
+            pet {
+                ... on Cat {
+                    name
+                    lives @include(if: $withLives)
+                }
+
+                ...DogFields
+
+                ... @skip(if: true) {
+                    ... on Cat {
+                        id
+                    }
+                }
+            }
+        }
+
+        fragment DogFields on Dog {
+            name @skip(if: $skipName)
+            barks
+            ...DogFields
+        }
+
+        query Nodes($a: Boolean!, $b: Boolean!) {
+            node @include(if: $a) {
+                id
+            }
+
+            node @include(if: $b) {
+                ... on Cat {
+                    name @include(if: $a)
+                    name
+                }
+            }
+        }
+    "#;
+
+    fn database() -> Database<String> {
+        let (document, _) =
+            Document::<String>::parse_from_str(Default::default(), DOCUMENT).unwrap();
+
+        Database::from_iter([&document])
+    }
+
+    fn keys<'a>(selections: &'a Selections<String>, ty: &str) -> Vec<&'a str> {
+        selections
+            .fields(&ty.to_owned())
+            .iter()
+            .map(|field| field.response_key.as_str())
+            .collect()
+    }
+
+    fn field<'a, 'b>(
+        selections: &'b Selections<'a, String>,
+        ty: &str,
+        key: &str,
+    ) -> &'b SelectedField<'a, String> {
+        selections
+            .fields(&ty.to_owned())
+            .iter()
+            .find(|field| field.response_key == key)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_fragments() {
+        let database = database();
+        let operation = database
+            .operations
+            .by_name(&"Pets".to_owned())
+            .next()
+            .unwrap();
+        let tree = database.selection_tree(operation).unwrap();
+
+        assert_eq!(tree.root_type, "Query");
+        assert_eq!(keys(&tree.selections, "Query"), vec!["pet"]);
+
+        let pet = field(&tree.selections, "Query", "pet");
+        let types = pet.selections.by_type.iter().map(|(ty, _)| ty.as_str());
+        assert_eq!(types.collect::<Vec<_>>(), vec!["Cat", "Dog"]);
+        assert_eq!(keys(&pet.selections, "Cat"), vec!["name", "lives"]);
+        assert_eq!(keys(&pet.selections, "Dog"), vec!["name", "barks"]);
+
+        let name = field(&pet.selections, "Cat", "name");
+        assert!(name.conditions.is_unconditional());
+        assert_eq!(name.definition.unwrap().name.as_ref(), "name");
+        assert_eq!(name.ty.unwrap().name().unwrap(), "String");
+
+        let lives = field(&pet.selections, "Cat", "lives");
+        let with_lives = "withLives".to_owned();
+        assert_eq!(
+            lives.conditions.alternatives(),
+            &[vec![Condition::Include(&with_lives)]]
+        );
+
+        let name = field(&pet.selections, "Dog", "name");
+        let skip_name = "skipName".to_owned();
+        assert_eq!(
+            name.conditions.alternatives(),
+            &[vec![Condition::Skip(&skip_name)]]
+        );
+    }
+
+    #[test]
+    fn test_merging() {
+        let database = database();
+        let operation = database
+            .operations
+            .by_name(&"Nodes".to_owned())
+            .next()
+            .unwrap();
+        let tree = database.selection_tree(operation).unwrap();
+
+        let (a, b) = ("a".to_owned(), "b".to_owned());
+
+        let node = field(&tree.selections, "Query", "node");
+        assert_eq!(node.fields.len(), 2);
+        assert_eq!(
+            node.conditions.alternatives(),
+            &[vec![Condition::Include(&a)], vec![Condition::Include(&b)]]
+        );
+
+        assert_eq!(keys(&node.selections, "Cat"), vec!["id", "name"]);
+        assert_eq!(keys(&node.selections, "Dog"), vec!["id"]);
+
+        let id = field(&node.selections, "Dog", "id");
+        assert_eq!(
+            id.conditions.alternatives(),
+            &[vec![Condition::Include(&a)]]
+        );
+
+        let name = field(&node.selections, "Cat", "name");
+        assert_eq!(name.fields.len(), 2);
+        assert_eq!(
+            name.conditions.alternatives(),
+            &[vec![Condition::Include(&b)]]
+        );
+    }
+}
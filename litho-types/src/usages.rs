@@ -1,6 +1,6 @@
 use std::hash::Hash;
 
-use litho_language::ast::{FragmentDefinition, FragmentSpread};
+use litho_language::ast::{Directive, DirectiveDefinition, FragmentDefinition, FragmentSpread};
 
 use super::References;
 
@@ -9,6 +9,7 @@ pub struct Usages<T>
 where
     T: Eq + Hash,
 {
+    pub directives: References<DirectiveDefinition<T>, Directive<T>>,
     pub fragments: References<FragmentDefinition<T>, FragmentSpread<T>>,
 }
 
@@ -18,6 +19,7 @@ where
 {
     fn default() -> Self {
         Usages {
+            directives: Default::default(),
             fragments: Default::default(),
         }
     }
@@ -1,3 +1,4 @@
+use std::borrow::Borrow;
 use std::hash::Hash;
 use std::sync::Arc;
 
@@ -5,28 +6,87 @@ use litho_diagnostics::Diagnostic;
 use litho_language::ast::*;
 use litho_types::Database;
 
+use crate::suggestions::suggestions;
+
 pub struct ArgumentNames<'a, T>(pub &'a Database<T>)
 where
     T: Eq + Hash;
 
+impl<'a, T> ArgumentNames<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    fn check_arguments(
+        &self,
+        arguments: Option<&Arc<Arguments<T>>>,
+        definitions: Option<&ArgumentsDefinition<T>>,
+        accumulator: &mut Vec<Diagnostic<Span>>,
+    ) {
+        for argument in arguments
+            .into_iter()
+            .flat_map(|arguments| arguments.items.iter())
+        {
+            if self
+                .0
+                .inference
+                .definitions_for_arguments
+                .get(argument)
+                .is_some()
+            {
+                continue;
+            }
+
+            accumulator.push(Diagnostic::undefined_argument(
+                argument.name.as_ref().to_string(),
+                suggestions(
+                    argument.name.as_ref().borrow(),
+                    definitions
+                        .into_iter()
+                        .flat_map(|definitions| definitions.definitions.iter())
+                        .map(|definition| definition.name.as_ref().borrow()),
+                ),
+                argument.name.span(),
+            ))
+        }
+    }
+}
+
 impl<'a, T> Visit<'a, T> for ArgumentNames<'a, T>
 where
-    T: Eq + Hash + ToString,
+    T: Eq + Hash + Borrow<str> + ToString,
 {
     type Accumulator = Vec<Diagnostic<Span>>;
 
-    fn visit_argument(&self, node: &'a Arc<Argument<T>>, accumulator: &mut Self::Accumulator) {
-        if self
-            .0
-            .inference
-            .definitions_for_arguments
-            .get(node)
-            .is_none()
+    fn visit_field(&self, node: &'a Arc<Field<T>>, accumulator: &mut Self::Accumulator) {
+        let definition = self.0.inference.field_definitions_by_field.get(node);
+
+        self.check_arguments(
+            node.arguments.as_ref(),
+            definition.and_then(|definition| definition.arguments_definition.as_deref()),
+            accumulator,
+        );
+    }
+
+    fn visit_directive(&self, node: &'a Arc<Directive<T>>, accumulator: &mut Self::Accumulator) {
+        let definition = self.0.inference.definition_for_directive(node);
+
+        // Relay's `@argumentDefinitions` and `@arguments` take arbitrary
+        // arguments, which are checked by the Relay rules instead.
+        let is_variadic = matches!(
+            node.name.ok().map(|name| name.as_ref().borrow()),
+            Some("argumentDefinitions" | "arguments")
+        );
+
+        if is_variadic
+            && matches!(definition, Some(definition) if definition.arguments_definition.is_none())
         {
-            accumulator.push(Diagnostic::undefined_argument(
-                node.name.as_ref().to_string(),
-                node.name.span(),
-            ))
+            return;
         }
+
+        self.check_arguments(
+            node.arguments.as_ref(),
+            definition.and_then(|definition| definition.arguments_definition.as_deref()),
+            accumulator,
+        );
     }
 }
@@ -1,3 +1,4 @@
+use std::borrow::Borrow;
 use std::hash::Hash;
 use std::sync::Arc;
 
@@ -5,13 +6,15 @@ use litho_diagnostics::Diagnostic;
 use litho_language::ast::*;
 use litho_types::Database;
 
+use crate::suggestions::suggestions;
+
 pub struct DirectivesAreDefined<'a, T>(pub &'a Database<T>)
 where
     T: Eq + Hash;
 
 impl<'a, T> Visit<'a, T> for DirectivesAreDefined<'a, T>
 where
-    T: Eq + Hash + ToString,
+    T: Eq + Hash + Borrow<str> + ToString,
 {
     type Accumulator = Vec<Diagnostic<Span>>;
 
@@ -21,6 +24,13 @@ where
         if self.0.inference.definition_for_directive(node).is_none() {
             accumulator.push(Diagnostic::undefined_directive(
                 name.as_ref().to_string(),
+                suggestions(
+                    name.as_ref().borrow(),
+                    self.0
+                        .directive_definitions()
+                        .flat_map(|definition| definition.name.ok())
+                        .map(|name| name.as_ref().borrow()),
+                ),
                 name.span(),
             ))
         }
@@ -11,6 +11,8 @@ mod directives;
 mod values;
 mod variables;
 
+pub(crate) use values::InputCoercion;
+
 pub fn check<N, T>(document: &N, database: &Database<T>) -> Vec<Diagnostic<Span>>
 where
     N: Node<T>,
@@ -31,7 +33,9 @@ where
     );
     document.traverse(&values::EnumCoercion(database), &mut errors);
     document.traverse(&values::InputCoercion(database), &mut errors);
+    document.traverse(&values::InputFieldUniqueness, &mut errors);
     document.traverse(&values::ObjectCoercion(database), &mut errors);
+    document.traverse(&values::ScalarCoercion::new(database), &mut errors);
     document.traverse(&variables::VariableUniqueness(database), &mut errors);
     document.traverse(&variables::VariablesAreInputTypes(database), &mut errors);
     document.traverse(&variables::VariablesAreDefined(database), &mut errors);
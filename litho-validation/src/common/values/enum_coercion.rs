@@ -6,6 +6,8 @@ use litho_diagnostics::Diagnostic;
 use litho_language::ast::*;
 use litho_types::Database;
 
+use crate::suggestions::suggestions;
+
 pub struct EnumCoercion<'a, T>(pub &'a Database<T>)
 where
     T: Eq + Hash;
@@ -62,6 +64,12 @@ where
             accumulator.push(Diagnostic::unrecognized_enum_value(
                 ty.to_string(),
                 value.to_string(),
+                suggestions(
+                    value.borrow(),
+                    self.0
+                        .enum_value_definitions(ty)
+                        .map(|definition| definition.enum_value.0.as_ref().borrow()),
+                ),
                 node.span(),
             ))
         }
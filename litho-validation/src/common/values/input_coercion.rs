@@ -14,7 +14,10 @@ impl<'a, T> InputCoercion<'a, T>
 where
     T: Eq + Hash + ToString + Borrow<str>,
 {
-    fn check_ty(&self, ty: &Type<T>, node: &Value<T>) -> Option<Diagnostic<Span>> {
+    pub fn check_ty<U>(&self, ty: &Type<U>, node: &Value<T>) -> Option<Diagnostic<Span>>
+    where
+        U: ToString + Borrow<str>,
+    {
         if node.is_variable() {
             return None;
         }
@@ -44,6 +47,15 @@ where
             "Int" if !node.is_int() => {
                 Some(Diagnostic::expected_int_value(ty.to_string(), node.span()))
             }
+            "Int" => match node {
+                Value::IntValue(value) if value.to_i32().is_err() => {
+                    Some(Diagnostic::int_value_out_of_range(
+                        value.as_raw_token().source.borrow().to_owned(),
+                        node.span(),
+                    ))
+                }
+                _ => None,
+            },
             "Float" if !node.is_float_like() => Some(Diagnostic::expected_float_value(
                 ty.to_string(),
                 node.span(),
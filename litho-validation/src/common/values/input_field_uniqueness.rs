@@ -0,0 +1,30 @@
+use std::hash::Hash;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+
+pub struct InputFieldUniqueness;
+
+impl<'a, T> Visit<'a, T> for InputFieldUniqueness
+where
+    T: Eq + Hash + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_object_value(&self, node: &'a ObjectValue<T>, accumulator: &mut Self::Accumulator) {
+        for (index, field) in node.object_fields.iter().enumerate() {
+            let Some(first) = node.object_fields[..index]
+                .iter()
+                .find(|first| first.name.as_ref() == field.name.as_ref())
+            else {
+                continue;
+            };
+
+            accumulator.push(Diagnostic::duplicate_input_field(
+                field.name.as_ref().to_string(),
+                first.name.span(),
+                field.name.span(),
+            ));
+        }
+    }
+}
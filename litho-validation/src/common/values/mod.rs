@@ -1,7 +1,11 @@
 mod enum_coercion;
 mod input_coercion;
+mod input_field_uniqueness;
 mod object_coercion;
+mod scalar_coercion;
 
 pub use enum_coercion::EnumCoercion;
 pub use input_coercion::InputCoercion;
+pub use input_field_uniqueness::InputFieldUniqueness;
 pub use object_coercion::ObjectCoercion;
+pub use scalar_coercion::ScalarCoercion;
@@ -6,6 +6,8 @@ use litho_diagnostics::Diagnostic;
 use litho_language::ast::*;
 use litho_types::Database;
 
+use crate::suggestions::suggestions;
+
 pub struct ObjectCoercion<'a, T>(pub &'a Database<T>)
 where
     T: Eq + Hash;
@@ -81,10 +83,35 @@ where
                         accumulator.push(Diagnostic::unrecognized_input_field(
                             value.name.as_ref().to_string(),
                             ty.to_string(),
+                            suggestions(
+                                value.name.as_ref().borrow(),
+                                self.0
+                                    .input_value_definitions(name)
+                                    .map(|definition| definition.name.as_ref().borrow()),
+                            ),
                             value.name.span(),
                         ))
                     }
                 }
+
+                if self.0.is_one_of_input_object(name) {
+                    match value.object_fields.as_slice() {
+                        [field] => {
+                            if let Some(null) = field.value.ok().filter(|value| value.is_null()) {
+                                accumulator.push(Diagnostic::null_one_of_input_field(
+                                    ty.to_string(),
+                                    field.name.as_ref().to_string(),
+                                    null.span(),
+                                ))
+                            }
+                        }
+                        fields => accumulator.push(Diagnostic::invalid_one_of_input_field_count(
+                            ty.to_string(),
+                            fields.len().to_string(),
+                            node.span(),
+                        )),
+                    }
+                }
             }
         }
     }
@@ -0,0 +1,276 @@
+use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, OnceLock};
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+use regex::Regex;
+
+/// Literal format of a custom scalar, which is either taken from a builtin
+/// registry keyed on `@specifiedBy(url:)` or declared with
+/// `@litho_format(pattern:)`.
+pub enum ScalarFormat {
+    DateTime,
+    FullDate,
+    FullTime,
+    Uuid,
+    Url,
+    Email,
+    Json,
+    Pattern(String, Regex),
+}
+
+impl ScalarFormat {
+    /// Returns the format of a scalar with the given name and
+    /// `@specifiedBy(url:)`. RFC 3339 defines several formats, so the name of
+    /// the scalar decides between them (and the format is unknown if it
+    /// doesn't).
+    pub fn specified_by(url: &str, name: &str) -> Option<ScalarFormat> {
+        let url = url.trim().to_ascii_lowercase();
+        let url = url
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .trim_start_matches("www.")
+            .trim_end_matches('/');
+
+        let rfc = |number: &str| {
+            url.split(|c: char| !c.is_ascii_alphanumeric())
+                .any(|part| part == format!("rfc{number}"))
+        };
+
+        if url == "scalars.graphql.org/andimarek/date-time" {
+            Some(ScalarFormat::DateTime)
+        } else if rfc("3339") {
+            let name = name.to_ascii_lowercase();
+
+            if name.contains("datetime") || name.contains("timestamp") {
+                Some(ScalarFormat::DateTime)
+            } else if name.contains("date") {
+                Some(ScalarFormat::FullDate)
+            } else if name.contains("time") {
+                Some(ScalarFormat::FullTime)
+            } else {
+                None
+            }
+        } else if rfc("4122") || rfc("9562") {
+            Some(ScalarFormat::Uuid)
+        } else if rfc("3986") || url.starts_with("url.spec.whatwg.org") {
+            Some(ScalarFormat::Url)
+        } else if rfc("5322")
+            || url == "html.spec.whatwg.org/multipage/input.html#valid-e-mail-address"
+        {
+            Some(ScalarFormat::Email)
+        } else if rfc("7159") || rfc("8259") || url == "json.org" {
+            Some(ScalarFormat::Json)
+        } else {
+            None
+        }
+    }
+
+    pub fn pattern(pattern: &str) -> Result<ScalarFormat, regex::Error> {
+        let regex = Regex::new(&format!("^(?:{pattern})$"))?;
+        Ok(ScalarFormat::Pattern(pattern.to_owned(), regex))
+    }
+
+    pub fn of<T>(database: &Database<T>, ty: &T) -> Option<ScalarFormat>
+    where
+        T: Eq + Hash + Borrow<str>,
+    {
+        database.type_directives(ty).find_map(|directive| {
+            let name = directive.name.ok()?.as_ref().borrow();
+            let value = match name {
+                "litho_format" => directive.argument("pattern"),
+                "specifiedBy" => directive.argument("url"),
+                _ => None,
+            }?;
+
+            let value = match value.value.ok()?.as_ref() {
+                Value::StringValue(value) => value.to_string(),
+                _ => return None,
+            };
+
+            match name {
+                "litho_format" => ScalarFormat::pattern(&value).ok(),
+                _ => ScalarFormat::specified_by(&value, ty.borrow()),
+            }
+        })
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            ScalarFormat::DateTime => "an RFC 3339 date-time".to_owned(),
+            ScalarFormat::FullDate => "an RFC 3339 full-date".to_owned(),
+            ScalarFormat::FullTime => "an RFC 3339 full-time".to_owned(),
+            ScalarFormat::Uuid => "a UUID".to_owned(),
+            ScalarFormat::Url => "a URL".to_owned(),
+            ScalarFormat::Email => "an email address".to_owned(),
+            ScalarFormat::Json => "a JSON document".to_owned(),
+            ScalarFormat::Pattern(pattern, _) => format!("a value matching `{pattern}`"),
+        }
+    }
+
+    /// Returns `false` if the given string or int literal does not match this
+    /// format. Other literals are not checked.
+    pub fn accepts<T>(&self, value: &Value<T>) -> bool
+    where
+        T: Borrow<str>,
+    {
+        let regex = match self {
+            ScalarFormat::DateTime => date_time(),
+            ScalarFormat::FullDate => full_date(),
+            ScalarFormat::FullTime => full_time(),
+            ScalarFormat::Uuid => uuid(),
+            ScalarFormat::Url => url(),
+            ScalarFormat::Email => email(),
+            ScalarFormat::Json => {
+                return match value {
+                    Value::StringValue(value) => {
+                        serde_json::from_str::<serde_json::Value>(&value.to_string()).is_ok()
+                    }
+                    _ => true,
+                }
+            }
+            ScalarFormat::Pattern(_, regex) => regex,
+        };
+
+        match value {
+            Value::StringValue(value) => regex.is_match(&value.to_string()),
+            Value::IntValue(value) => match self {
+                ScalarFormat::Pattern(_, regex) => {
+                    regex.is_match(value.as_raw_token().source.borrow())
+                }
+                _ => false,
+            },
+            _ => true,
+        }
+    }
+}
+
+const FULL_DATE: &str = r"\d{4}-(0[1-9]|1[0-2])-(0[1-9]|[12]\d|3[01])";
+const FULL_TIME: &str = concat!(
+    r"([01]\d|2[0-3]):[0-5]\d:([0-5]\d|60)(\.\d+)?",
+    r"([Zz]|[+-]([01]\d|2[0-3]):[0-5]\d)",
+);
+
+fn date_time() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(&format!("^{FULL_DATE}[Tt]{FULL_TIME}$")).unwrap())
+}
+
+fn full_date() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(&format!("^{FULL_DATE}$")).unwrap())
+}
+
+fn full_time() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(&format!("^{FULL_TIME}$")).unwrap())
+}
+
+fn uuid() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"^[0-9a-fA-F]{8}(-[0-9a-fA-F]{4}){3}-[0-9a-fA-F]{12}$").unwrap()
+    })
+}
+
+fn url() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"^[A-Za-z][A-Za-z0-9+.\-]*:\S+$").unwrap())
+}
+
+fn email() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"^[^\s@]+@[^\s@]+\.[^\s@]+$").unwrap())
+}
+
+/// Checks string and int literals of custom scalars against their format.
+/// Formats are looked up (and patterns compiled) once per scalar.
+pub struct ScalarCoercion<'a, T>(
+    pub &'a Database<T>,
+    RefCell<HashMap<&'a T, Option<ScalarFormat>>>,
+)
+where
+    T: Eq + Hash;
+
+impl<'a, T> ScalarCoercion<'a, T>
+where
+    T: Eq + Hash,
+{
+    pub fn new(database: &'a Database<T>) -> ScalarCoercion<'a, T> {
+        ScalarCoercion(database, Default::default())
+    }
+}
+
+impl<'a, T> Visit<'a, T> for ScalarCoercion<'a, T>
+where
+    T: Eq + Hash + ToString + Borrow<str>,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_directive(&self, node: &'a Arc<Directive<T>>, accumulator: &mut Self::Accumulator) {
+        match node.name.ok() {
+            Some(name) if name.as_ref().borrow() == "litho_format" => {}
+            _ => return,
+        }
+
+        let Some(value) = node
+            .argument("pattern")
+            .and_then(|argument| argument.value.ok())
+        else {
+            return;
+        };
+
+        if let Value::StringValue(pattern) = value.as_ref() {
+            if ScalarFormat::pattern(&pattern.to_string()).is_err() {
+                accumulator.push(Diagnostic::invalid_scalar_format_pattern(
+                    pattern.to_string(),
+                    value.span(),
+                ));
+            }
+        }
+    }
+
+    fn visit_value(&self, node: &'a Arc<Value<T>>, accumulator: &mut Self::Accumulator) {
+        if !node.is_string() && !node.is_int() {
+            return;
+        }
+
+        let Some(ty) = self.0.inference.types_for_values.get(node) else {
+            return;
+        };
+
+        if ty.list_value_type().is_some() {
+            return;
+        }
+
+        let Some(name) = ty.name() else { return };
+
+        if !self
+            .0
+            .type_definitions_by_name(name)
+            .any(|definition| definition.is_scalar())
+        {
+            return;
+        }
+
+        let mut formats = self.1.borrow_mut();
+        let Some(format) = formats
+            .entry(name)
+            .or_insert_with(|| ScalarFormat::of(self.0, name))
+        else {
+            return;
+        };
+
+        if !format.accepts(node) {
+            accumulator.push(Diagnostic::invalid_scalar_literal(
+                name.to_string(),
+                format.description(),
+                node.span(),
+            ))
+        }
+    }
+}
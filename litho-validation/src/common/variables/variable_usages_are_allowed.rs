@@ -1,3 +1,4 @@
+use std::borrow::Borrow;
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 use std::iter::once;
@@ -7,13 +8,15 @@ use litho_diagnostics::Diagnostic;
 use litho_language::ast::*;
 use litho_types::Database;
 
+use crate::executable::relay::{fragment_argument_field, fragment_argument_type};
+
 pub struct VariableUsagesAreAllowed<'a, T>(pub &'a Database<T>)
 where
     T: Eq + Hash;
 
 impl<'a, T> Visit<'a, T> for VariableUsagesAreAllowed<'a, T>
 where
-    T: Eq + Hash + ToString,
+    T: Eq + Hash + Borrow<str> + ToString,
 {
     type Accumulator = Vec<Diagnostic<Span>>;
 
@@ -40,42 +43,63 @@ where
             accumulator,
         )
     }
+
+    fn visit_fragment_definition(
+        &self,
+        node: &'a Arc<FragmentDefinition<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let arguments = node
+            .argument_definitions()
+            .filter_map(|argument| {
+                let ty = fragment_argument_type(argument)?;
+                let span = fragment_argument_field(argument, "type")?.span();
+                let has_default_value = fragment_argument_field(argument, "defaultValue").is_some();
+
+                Some((argument.name.as_ref(), (ty, span, has_default_value)))
+            })
+            .collect::<HashMap<_, _>>();
+
+        if let (false, Some(selection_set)) = (arguments.is_empty(), node.selection_set.ok()) {
+            selection_set.traverse(
+                &FragmentArgumentUsagesAreAllowed {
+                    database: self.0,
+                    arguments: &arguments,
+                },
+                accumulator,
+            )
+        }
+    }
 }
 
-fn is_variable_usage_allowed<T>(
+fn is_variable_usage_allowed<T, U>(
     database: &Database<T>,
-    definition: &VariableDefinition<T>,
+    variable_type: &Type<U>,
+    has_default_value: bool,
     value: &Arc<Value<T>>,
 ) -> bool
 where
-    T: Eq + Hash,
+    T: Eq + Hash + Borrow<str>,
+    U: Borrow<str>,
 {
-    let definition_default_value = definition
-        .default_value
-        .as_ref()
-        .and_then(|value| value.value.ok());
-
     let location_default_value = database.inference.default_value_for_values.get(value);
 
-    let Some(actual) = definition.ty.ok() else {
-        return true;
-    };
-
     let Some(expected) = database.inference.types_for_values.get(value) else {
         return true;
     };
 
-    let expected_nullable = match (definition_default_value, location_default_value) {
-        (None, None) => expected,
+    let expected_nullable = match (has_default_value, location_default_value) {
+        (false, None) => expected,
         (_, _) => expected.as_nullable(),
     };
 
-    are_types_compatible(actual, expected_nullable)
+    are_types_compatible(variable_type, expected_nullable)
 }
 
-fn are_types_compatible<T>(variable_type: &Type<T>, location_type: &Type<T>) -> bool
+fn are_types_compatible<T, U>(variable_type: &Type<U>, location_type: &Type<T>) -> bool
 where
-    T: Eq,
+    T: Borrow<str>,
+    U: Borrow<str>,
 {
     match (location_type, variable_type) {
         (Type::NonNull(location_type), Type::NonNull(variable_type)) => {
@@ -94,7 +118,138 @@ where
             }
         }
         (_, Type::List(_)) => false,
-        (lhs, rhs) => lhs.is_invariant(rhs),
+        (lhs, rhs) => is_invariant(lhs, rhs),
+    }
+}
+
+fn is_invariant<T, U>(lhs: &Type<T>, rhs: &Type<U>) -> bool
+where
+    T: Borrow<str>,
+    U: Borrow<str>,
+{
+    match (lhs, rhs) {
+        (Type::Named(lhs), Type::Named(rhs)) => lhs.0.as_ref().borrow() == rhs.0.as_ref().borrow(),
+        (Type::List(lhs), Type::List(rhs)) => lhs
+            .ty
+            .ok()
+            .zip(rhs.ty.ok())
+            .map(|(lhs, rhs)| is_invariant(lhs, rhs))
+            .unwrap_or_default(),
+        (Type::NonNull(lhs), Type::NonNull(rhs)) => is_invariant(&lhs.ty, &rhs.ty),
+        (_, _) => false,
+    }
+}
+
+fn check_one_of_variables<T>(
+    database: &Database<T>,
+    variable_definitions: &HashMap<&T, &VariableDefinition<T>>,
+    value: &Arc<Value<T>>,
+    object: &ObjectValue<T>,
+) -> Vec<Diagnostic<Span>>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    let Some(name) = database
+        .inference
+        .types_for_values
+        .get(value)
+        .and_then(|ty| ty.name())
+    else {
+        return vec![];
+    };
+
+    if !database.is_one_of_input_object(name) {
+        return vec![];
+    }
+
+    let mut errors = vec![];
+
+    for field in object.object_fields.iter() {
+        let Some(Value::Variable(variable)) = field.value.ok().map(AsRef::as_ref) else {
+            continue;
+        };
+
+        let Some(definition) = variable_definitions.get(variable.name.as_ref()) else {
+            continue;
+        };
+
+        match definition.ty.ok() {
+            Some(ty) if ty.is_nullable() => errors.push(Diagnostic::nullable_one_of_variable(
+                variable.name.as_ref().to_string(),
+                ty.to_string(),
+                name.to_string(),
+                definition.ty.span(),
+                variable.span(),
+            )),
+            _ => {}
+        }
+    }
+
+    errors
+}
+
+/// Reports each variable that is passed to a fragment with Relay's
+/// `@arguments` but isn't allowed by the type that the fragment defines the
+/// argument with in `@argumentDefinitions`.
+fn check_fragment_arguments<T, F>(
+    definition: &FragmentDefinition<T>,
+    variable_definitions: &HashMap<&T, &VariableDefinition<T>>,
+    node: &FragmentSpread<T>,
+    mut report: F,
+) where
+    T: Eq + Hash + Borrow<str> + ToString,
+    F: FnMut(&VariableDefinition<T>, &Type<T>, &Type<String>, Span),
+{
+    let arguments = node
+        .directives
+        .as_ref()
+        .and_then(|directives| directives.directive("arguments"))
+        .and_then(|directive| directive.arguments.as_ref())
+        .into_iter()
+        .flat_map(|arguments| arguments.items.iter());
+
+    for argument in arguments {
+        let Some(value) = argument.value.ok() else {
+            continue;
+        };
+
+        let Value::Variable(variable) = value.as_ref() else {
+            continue;
+        };
+
+        let Some(variable_definition) = variable_definitions.get(variable.name.as_ref()) else {
+            continue;
+        };
+
+        let Some(actual) = variable_definition.ty.ok() else {
+            continue;
+        };
+
+        let Some(argument_definition) =
+            definition.argument_definition(argument.name.as_ref().borrow())
+        else {
+            continue;
+        };
+
+        let Some(expected) = fragment_argument_type(argument_definition) else {
+            continue;
+        };
+
+        let has_default_value = variable_definition
+            .default_value
+            .as_ref()
+            .and_then(|value| value.value.ok())
+            .is_some()
+            || fragment_argument_field(argument_definition, "defaultValue").is_some();
+
+        let location_type = match has_default_value {
+            false => &expected,
+            true => expected.as_nullable(),
+        };
+
+        if !are_types_compatible(actual.as_ref(), location_type.as_ref()) {
+            report(variable_definition, actual, &expected, value.span());
+        }
     }
 }
 
@@ -108,13 +263,22 @@ where
 
 impl<'a, T> Visit<'a, T> for VariableUsagesAreAllowedInOperation<'a, T>
 where
-    T: Eq + Hash + ToString,
+    T: Eq + Hash + Borrow<str> + ToString,
 {
     type Accumulator = Vec<Diagnostic<Span>>;
 
     fn visit_value(&self, node: &'a Arc<Value<T>>, accumulator: &mut Self::Accumulator) {
         let variable = match node.as_ref() {
             Value::Variable(variable) => variable,
+            Value::ObjectValue(object) => {
+                accumulator.extend(check_one_of_variables(
+                    self.database,
+                    self.variable_definitions,
+                    node,
+                    object,
+                ));
+                return;
+            }
             _ => return,
         };
 
@@ -130,7 +294,13 @@ where
             return;
         };
 
-        if !is_variable_usage_allowed(self.database, definition, node) {
+        let has_default_value = definition
+            .default_value
+            .as_ref()
+            .and_then(|value| value.value.ok())
+            .is_some();
+
+        if !is_variable_usage_allowed(self.database, actual, has_default_value, node) {
             accumulator.push(Diagnostic::incompatible_variable(
                 variable.name.as_ref().to_string(),
                 actual.to_string(),
@@ -155,9 +325,25 @@ where
             return;
         };
 
+        check_fragment_arguments(
+            definition,
+            self.variable_definitions,
+            node,
+            |definition, actual, expected, span| {
+                accumulator.push(Diagnostic::incompatible_variable(
+                    definition.variable.name.as_ref().to_string(),
+                    actual.to_string(),
+                    expected.to_string(),
+                    definition.ty.span(),
+                    span,
+                ))
+            },
+        );
+
         definition.traverse(
             &VariableUsagesAreAllowedInFragment {
                 database: self.database,
+                definition,
                 variable_definitions: self.variable_definitions,
                 fragment_name: node.fragment_name.as_ref(),
                 fragment_span: node.fragment_name.span(),
@@ -173,6 +359,7 @@ where
     T: Eq + Hash,
 {
     database: &'a Database<T>,
+    definition: &'a FragmentDefinition<T>,
     variable_definitions: &'a HashMap<&'a T, &'a VariableDefinition<T>>,
     fragment_name: &'a T,
     fragment_span: Span,
@@ -181,16 +368,33 @@ where
 
 impl<'a, T> Visit<'a, T> for VariableUsagesAreAllowedInFragment<'a, T>
 where
-    T: Eq + Hash + ToString,
+    T: Eq + Hash + Borrow<str> + ToString,
 {
     type Accumulator = Vec<Diagnostic<Span>>;
 
     fn visit_value(&self, node: &'a Arc<Value<T>>, accumulator: &mut Self::Accumulator) {
         let variable = match node.as_ref() {
             Value::Variable(variable) => variable,
+            Value::ObjectValue(object) => {
+                accumulator.extend(check_one_of_variables(
+                    self.database,
+                    self.variable_definitions,
+                    node,
+                    object,
+                ));
+                return;
+            }
             _ => return,
         };
 
+        if self
+            .definition
+            .argument_definition(variable.name.as_ref().borrow())
+            .is_some()
+        {
+            return;
+        }
+
         let Some(definition) = self.variable_definitions.get(variable.name.as_ref()) else {
             return;
         };
@@ -203,7 +407,13 @@ where
             return;
         };
 
-        if !is_variable_usage_allowed(self.database, definition, node) {
+        let has_default_value = definition
+            .default_value
+            .as_ref()
+            .and_then(|value| value.value.ok())
+            .is_some();
+
+        if !is_variable_usage_allowed(self.database, actual, has_default_value, node) {
             accumulator.push(Diagnostic::incompatible_variable_in_fragment(
                 self.fragment_name.to_string(),
                 variable.name.as_ref().to_string(),
@@ -234,8 +444,32 @@ where
             return;
         };
 
+        check_fragment_arguments(
+            definition,
+            self.variable_definitions,
+            node,
+            |definition, actual, expected, span| {
+                let name = definition.variable.name.as_ref();
+
+                if self.definition.argument_definition(name.borrow()).is_some() {
+                    return;
+                }
+
+                accumulator.push(Diagnostic::incompatible_variable_in_fragment(
+                    self.fragment_name.to_string(),
+                    name.to_string(),
+                    actual.to_string(),
+                    expected.to_string(),
+                    self.fragment_span,
+                    definition.ty.span(),
+                    span,
+                ))
+            },
+        );
+
         definition.traverse(
             &VariableUsagesAreAllowedInFragment {
+                definition,
                 stack: self
                     .stack
                     .iter()
@@ -248,3 +482,45 @@ where
         )
     }
 }
+
+/// Checks the usages of the local arguments that a fragment defines with
+/// Relay's `@argumentDefinitions` against the types that they are defined with.
+pub struct FragmentArgumentUsagesAreAllowed<'a, T>
+where
+    T: Eq + Hash,
+{
+    database: &'a Database<T>,
+    arguments: &'a HashMap<&'a T, (Arc<Type<String>>, Span, bool)>,
+}
+
+impl<'a, T> Visit<'a, T> for FragmentArgumentUsagesAreAllowed<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_value(&self, node: &'a Arc<Value<T>>, accumulator: &mut Self::Accumulator) {
+        let Value::Variable(variable) = node.as_ref() else {
+            return;
+        };
+
+        let Some((actual, span, has_default_value)) = self.arguments.get(variable.name.as_ref())
+        else {
+            return;
+        };
+
+        let Some(expected) = self.database.inference.types_for_values.get(node) else {
+            return;
+        };
+
+        if !is_variable_usage_allowed(self.database, actual, *has_default_value, node) {
+            accumulator.push(Diagnostic::incompatible_variable(
+                variable.name.as_ref().to_string(),
+                actual.to_string(),
+                expected.to_string(),
+                *span,
+                node.span(),
+            ))
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::borrow::Borrow;
 use std::collections::HashSet;
 use std::hash::Hash;
 use std::iter::once;
@@ -13,7 +14,7 @@ where
 
 impl<'a, T> Visit<'a, T> for VariablesAreDefined<'a, T>
 where
-    T: Eq + Hash + ToString,
+    T: Eq + Hash + Borrow<str> + ToString,
 {
     type Accumulator = Vec<Diagnostic<Span>>;
 
@@ -50,7 +51,7 @@ where
 
 impl<'a, T> Visit<'a, T> for VariablesAreDefinedInOperation<'a, T>
 where
-    T: Eq + Hash + ToString,
+    T: Eq + Hash + Borrow<str> + ToString,
 {
     type Accumulator = Vec<Diagnostic<Span>>;
 
@@ -82,6 +83,7 @@ where
         definition.traverse(
             &VariablesAreDefinedInFragment {
                 database: self.database,
+                definition,
                 variable_names: &self.variable_names,
                 fragment_name: node.fragment_name.as_ref(),
                 fragment_span: node.fragment_name.span(),
@@ -97,6 +99,7 @@ where
     T: Eq + Hash,
 {
     database: &'a Database<T>,
+    definition: &'a FragmentDefinition<T>,
     variable_names: &'a HashSet<&'a T>,
     fragment_name: &'a T,
     fragment_span: Span,
@@ -105,12 +108,17 @@ where
 
 impl<'a, T> Visit<'a, T> for VariablesAreDefinedInFragment<'a, T>
 where
-    T: Eq + Hash + ToString,
+    T: Eq + Hash + Borrow<str> + ToString,
 {
     type Accumulator = Vec<Diagnostic<Span>>;
 
     fn visit_variable(&self, node: &'a Variable<T>, accumulator: &mut Self::Accumulator) {
-        if self.variable_names.contains(node.name.as_ref()) {
+        if self.variable_names.contains(node.name.as_ref())
+            || self
+                .definition
+                .argument_definition(node.name.as_ref().borrow())
+                .is_some()
+        {
             return;
         }
 
@@ -142,6 +150,7 @@ where
 
         definition.traverse(
             &VariablesAreDefinedInFragment {
+                definition,
                 stack: self
                     .stack
                     .iter()
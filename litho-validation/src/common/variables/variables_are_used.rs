@@ -1,3 +1,4 @@
+use std::borrow::Borrow;
 use std::collections::HashSet;
 use std::hash::Hash;
 use std::iter::once;
@@ -13,7 +14,7 @@ where
 
 impl<'a, T> Visit<'a, T> for VariablesAreUsed<'a, T>
 where
-    T: Eq + Hash + ToString,
+    T: Eq + Hash + Borrow<str> + ToString,
 {
     type Accumulator = Vec<Diagnostic<Span>>;
 
@@ -25,7 +26,7 @@ where
         let mut used = HashSet::new();
 
         node.selection_set
-            .traverse(&VariableUsage(self.0, HashSet::new()), &mut used);
+            .traverse(&VariableUsage(self.0, None, HashSet::new()), &mut used);
 
         for def in node
             .variable_definitions
@@ -44,17 +45,30 @@ where
     }
 }
 
-pub struct VariableUsage<'a, T>(&'a Database<T>, HashSet<&'a T>)
+pub struct VariableUsage<'a, T>(
+    &'a Database<T>,
+    Option<&'a FragmentDefinition<T>>,
+    HashSet<&'a T>,
+)
 where
     T: Eq + Hash;
 
 impl<'a, T> Visit<'a, T> for VariableUsage<'a, T>
 where
-    T: Eq + Hash + ToString,
+    T: Eq + Hash + Borrow<str> + ToString,
 {
     type Accumulator = HashSet<&'a T>;
 
     fn visit_variable(&self, node: &'a Variable<T>, accumulator: &mut Self::Accumulator) {
+        if let Some(definition) = self.1 {
+            if definition
+                .argument_definition(node.name.as_ref().borrow())
+                .is_some()
+            {
+                return;
+            }
+        }
+
         accumulator.insert(node.name.as_ref());
     }
 
@@ -63,7 +77,7 @@ where
         node: &'a Arc<FragmentSpread<T>>,
         accumulator: &mut Self::Accumulator,
     ) {
-        if self.1.contains(node.fragment_name.as_ref()) {
+        if self.2.contains(node.fragment_name.as_ref()) {
             return;
         }
 
@@ -74,7 +88,8 @@ where
         definition.traverse(
             &VariableUsage(
                 self.0,
-                self.1
+                Some(definition),
+                self.2
                     .iter()
                     .copied()
                     .chain(once(node.fragment_name.as_ref()))
@@ -0,0 +1,413 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_language::ast::*;
+use litho_language::SchemaCoordinate;
+use litho_types::coordinate::Element;
+use litho_types::Database;
+
+/// Static cost metrics of an operation. Fragment spreads are expanded, so the
+/// fields of a fragment are counted every time the fragment is spread.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Complexity {
+    /// Maximum depth of nested fields.
+    pub depth: usize,
+
+    /// Total number of selected fields.
+    pub fields: usize,
+
+    /// Number of aliased fields.
+    pub aliases: usize,
+
+    /// Estimated cost based on `@cost` weights and `@listSize` estimates.
+    pub cost: usize,
+}
+
+impl Complexity {
+    pub fn of<T>(operation: &OperationDefinition<T>, database: &Database<T>) -> Complexity
+    where
+        T: Eq + Hash + Borrow<str>,
+    {
+        Complexity::analyze(operation, database, Costs::default())
+    }
+
+    /// Returns the complexity of an operation where the given weights take
+    /// precedence over `@cost` on the types, fields and arguments that their
+    /// coordinates refer to.
+    pub fn with_costs<T>(
+        operation: &OperationDefinition<T>,
+        database: &Database<T>,
+        costs: &[(SchemaCoordinate<String>, usize)],
+    ) -> Complexity
+    where
+        T: Eq + Hash + Borrow<str> + for<'a> From<&'a str>,
+    {
+        Complexity::analyze(operation, database, Costs::resolve(database, costs))
+    }
+
+    fn analyze<T>(
+        operation: &OperationDefinition<T>,
+        database: &Database<T>,
+        costs: Costs<T>,
+    ) -> Complexity
+    where
+        T: Eq + Hash + Borrow<str>,
+    {
+        let Some(selection_set) = operation.selection_set.ok() else {
+            return Complexity::default();
+        };
+
+        Analyzer {
+            database,
+            operation,
+            costs,
+        }
+        .selection_set(selection_set, None, &mut vec![])
+    }
+
+    fn merge(self, other: Complexity) -> Complexity {
+        Complexity {
+            depth: self.depth.max(other.depth),
+            fields: self.fields.saturating_add(other.fields),
+            aliases: self.aliases.saturating_add(other.aliases),
+            cost: self.cost.saturating_add(other.cost),
+        }
+    }
+}
+
+/// Fields of a selection set whose cost is multiplied by the size of a list,
+/// as configured with `@listSize(sizedFields:)` on the parent field.
+struct SizedFields<'a> {
+    names: &'a [String],
+    size: usize,
+}
+
+/// Weights that override `@cost`, resolved to the definitions that they apply
+/// to.
+struct Costs<'a, T> {
+    types: Vec<(&'a T, usize)>,
+    fields: Vec<(&'a Arc<FieldDefinition<T>>, usize)>,
+    arguments: Vec<(&'a Arc<InputValueDefinition<T>>, usize)>,
+}
+
+impl<'a, T> Default for Costs<'a, T> {
+    fn default() -> Self {
+        Costs {
+            types: vec![],
+            fields: vec![],
+            arguments: vec![],
+        }
+    }
+}
+
+impl<'a, T> Costs<'a, T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    fn resolve(database: &'a Database<T>, costs: &[(SchemaCoordinate<String>, usize)]) -> Self
+    where
+        T: for<'b> From<&'b str>,
+    {
+        let mut resolved = Costs::default();
+
+        for (coordinate, weight) in costs {
+            match database.resolve_coordinate(&coordinate.map(|name| name.as_str().into())) {
+                Some(Element::Type(ty)) => resolved.types.push((ty.name(), *weight)),
+                Some(Element::Field(field)) => resolved.fields.push((field.definition(), *weight)),
+                Some(Element::Argument(_, argument)) => {
+                    resolved.arguments.push((argument.definition(), *weight))
+                }
+                _ => {}
+            }
+        }
+
+        resolved
+    }
+
+    fn ty(&self, name: &T) -> Option<usize> {
+        self.types
+            .iter()
+            .find(|(ty, _)| *ty == name)
+            .map(|(_, weight)| *weight)
+    }
+
+    fn field(&self, definition: &Arc<FieldDefinition<T>>) -> Option<usize> {
+        self.fields
+            .iter()
+            .find(|(field, _)| Arc::ptr_eq(field, definition))
+            .map(|(_, weight)| *weight)
+    }
+
+    fn argument(&self, definition: &Arc<InputValueDefinition<T>>) -> Option<usize> {
+        self.arguments
+            .iter()
+            .find(|(argument, _)| Arc::ptr_eq(argument, definition))
+            .map(|(_, weight)| *weight)
+    }
+}
+
+struct Analyzer<'a, T>
+where
+    T: Eq + Hash,
+{
+    database: &'a Database<T>,
+    operation: &'a OperationDefinition<T>,
+    costs: Costs<'a, T>,
+}
+
+impl<'a, T> Analyzer<'a, T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    fn selection_set(
+        &self,
+        node: &'a SelectionSet<T>,
+        sized_fields: Option<&SizedFields>,
+        stack: &mut Vec<&'a T>,
+    ) -> Complexity {
+        node.selections
+            .iter()
+            .map(|selection| match selection {
+                Selection::Field(field) => {
+                    let mut complexity = self.field(field, stack);
+
+                    let size = sized_fields
+                        .filter(|sized| {
+                            field
+                                .name
+                                .ok()
+                                .map(|name| {
+                                    sized
+                                        .names
+                                        .iter()
+                                        .any(|sized| sized == name.as_ref().borrow())
+                                })
+                                .unwrap_or_default()
+                        })
+                        .map(|sized| sized.size);
+
+                    if let Some(size) = size {
+                        complexity.cost = complexity.cost.saturating_mul(size);
+                    }
+
+                    complexity
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.fragment_name.as_ref();
+
+                    if stack.contains(&name) {
+                        return Complexity::default();
+                    }
+
+                    let Some(selection_set) = self
+                        .database
+                        .fragments
+                        .by_name(name)
+                        .next()
+                        .and_then(|definition| definition.selection_set.ok())
+                    else {
+                        return Complexity::default();
+                    };
+
+                    stack.push(name);
+                    let complexity = self.selection_set(selection_set, sized_fields, stack);
+                    stack.pop();
+                    complexity
+                }
+                Selection::InlineFragment(fragment) => match fragment.selection_set.ok() {
+                    Some(selection_set) => self.selection_set(selection_set, sized_fields, stack),
+                    None => Complexity::default(),
+                },
+            })
+            .fold(Complexity::default(), Complexity::merge)
+    }
+
+    fn field(&self, node: &'a Arc<Field<T>>, stack: &mut Vec<&'a T>) -> Complexity {
+        let definition = self.database.inference.field_definitions_by_field.get(node);
+
+        let list_size =
+            definition.and_then(|definition| definition.directives.as_ref()?.directive("listSize"));
+
+        let sized_field_names = list_size
+            .and_then(|directive| directive.argument("sizedFields"))
+            .map(|argument| strings(argument))
+            .unwrap_or_default();
+
+        let size = self.list_size(node, list_size);
+
+        let sized_fields = SizedFields {
+            names: &sized_field_names,
+            size,
+        };
+
+        let children = match node.selection_set.as_ref() {
+            Some(selection_set) => self.selection_set(
+                selection_set,
+                Some(&sized_fields).filter(|sized| !sized.names.is_empty()),
+                stack,
+            ),
+            None => Complexity::default(),
+        };
+
+        let is_list = definition
+            .and_then(|definition| definition.ty.ok())
+            .map(|ty| ty.list_value_type().is_some())
+            .unwrap_or_default();
+
+        let multiplier = match is_list && sized_field_names.is_empty() {
+            true => size,
+            false => 1,
+        };
+
+        Complexity {
+            depth: children.depth + 1,
+            fields: children.fields + 1,
+            aliases: children.aliases + node.alias.is_some() as usize,
+            cost: self
+                .weight(node, definition)
+                .saturating_add(children.cost)
+                .saturating_mul(multiplier),
+        }
+    }
+
+    /// Returns the weight of a field (including the weights of its arguments),
+    /// which defaults to `1` for composite types and `0` otherwise.
+    fn weight(&self, node: &Field<T>, definition: Option<&Arc<FieldDefinition<T>>>) -> usize {
+        let Some(definition) = definition else {
+            return 0;
+        };
+
+        let ty = definition.ty.ok().and_then(|ty| ty.name());
+
+        let weight = self
+            .costs
+            .field(definition)
+            .or_else(|| cost(definition.directives.as_ref()))
+            .or_else(|| {
+                ty.and_then(|ty| {
+                    self.costs.ty(ty).or_else(|| {
+                        self.database
+                            .type_directives(ty)
+                            .find_map(|directive| weight(directive))
+                    })
+                })
+            })
+            .unwrap_or_else(|| match ty {
+                Some(ty) if self.database.is_composite_type(ty) => 1,
+                _ => 0,
+            });
+
+        let arguments = node
+            .arguments
+            .iter()
+            .flat_map(|arguments| arguments.items.iter())
+            .filter_map(|argument| {
+                definition
+                    .arguments_definition
+                    .as_ref()?
+                    .argument(argument.name.as_ref())
+            })
+            .filter_map(|argument| {
+                self.costs
+                    .argument(argument)
+                    .or_else(|| cost(argument.directives.as_ref()))
+            })
+            .fold(0usize, usize::saturating_add);
+
+        weight.saturating_add(arguments)
+    }
+
+    /// Returns the estimated size of the list that a field returns, which is
+    /// the largest slicing argument, the assumed size or `1` otherwise.
+    fn list_size(&self, node: &Field<T>, list_size: Option<&Arc<Directive<T>>>) -> usize {
+        let slicing_arguments = list_size
+            .and_then(|directive| directive.argument("slicingArguments"))
+            .map(|argument| strings(argument))
+            .unwrap_or_else(|| vec!["first".to_owned(), "last".to_owned()]);
+
+        let assumed_size = list_size
+            .and_then(|directive| directive.argument("assumedSize"))
+            .and_then(|argument| argument.value.ok())
+            .and_then(|value| int(value));
+
+        node.arguments
+            .iter()
+            .flat_map(|arguments| arguments.items.iter())
+            .filter(|argument| {
+                slicing_arguments
+                    .iter()
+                    .any(|name| name == argument.name.as_ref().borrow())
+            })
+            .filter_map(|argument| self.argument_value(argument))
+            .max()
+            .or(assumed_size)
+            .unwrap_or(1)
+    }
+
+    /// Returns the integer value of an argument, resolving variables to their
+    /// default value.
+    fn argument_value(&self, argument: &Argument<T>) -> Option<usize> {
+        match argument.value.ok()?.as_ref() {
+            Value::Variable(variable) => self
+                .operation
+                .variable_definitions
+                .iter()
+                .flat_map(|definitions| definitions.variable_definitions.iter())
+                .find(|definition| definition.variable.name.as_ref() == variable.name.as_ref())?
+                .default_value
+                .as_ref()?
+                .value
+                .ok()
+                .and_then(|value| int(value)),
+            _ => int(argument.value.ok()?),
+        }
+    }
+}
+
+fn cost<T>(directives: Option<&Directives<T>>) -> Option<usize>
+where
+    T: Borrow<str>,
+{
+    directives?
+        .directive("cost")
+        .and_then(|directive| weight(directive))
+}
+
+fn weight<T>(directive: &Directive<T>) -> Option<usize>
+where
+    T: Borrow<str>,
+{
+    match directive.name.ok()?.as_ref().borrow() {
+        "cost" => int(directive.argument("weight")?.value.ok()?),
+        _ => None,
+    }
+}
+
+fn int<T>(value: &Value<T>) -> Option<usize>
+where
+    T: Borrow<str>,
+{
+    match value {
+        Value::IntValue(value) => value.to_i32().ok()?.try_into().ok(),
+        _ => None,
+    }
+}
+
+fn strings<T>(argument: &Argument<T>) -> Vec<String>
+where
+    T: Borrow<str>,
+{
+    match argument.value.ok().map(AsRef::as_ref) {
+        Some(Value::ListValue(list)) => list
+            .values
+            .iter()
+            .filter_map(|value| match value.as_ref() {
+                Value::StringValue(value) => Some(value.to_string()),
+                _ => None,
+            })
+            .collect(),
+        Some(Value::StringValue(value)) => vec![value.to_string()],
+        _ => vec![],
+    }
+}
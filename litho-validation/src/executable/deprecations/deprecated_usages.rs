@@ -0,0 +1,153 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+pub struct DeprecatedUsages<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> DeprecatedUsages<'a, T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    fn deprecation_reason(&self, directives: Option<&Directives<T>>) -> Option<String> {
+        let directive = directives?.directive("deprecated")?;
+
+        let reason = directive
+            .argument("reason")
+            .and_then(|argument| argument.value.ok())
+            .or_else(|| {
+                self.0
+                    .inference
+                    .definition_for_directive(directive)?
+                    .arguments_definition
+                    .as_ref()?
+                    .definitions
+                    .iter()
+                    .find(|definition| definition.name.as_ref().borrow() == "reason")?
+                    .default_value
+                    .as_ref()?
+                    .value
+                    .ok()
+            });
+
+        match reason.map(AsRef::as_ref) {
+            Some(Value::StringValue(reason)) => Some(reason.to_string()),
+            Some(_) | None => Some("No longer supported".to_owned()),
+        }
+    }
+}
+
+impl<'a, T> Visit<'a, T> for DeprecatedUsages<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_executable_definition(
+        &self,
+        node: &'a ExecutableDefinition<T>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        node.traverse(&DeprecatedExecutableUsages(self), accumulator);
+    }
+}
+
+struct DeprecatedExecutableUsages<'a, 'b, T>(&'b DeprecatedUsages<'a, T>)
+where
+    T: Eq + Hash;
+
+impl<'a, 'b, T> Visit<'a, T> for DeprecatedExecutableUsages<'a, 'b, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_field(&self, node: &'a Arc<Field<T>>, accumulator: &mut Self::Accumulator) {
+        let Some(name) = node.name.ok() else { return };
+
+        let Some(definition) = self.0 .0.inference.field_definitions_by_field.get(node) else {
+            return;
+        };
+
+        if let Some(reason) = self.0.deprecation_reason(definition.directives.as_ref()) {
+            accumulator.push(Diagnostic::deprecated_field(
+                name.as_ref().to_string(),
+                reason,
+                name.span(),
+            ));
+        }
+    }
+
+    fn visit_argument(&self, node: &'a Arc<Argument<T>>, accumulator: &mut Self::Accumulator) {
+        let Some(definition) = self.0 .0.inference.definitions_for_arguments.get(node) else {
+            return;
+        };
+
+        if let Some(reason) = self.0.deprecation_reason(definition.directives.as_ref()) {
+            accumulator.push(Diagnostic::deprecated_argument(
+                node.name.as_ref().to_string(),
+                reason,
+                node.name.span(),
+            ));
+        }
+    }
+
+    fn visit_value(&self, node: &'a Arc<Value<T>>, accumulator: &mut Self::Accumulator) {
+        let database = self.0 .0;
+
+        let Some(ty) = database
+            .inference
+            .types_for_values
+            .get(node)
+            .and_then(|ty| ty.name())
+        else {
+            return;
+        };
+
+        match node.as_ref() {
+            Value::EnumValue(value) => {
+                let Some(definition) = database
+                    .enum_value_definitions_by_name(ty, value.0.as_ref())
+                    .next()
+                else {
+                    return;
+                };
+
+                if let Some(reason) = self.0.deprecation_reason(definition.directives.as_ref()) {
+                    accumulator.push(Diagnostic::deprecated_enum_value(
+                        ty.to_string(),
+                        value.0.as_ref().to_string(),
+                        reason,
+                        value.span(),
+                    ));
+                }
+            }
+            Value::ObjectValue(value) => {
+                for field in value.object_fields.iter() {
+                    let Some(definition) = database
+                        .input_value_definitions_by_name(ty, field.name.as_ref())
+                        .next()
+                    else {
+                        continue;
+                    };
+
+                    if let Some(reason) = self.0.deprecation_reason(definition.directives.as_ref())
+                    {
+                        accumulator.push(Diagnostic::deprecated_input_field(
+                            field.name.as_ref().to_string(),
+                            ty.to_string(),
+                            reason,
+                            field.name.span(),
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
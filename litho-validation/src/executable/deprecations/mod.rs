@@ -0,0 +1,3 @@
+mod deprecated_usages;
+
+pub use deprecated_usages::DeprecatedUsages;
@@ -1,9 +1,10 @@
-use std::collections::HashMap;
+use std::borrow::Borrow;
 use std::hash::Hash;
 use std::sync::Arc;
 
 use litho_diagnostics::Diagnostic;
 use litho_language::ast::*;
+use litho_types::selection::{CollectedField, CollectedFields};
 use litho_types::Database;
 
 pub struct FieldSelectionMerging<'a, T>(pub &'a Database<T>)
@@ -12,50 +13,19 @@ where
 
 impl<'a, T> FieldSelectionMerging<'a, T>
 where
-    T: Eq + Hash + ToString,
+    T: Eq + Hash + Borrow<str> + ToString,
 {
-    fn fields_by_name(
-        &self,
-        set: &'a Arc<SelectionSet<T>>,
-        fields: &mut HashMap<&'a T, Vec<(&'a T, &'a Arc<Field<T>>)>>,
-    ) {
-        let Some(ty) = self.0.inference.type_by_selection_set.get(set) else {
-            return;
-        };
+    fn fields_by_name<I>(&self, sets: I) -> CollectedFields<'a, T>
+    where
+        I: IntoIterator<Item = &'a Option<Arc<SelectionSet<T>>>>,
+    {
+        let mut fields = CollectedFields::default();
 
-        for selection in set.selections.iter() {
-            match selection {
-                Selection::Field(field) => {
-                    let name = field
-                        .alias
-                        .as_ref()
-                        .map(|alias| &alias.name)
-                        .or(field.name.ok())
-                        .map(AsRef::as_ref);
-
-                    if let Some(name) = name {
-                        fields.entry(name).or_default().push((ty, field));
-                    }
-                }
-                Selection::FragmentSpread(fragment) => {
-                    let selection_set = self
-                        .0
-                        .operations
-                        .by_name(fragment.fragment_name.as_ref())
-                        .next()
-                        .and_then(|definition| definition.selection_set.ok());
-
-                    if let Some(selection_set) = selection_set {
-                        self.fields_by_name(selection_set, fields);
-                    }
-                }
-                Selection::InlineFragment(fragment) => {
-                    if let Some(selection_set) = fragment.selection_set.ok() {
-                        self.fields_by_name(selection_set, fields);
-                    }
-                }
-            }
+        for set in sets.into_iter().flatten() {
+            fields.collect(self.0, set, None);
         }
+
+        fields
     }
 
     fn pairs<'b, U>(&self, fields: &'b [U]) -> impl Iterator<Item = (&'b U, &'b U)>
@@ -70,7 +40,7 @@ where
     fn fields_can_merge(
         &self,
         response_key: &T,
-        fields: &[(&'a T, &'a Arc<Field<T>>)],
+        fields: &[CollectedField<'a, T>],
     ) -> Vec<Diagnostic<Span>> {
         let mut diagnostics = vec![];
 
@@ -84,21 +54,23 @@ where
     fn check_pair(
         &self,
         response_key: &T,
-        a: &(&T, &Arc<Field<T>>),
-        b: &(&T, &Arc<Field<T>>),
+        a: &CollectedField<'a, T>,
+        b: &CollectedField<'a, T>,
     ) -> Option<Diagnostic<Span>> {
-        let span_a =
-            a.1.alias
-                .as_ref()
-                .map(|alias| alias.name.span())
-                .unwrap_or(a.1.name.span());
-        let span_b =
-            b.1.alias
-                .as_ref()
-                .map(|alias| alias.name.span())
-                .unwrap_or(b.1.name.span());
-
-        if self.same_response_shape(a.1, b.1)? == false {
+        let span_a = a
+            .field
+            .alias
+            .as_ref()
+            .map(|alias| alias.name.span())
+            .unwrap_or(a.field.name.span());
+        let span_b = b
+            .field
+            .alias
+            .as_ref()
+            .map(|alias| alias.name.span())
+            .unwrap_or(b.field.name.span());
+
+        if !self.same_response_shape(a.field, b.field)? {
             return Some(Diagnostic::incompatible_response_shape(
                 response_key.to_string(),
                 span_a,
@@ -106,8 +78,11 @@ where
             ));
         }
 
-        if a.0 == b.0 || !self.0.is_object_type(&a.0) || !self.0.is_object_type(&b.0) {
-            if a.1.name.ok()?.as_ref() != b.1.name.ok()?.as_ref() {
+        if a.parent_type == b.parent_type
+            || !self.0.is_object_type(a.parent_type)
+            || !self.0.is_object_type(b.parent_type)
+        {
+            if a.field.name.ok()?.as_ref() != b.field.name.ok()?.as_ref() {
                 return Some(Diagnostic::different_field_names(
                     response_key.to_string(),
                     span_a,
@@ -115,7 +90,12 @@ where
                 ));
             }
 
-            if !a.1.arguments.as_ref().congruent(&b.1.arguments.as_ref()) {
+            if !a
+                .field
+                .arguments
+                .as_ref()
+                .congruent(&b.field.arguments.as_ref())
+            {
                 return Some(Diagnostic::different_field_arguments(
                     response_key.to_string(),
                     span_a,
@@ -123,18 +103,14 @@ where
                 ));
             }
 
-            let mut fields_by_name = HashMap::new();
+            let fields_by_name =
+                self.fields_by_name([&a.field.selection_set, &b.field.selection_set]);
 
-            if let Some(set) = a.1.selection_set.as_ref() {
-                self.fields_by_name(set, &mut fields_by_name);
-            }
-
-            if let Some(set) = b.1.selection_set.as_ref() {
-                self.fields_by_name(set, &mut fields_by_name);
-            }
-
-            for (response_key, fields) in fields_by_name.iter() {
-                if !self.fields_can_merge(response_key, fields).is_empty() {
+            for (nested_response_key, fields) in fields_by_name.iter() {
+                if !self
+                    .fields_can_merge(nested_response_key, fields)
+                    .is_empty()
+                {
                     return Some(Diagnostic::incompatible_response_fields(
                         response_key.to_string(),
                         span_a,
@@ -147,7 +123,7 @@ where
         None
     }
 
-    fn same_response_shape(&self, a: &Arc<Field<T>>, b: &Arc<Field<T>>) -> Option<bool> {
+    fn same_response_shape(&self, a: &'a Arc<Field<T>>, b: &'a Arc<Field<T>>) -> Option<bool> {
         let fields = &self.0.inference.field_definitions_by_field;
 
         let ty_a = fields.get(a)?.ty.ok()?;
@@ -157,21 +133,13 @@ where
             return Some(false);
         }
 
-        let mut fields_by_name = HashMap::new();
-
-        if let Some(set) = a.selection_set.as_ref() {
-            self.fields_by_name(set, &mut fields_by_name);
-        }
-
-        if let Some(set) = b.selection_set.as_ref() {
-            self.fields_by_name(set, &mut fields_by_name);
-        }
+        let fields_by_name = self.fields_by_name([&a.selection_set, &b.selection_set]);
 
         for (_, fields) in fields_by_name.iter() {
             let pairs = self.pairs(fields);
 
             for (first, second) in pairs {
-                if !self.same_response_shape(first.1, second.1)? {
+                if !self.same_response_shape(first.field, second.field)? {
                     return Some(false);
                 }
             }
@@ -208,7 +176,7 @@ where
 
 impl<'a, T> Visit<'a, T> for FieldSelectionMerging<'a, T>
 where
-    T: Eq + Hash + ToString,
+    T: Eq + Hash + Borrow<str> + ToString,
 {
     type Accumulator = Vec<Diagnostic<Span>>;
 
@@ -217,8 +185,8 @@ where
         node: &'a Arc<SelectionSet<T>>,
         accumulator: &mut Self::Accumulator,
     ) {
-        let mut fields_by_name = HashMap::new();
-        self.fields_by_name(node, &mut fields_by_name);
+        let mut fields_by_name = CollectedFields::default();
+        fields_by_name.collect(self.0, node, None);
 
         for (response_key, fields) in fields_by_name.iter() {
             accumulator.extend(self.fields_can_merge(response_key, fields).into_iter());
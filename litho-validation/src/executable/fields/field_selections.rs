@@ -1,3 +1,4 @@
+use std::borrow::Borrow;
 use std::hash::Hash;
 use std::sync::Arc;
 
@@ -5,13 +6,15 @@ use litho_diagnostics::Diagnostic;
 use litho_language::ast::*;
 use litho_types::Database;
 
+use crate::suggestions::suggestions;
+
 pub struct FieldSelections<'a, T>(pub &'a Database<T>)
 where
     T: Eq + Hash;
 
 impl<'a, T> Visit<'a, T> for FieldSelections<'a, T>
 where
-    T: Eq + Hash + ToString,
+    T: Eq + Hash + Borrow<str> + ToString,
 {
     type Accumulator = Vec<Diagnostic<Span>>;
 
@@ -42,6 +45,12 @@ where
                             accumulator.push(Diagnostic::undefined_field(
                                 ty.to_string(),
                                 name.as_ref().to_string(),
+                                suggestions(
+                                    name.as_ref().borrow(),
+                                    self.0
+                                        .field_definitions(ty)
+                                        .map(|definition| definition.name.as_ref().borrow()),
+                                ),
                                 name.span(),
                             ))
                         }
@@ -1,3 +1,4 @@
+use std::borrow::Borrow;
 use std::hash::Hash;
 use std::sync::Arc;
 
@@ -5,13 +6,15 @@ use litho_diagnostics::Diagnostic;
 use litho_language::ast::*;
 use litho_types::Database;
 
+use crate::suggestions::suggestions;
+
 pub struct FragmentSpreadTargetDefined<'a, T>(pub &'a Database<T>)
 where
     T: Eq + Hash;
 
 impl<'a, T> Visit<'a, T> for FragmentSpreadTargetDefined<'a, T>
 where
-    T: Eq + Hash + ToString,
+    T: Eq + Hash + Borrow<str> + ToString,
 {
     type Accumulator = Vec<Diagnostic<Span>>;
 
@@ -29,6 +32,10 @@ where
         {
             accumulator.push(Diagnostic::undefined_fragment(
                 node.fragment_name.as_ref().to_string(),
+                suggestions(
+                    node.fragment_name.as_ref().borrow(),
+                    self.0.fragments.names().map(Borrow::borrow),
+                ),
                 node.fragment_name.span(),
             ));
         }
@@ -0,0 +1,166 @@
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+/// Checks that the labels of `@defer` and `@stream` are unique per operation.
+/// Only string labels can be compared, so labels must not be variables.
+pub struct DeferStreamLabelUniqueness<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> DeferStreamLabelUniqueness<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    fn collect_label(
+        directive: Option<&'a Arc<Directive<T>>>,
+        spread: Option<Span>,
+        labels: &mut Vec<(String, Span)>,
+    ) {
+        let Some(value) = directive
+            .and_then(|directive| directive.argument("label"))
+            .and_then(|argument| argument.value.ok())
+        else {
+            return;
+        };
+
+        if let Value::StringValue(label) = value.as_ref() {
+            labels.push((label.to_string(), spread.unwrap_or(value.span())));
+        }
+    }
+
+    /// Collects the labels of all `@defer` and `@stream` directives in the
+    /// given selection set. Labels that are used in a fragment are attributed
+    /// to the spread of that fragment.
+    fn collect_labels(
+        &self,
+        selection_set: &'a SelectionSet<T>,
+        spread: Option<Span>,
+        visited: &mut HashSet<&'a T>,
+        labels: &mut Vec<(String, Span)>,
+    ) {
+        for selection in selection_set.selections.iter() {
+            match selection {
+                Selection::Field(field) => {
+                    Self::collect_label(
+                        field
+                            .directives
+                            .as_ref()
+                            .and_then(|directives| directives.directive("stream")),
+                        spread,
+                        labels,
+                    );
+
+                    if let Some(selection_set) = field.selection_set.as_ref() {
+                        self.collect_labels(selection_set, spread, visited, labels);
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    Self::collect_label(
+                        fragment
+                            .directives
+                            .as_ref()
+                            .and_then(|directives| directives.directive("defer")),
+                        spread,
+                        labels,
+                    );
+
+                    if let Some(selection_set) = fragment.selection_set.ok() {
+                        self.collect_labels(selection_set, spread, visited, labels);
+                    }
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    Self::collect_label(
+                        fragment_spread
+                            .directives
+                            .as_ref()
+                            .and_then(|directives| directives.directive("defer")),
+                        spread,
+                        labels,
+                    );
+
+                    let name = fragment_spread.fragment_name.as_ref();
+
+                    if !visited.insert(name) {
+                        continue;
+                    }
+
+                    let Some(selection_set) = self
+                        .0
+                        .fragments
+                        .by_name(name)
+                        .next()
+                        .and_then(|definition| definition.selection_set.ok())
+                    else {
+                        continue;
+                    };
+
+                    self.collect_labels(
+                        selection_set,
+                        spread.or(Some(fragment_spread.fragment_name.span())),
+                        visited,
+                        labels,
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T> Visit<'a, T> for DeferStreamLabelUniqueness<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_directive(&self, node: &'a Arc<Directive<T>>, accumulator: &mut Self::Accumulator) {
+        let Some(name) = node.name.ok() else { return };
+
+        if !matches!(name.as_ref().borrow(), "defer" | "stream") {
+            return;
+        }
+
+        let Some(value) = node
+            .argument("label")
+            .and_then(|argument| argument.value.ok())
+        else {
+            return;
+        };
+
+        if let Value::Variable(variable) = value.as_ref() {
+            accumulator.push(Diagnostic::defer_stream_label_is_variable(
+                name.as_ref().to_string(),
+                variable.name.as_ref().to_string(),
+                value.span(),
+            ));
+        }
+    }
+
+    fn visit_operation_definition(
+        &self,
+        node: &'a Arc<OperationDefinition<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(selection_set) = node.selection_set.ok() else {
+            return;
+        };
+
+        let mut labels = vec![];
+        self.collect_labels(selection_set, None, &mut HashSet::new(), &mut labels);
+
+        for (index, (label, span)) in labels.iter().enumerate() {
+            if let Some((_, first)) = labels[..index].iter().find(|(other, _)| other == label) {
+                accumulator.push(Diagnostic::duplicate_defer_stream_label(
+                    label.to_owned(),
+                    *first,
+                    *span,
+                ));
+            }
+        }
+    }
+}
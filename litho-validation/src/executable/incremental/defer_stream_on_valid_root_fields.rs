@@ -0,0 +1,118 @@
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+pub struct DeferStreamOnValidRootFields<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> DeferStreamOnValidRootFields<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    /// Checks the root selection set of a subscription, including inline
+    /// fragments and fragment spreads. Directives that are used in a fragment
+    /// are attributed to the spread of that fragment.
+    fn check_selection_set(
+        &self,
+        selection_set: &'a SelectionSet<T>,
+        spread: Option<Span>,
+        visited: &mut HashSet<&'a T>,
+        accumulator: &mut Vec<Diagnostic<Span>>,
+    ) {
+        for selection in selection_set.selections.iter() {
+            match selection {
+                Selection::Field(field) => {
+                    if let Some(directive) = field
+                        .directives
+                        .as_ref()
+                        .and_then(|directives| directives.directive("stream"))
+                        .filter(|directive| directive.is_enabled())
+                    {
+                        accumulator.push(Diagnostic::defer_stream_on_subscription_root(
+                            "stream".to_owned(),
+                            spread.unwrap_or(directive.span()),
+                        ));
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    if let Some(directive) =
+                        fragment.directives.as_ref().and_then(Directives::defer)
+                    {
+                        accumulator.push(Diagnostic::defer_stream_on_subscription_root(
+                            "defer".to_owned(),
+                            spread.unwrap_or(directive.span()),
+                        ));
+                    }
+
+                    if let Some(selection_set) = fragment.selection_set.ok() {
+                        self.check_selection_set(selection_set, spread, visited, accumulator);
+                    }
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    if let Some(directive) = fragment_spread
+                        .directives
+                        .as_ref()
+                        .and_then(Directives::defer)
+                    {
+                        accumulator.push(Diagnostic::defer_stream_on_subscription_root(
+                            "defer".to_owned(),
+                            spread.unwrap_or(directive.span()),
+                        ));
+                    }
+
+                    let name = fragment_spread.fragment_name.as_ref();
+
+                    if !visited.insert(name) {
+                        continue;
+                    }
+
+                    let Some(selection_set) = self
+                        .0
+                        .fragments
+                        .by_name(name)
+                        .next()
+                        .and_then(|definition| definition.selection_set.ok())
+                    else {
+                        continue;
+                    };
+
+                    self.check_selection_set(
+                        selection_set,
+                        spread.or(Some(fragment_spread.fragment_name.span())),
+                        visited,
+                        accumulator,
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T> Visit<'a, T> for DeferStreamOnValidRootFields<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_operation_definition(
+        &self,
+        node: &'a Arc<OperationDefinition<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(OperationType::Subscription(_)) = node.ty.as_ref() else {
+            return;
+        };
+
+        let Some(selection_set) = node.selection_set.ok() else {
+            return;
+        };
+
+        self.check_selection_set(selection_set, None, &mut HashSet::new(), accumulator);
+    }
+}
@@ -0,0 +1,7 @@
+mod defer_stream_label_uniqueness;
+mod defer_stream_on_valid_root_fields;
+mod stream_on_list_fields;
+
+pub use defer_stream_label_uniqueness::DeferStreamLabelUniqueness;
+pub use defer_stream_on_valid_root_fields::DeferStreamOnValidRootFields;
+pub use stream_on_list_fields::StreamOnListFields;
@@ -0,0 +1,42 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+pub struct StreamOnListFields<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> Visit<'a, T> for StreamOnListFields<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_field(&self, node: &'a Arc<Field<T>>, accumulator: &mut Self::Accumulator) {
+        let Some(name) = node.name.ok() else { return };
+
+        let Some(directive) = node
+            .directives
+            .as_ref()
+            .and_then(|directives| directives.directive("stream"))
+        else {
+            return;
+        };
+
+        let Some(ty) = self.0.inference.type_for_field(node) else {
+            return;
+        };
+
+        if ty.list_value_type().is_none() {
+            accumulator.push(Diagnostic::stream_on_non_list_field(
+                name.as_ref().to_string(),
+                ty.to_string(),
+                directive.span(),
+            ));
+        }
+    }
+}
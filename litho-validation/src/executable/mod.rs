@@ -6,9 +6,12 @@ use litho_language::ast::*;
 use litho_language::lex::Span;
 use litho_types::Database;
 
+mod deprecations;
 mod fields;
 mod fragments;
+mod incremental;
 mod operations;
+pub(crate) mod relay;
 
 pub fn check<N, T>(document: &N, database: &Database<T>) -> Vec<Diagnostic<Span>>
 where
@@ -16,6 +19,7 @@ where
     T: Eq + Hash + Borrow<str> + ToString,
 {
     let mut errors = vec![];
+    document.traverse(&deprecations::DeprecatedUsages(database), &mut errors);
     document.traverse(&fields::FieldSelectionMerging(database), &mut errors);
     document.traverse(&fields::FieldSelections(database), &mut errors);
     document.traverse(&fields::LeafFieldSelections(database), &mut errors);
@@ -31,8 +35,21 @@ where
         &mut errors,
     );
     document.traverse(&fragments::FragmentSpreadIsPossible(database), &mut errors);
+    document.traverse(
+        &incremental::DeferStreamLabelUniqueness(database),
+        &mut errors,
+    );
+    document.traverse(
+        &incremental::DeferStreamOnValidRootFields(database),
+        &mut errors,
+    );
+    document.traverse(&incremental::StreamOnListFields(database), &mut errors);
     document.traverse(&operations::OperationNameUniqueness(database), &mut errors);
     document.traverse(&operations::LoneAnonymousOperation(database), &mut errors);
+    document.traverse(&operations::SingleRootField(database), &mut errors);
     document.traverse(&operations::SupportedOperation(database), &mut errors);
+    document.traverse(&relay::ConnectionFields(database), &mut errors);
+    document.traverse(&relay::FragmentArgumentDefinitions, &mut errors);
+    document.traverse(&relay::FragmentArguments(database), &mut errors);
     errors
 }
@@ -1,7 +1,9 @@
 mod lone_anonymous_operation;
 mod operation_name_uniqueness;
+mod single_root_field;
 mod supported_operation;
 
 pub use lone_anonymous_operation::LoneAnonymousOperation;
 pub use operation_name_uniqueness::OperationNameUniqueness;
+pub use single_root_field::SingleRootField;
 pub use supported_operation::SupportedOperation;
@@ -0,0 +1,120 @@
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+pub struct SingleRootField<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> SingleRootField<'a, T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    /// Collects the response name, name and span of each root field. Fields
+    /// that are selected through a fragment spread are attributed to that
+    /// spread.
+    fn collect_fields(
+        &self,
+        selection_set: &'a SelectionSet<T>,
+        spread: Option<Span>,
+        visited: &mut HashSet<&'a T>,
+        fields: &mut Vec<(&'a T, &'a T, Span)>,
+    ) {
+        for selection in selection_set.selections.iter() {
+            match selection {
+                Selection::Field(field) => {
+                    let Some(name) = field.name.ok() else {
+                        continue;
+                    };
+
+                    let response_name = match field.alias.as_ref() {
+                        Some(alias) => alias.name.as_ref(),
+                        None => name.as_ref(),
+                    };
+
+                    fields.push((response_name, name.as_ref(), spread.unwrap_or(name.span())));
+                }
+                Selection::InlineFragment(fragment) => {
+                    if let Some(selection_set) = fragment.selection_set.ok() {
+                        self.collect_fields(selection_set, spread, visited, fields);
+                    }
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    let name = fragment_spread.fragment_name.as_ref();
+
+                    if !visited.insert(name) {
+                        continue;
+                    }
+
+                    let Some(selection_set) = self
+                        .0
+                        .fragments
+                        .by_name(name)
+                        .next()
+                        .and_then(|definition| definition.selection_set.ok())
+                    else {
+                        continue;
+                    };
+
+                    self.collect_fields(
+                        selection_set,
+                        spread.or(Some(fragment_spread.fragment_name.span())),
+                        visited,
+                        fields,
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T> Visit<'a, T> for SingleRootField<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_operation_definition(
+        &self,
+        node: &'a Arc<OperationDefinition<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(OperationType::Subscription(_)) = node.ty.as_ref() else {
+            return;
+        };
+
+        let Some(selection_set) = node.selection_set.ok() else {
+            return;
+        };
+
+        let mut fields = vec![];
+        self.collect_fields(selection_set, None, &mut HashSet::new(), &mut fields);
+
+        let Some(&(first, _, _)) = fields.first() else {
+            return;
+        };
+
+        let mut reported = HashSet::new();
+
+        for &(response_name, name, span) in fields.iter() {
+            if name.borrow().starts_with("__") {
+                accumulator.push(Diagnostic::introspection_subscription_root_field(
+                    name.to_string(),
+                    span,
+                ));
+            }
+
+            if response_name != first && reported.insert(response_name) {
+                accumulator.push(Diagnostic::multiple_subscription_root_fields(
+                    response_name.to_string(),
+                    span,
+                ));
+            }
+        }
+    }
+}
@@ -24,22 +24,19 @@ where
             return;
         };
 
-        let Some(ty) = self.0.inference.type_by_selection_set.get(selection_set) else {
-            return;
-        };
-
-        if self.0.type_exists(ty) {
-            return;
-        }
-
-        let name = match node.ty.as_ref() {
-            Some(OperationType::Query(_)) | None => "Query",
-            Some(OperationType::Mutation(_)) => "Mutation",
-            Some(OperationType::Subscription(_)) => "Subscription",
+        let name = match self.0.root_operation_type(node.ty.as_ref()) {
+            Some(ty) if self.0.type_exists(ty) => return,
+            Some(ty) => ty.to_string(),
+            None => node
+                .ty
+                .as_ref()
+                .map(OperationType::default_type_name)
+                .unwrap_or("Query")
+                .to_owned(),
         };
 
         accumulator.push(Diagnostic::unsupported_operation(
-            name.to_owned(),
+            name,
             node.ty
                 .as_ref()
                 .map(|ty| ty.span())
@@ -0,0 +1,96 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+pub struct ConnectionFields<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> ConnectionFields<'a, T>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    fn field_definition(&self, ty: &T, name: &str) -> Option<&'a Arc<FieldDefinition<T>>> {
+        self.0
+            .field_definitions(ty)
+            .find(|definition| definition.name.as_ref().borrow() == name)
+    }
+
+    fn missing_connection_field(&self, ty: &T) -> Option<&'static str> {
+        let Some(edges) = self
+            .field_definition(ty, "edges")
+            .and_then(|definition| definition.ty.ok())
+            .and_then(|ty| ty.list_value_type())
+            .and_then(|ty| ty.name())
+        else {
+            return Some("edges");
+        };
+
+        if self.field_definition(edges, "node").is_none() {
+            return Some("edges.node");
+        }
+
+        if self.field_definition(edges, "cursor").is_none() {
+            return Some("edges.cursor");
+        }
+
+        if self.field_definition(ty, "pageInfo").is_none() {
+            return Some("pageInfo");
+        }
+
+        None
+    }
+}
+
+impl<'a, T> Visit<'a, T> for ConnectionFields<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_field(&self, node: &'a Arc<Field<T>>, accumulator: &mut Self::Accumulator) {
+        let Some(name) = node.name.ok() else { return };
+
+        let Some(directive) = node
+            .directives
+            .as_ref()
+            .and_then(|directives| directives.directive("connection"))
+        else {
+            return;
+        };
+
+        let Some(definition) = self.0.inference.field_definitions_by_field.get(node) else {
+            return;
+        };
+
+        let Some(ty) = definition.ty.ok() else {
+            return;
+        };
+
+        if let Some(field) = ty.name().and_then(|ty| self.missing_connection_field(ty)) {
+            accumulator.push(Diagnostic::invalid_connection_field(
+                name.as_ref().to_string(),
+                ty.to_string(),
+                field.to_owned(),
+                directive.span(),
+            ));
+        }
+
+        let has_pagination_arguments = definition
+            .arguments_definition
+            .iter()
+            .flat_map(|definition| definition.definitions.iter())
+            .any(|definition| matches!(definition.name.as_ref().borrow(), "first" | "last"));
+
+        if !has_pagination_arguments {
+            accumulator.push(Diagnostic::missing_connection_arguments(
+                name.as_ref().to_string(),
+                directive.span(),
+            ));
+        }
+    }
+}
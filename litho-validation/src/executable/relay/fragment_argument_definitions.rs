@@ -0,0 +1,89 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_language::chk::collect_errors;
+use litho_language::Parse;
+
+pub struct FragmentArgumentDefinitions;
+
+impl<'a, T> Visit<'a, T> for FragmentArgumentDefinitions
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_fragment_definition(
+        &self,
+        node: &'a Arc<FragmentDefinition<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(fragment_name) = node.fragment_name.ok() else {
+            return;
+        };
+
+        for argument in node.argument_definitions() {
+            if fragment_argument_type(argument).is_some() {
+                continue;
+            }
+
+            accumulator.push(Diagnostic::invalid_fragment_argument_definition(
+                fragment_name.as_ref().to_string(),
+                argument.name.as_ref().to_string(),
+                argument.span(),
+            ));
+        }
+    }
+}
+
+/// Parses the `type` string of a fragment argument definition (e.g. `"Int!"`
+/// for `count: { type: "Int!" }`) and returns the resulting type.
+pub fn fragment_argument_type<T>(argument: &Argument<T>) -> Option<Arc<Type<String>>>
+where
+    T: Borrow<str> + ToString,
+{
+    let Value::StringValue(ty) = fragment_argument_field(argument, "type")? else {
+        return None;
+    };
+
+    let source = format!("query($argument: {}) {{ __typename }}", ty.to_string());
+    let result = Document::<String>::parse_from_str(Default::default(), &source).ok()?;
+
+    if !collect_errors(&result).is_empty() {
+        return None;
+    }
+
+    let Some(Definition::ExecutableDefinition(ExecutableDefinition::OperationDefinition(
+        operation,
+    ))) = result.0.definitions.first().map(AsRef::as_ref)
+    else {
+        return None;
+    };
+
+    match operation
+        .variable_definitions
+        .as_ref()?
+        .variable_definitions
+        .as_slice()
+    {
+        [definition] => definition.ty.ok().cloned(),
+        _ => None,
+    }
+}
+
+pub fn fragment_argument_field<'a, T>(argument: &'a Argument<T>, name: &str) -> Option<&'a Value<T>>
+where
+    T: Borrow<str>,
+{
+    match argument.value.ok().map(AsRef::as_ref) {
+        Some(Value::ObjectValue(object)) => object
+            .object_fields
+            .iter()
+            .find(|field| field.name.as_ref().borrow() == name)
+            .and_then(|field| field.value.ok())
+            .map(AsRef::as_ref),
+        _ => None,
+    }
+}
@@ -0,0 +1,195 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+use super::fragment_argument_definitions::{fragment_argument_field, fragment_argument_type};
+use crate::common::InputCoercion;
+use crate::suggestions::suggestions;
+
+pub struct FragmentArguments<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> FragmentArguments<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    /// Checks a value that is passed with `@arguments` against the type of
+    /// the argument that it is passed to, since these types aren't part of
+    /// the schema and the values are therefore never inferred.
+    fn check_value<U>(&self, ty: &Type<U>, node: &Value<T>, accumulator: &mut Vec<Diagnostic<Span>>)
+    where
+        U: ToString + Borrow<str>,
+    {
+        if let Some(diagnostic) = InputCoercion(self.0).check_ty(ty, node) {
+            accumulator.push(diagnostic);
+            return;
+        }
+
+        if node.is_variable() || node.is_null() {
+            return;
+        }
+
+        let ty = match ty {
+            Type::NonNull(ty) => ty.ty.as_ref(),
+            ty => ty,
+        };
+
+        let name = match (ty, node) {
+            (Type::List(ty), Value::ListValue(list)) => {
+                if let Some(ty) = ty.ty.ok() {
+                    for value in list.values.iter() {
+                        self.check_value(ty, value, accumulator);
+                    }
+                }
+
+                return;
+            }
+            (Type::Named(named), _) => named.0.as_ref().borrow(),
+            _ => return,
+        };
+
+        let Some(definition) = self.0.type_definitions().find(|definition| {
+            definition
+                .name()
+                .ok()
+                .is_some_and(|definition| definition.as_ref().borrow() == name)
+        }) else {
+            return;
+        };
+
+        let Some(name) = definition.name().ok().map(AsRef::as_ref) else {
+            return;
+        };
+
+        match (definition, node) {
+            (TypeDefinition::EnumTypeDefinition(_), Value::EnumValue(value)) => {
+                let value = value.0.as_ref();
+
+                if self
+                    .0
+                    .enum_value_definitions_by_name(name, value)
+                    .next()
+                    .is_none()
+                {
+                    accumulator.push(Diagnostic::unrecognized_enum_value(
+                        name.to_string(),
+                        value.to_string(),
+                        suggestions(
+                            value.borrow(),
+                            self.0
+                                .enum_value_definitions(name)
+                                .map(|definition| definition.enum_value.0.as_ref().borrow()),
+                        ),
+                        node.span(),
+                    ))
+                }
+            }
+            (TypeDefinition::EnumTypeDefinition(_), _) => {
+                accumulator.push(Diagnostic::expected_enum_value(ty.to_string(), node.span()))
+            }
+            (TypeDefinition::InputObjectTypeDefinition(_), Value::ObjectValue(object)) => {
+                for field in object.object_fields.iter() {
+                    let Some(ty) = self
+                        .0
+                        .input_value_definitions_by_name(name, field.name.as_ref())
+                        .next()
+                        .and_then(|definition| definition.ty.ok())
+                    else {
+                        continue;
+                    };
+
+                    if let Some(value) = field.value.ok() {
+                        self.check_value(ty, value, accumulator);
+                    }
+                }
+            }
+            (TypeDefinition::InputObjectTypeDefinition(_), _) => accumulator.push(
+                Diagnostic::expected_input_object_value(ty.to_string(), node.span()),
+            ),
+            _ => {}
+        }
+    }
+}
+
+impl<'a, T> Visit<'a, T> for FragmentArguments<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_fragment_spread(
+        &self,
+        node: &'a Arc<FragmentSpread<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(definition) = self.0.fragments.by_name(node.fragment_name.as_ref()).next() else {
+            return;
+        };
+
+        let arguments = node
+            .directives
+            .as_ref()
+            .and_then(|directives| directives.directive("arguments"))
+            .and_then(|directive| directive.arguments.as_ref())
+            .into_iter()
+            .flat_map(|arguments| arguments.items.iter())
+            .collect::<Vec<_>>();
+
+        for argument in arguments.iter() {
+            if let Some(argument_definition) =
+                definition.argument_definition(argument.name.as_ref().borrow())
+            {
+                if let Some((ty, value)) =
+                    fragment_argument_type(argument_definition).zip(argument.value.ok())
+                {
+                    self.check_value(&ty, value, accumulator);
+                }
+
+                continue;
+            }
+
+            accumulator.push(Diagnostic::undefined_fragment_argument(
+                node.fragment_name.as_ref().to_string(),
+                argument.name.as_ref().to_string(),
+                suggestions(
+                    argument.name.as_ref().borrow(),
+                    definition
+                        .argument_definitions()
+                        .map(|definition| definition.name.as_ref().borrow()),
+                ),
+                argument.name.span(),
+            ));
+        }
+
+        for argument_definition in definition.argument_definitions() {
+            let Some(ty) = fragment_argument_type(argument_definition) else {
+                continue;
+            };
+
+            if !ty.is_required()
+                || fragment_argument_field(argument_definition, "defaultValue").is_some()
+            {
+                continue;
+            }
+
+            if arguments
+                .iter()
+                .any(|argument| argument.name.as_ref() == argument_definition.name.as_ref())
+            {
+                continue;
+            }
+
+            accumulator.push(Diagnostic::missing_required_fragment_argument(
+                node.fragment_name.as_ref().to_string(),
+                argument_definition.name.as_ref().to_string(),
+                ty.to_string(),
+                node.fragment_name.span(),
+            ));
+        }
+    }
+}
@@ -0,0 +1,8 @@
+mod connection_fields;
+mod fragment_argument_definitions;
+mod fragment_arguments;
+
+pub use connection_fields::ConnectionFields;
+pub use fragment_argument_definitions::FragmentArgumentDefinitions;
+pub(crate) use fragment_argument_definitions::{fragment_argument_field, fragment_argument_type};
+pub use fragment_arguments::FragmentArguments;
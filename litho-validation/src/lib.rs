@@ -7,9 +7,19 @@ use litho_language::lex::Span;
 use litho_types::Database;
 
 mod common;
+mod complexity;
 mod executable;
+mod limits;
+mod lints;
+mod rule;
+mod suggestions;
 mod system;
 
+pub use complexity::Complexity;
+pub use limits::Limits;
+pub use lints::Lints;
+pub use rule::{Rule, RuleContext};
+
 pub fn check<N, T>(document: &N, database: &Database<T>) -> Vec<Diagnostic<Span>>
 where
     N: Node<T>,
@@ -20,3 +30,23 @@ where
     errors.extend(common::check(document, database));
     errors
 }
+
+pub fn lint<N, T>(document: &N, database: &Database<T>, lints: &Lints) -> Vec<Diagnostic<Span>>
+where
+    N: Node<T>,
+    T: Eq + Hash + Borrow<str> + for<'a> From<&'a str> + ToString,
+{
+    lints::check(document, database, lints)
+}
+
+pub fn check_limits<N, T>(
+    document: &N,
+    database: &Database<T>,
+    limits: &Limits,
+) -> Vec<Diagnostic<Span>>
+where
+    N: Node<T>,
+    T: Eq + Hash + Borrow<str> + for<'a> From<&'a str> + ToString,
+{
+    limits::check(document, database, limits)
+}
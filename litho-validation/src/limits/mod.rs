@@ -0,0 +1,58 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_language::lex::Span;
+use litho_language::SchemaCoordinate;
+use litho_types::Database;
+
+mod operation_limits;
+
+/// Set of optional limits on the [`Complexity`](crate::Complexity) of
+/// operations. All limits are disabled by default.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Limits {
+    /// Maximum depth of nested fields (E0340).
+    pub max_depth: Option<usize>,
+
+    /// Maximum number of selected fields (E0341).
+    pub max_fields: Option<usize>,
+
+    /// Maximum number of aliased fields (E0342).
+    pub max_aliases: Option<usize>,
+
+    /// Maximum estimated cost (E0343).
+    pub max_cost: Option<usize>,
+
+    /// Weights that override `@cost` on types, fields and arguments (e.g. for
+    /// a schema that can't be annotated).
+    pub costs: Vec<(SchemaCoordinate<String>, usize)>,
+}
+
+impl Limits {
+    /// Returns true if no limits are enabled (regardless of cost overrides).
+    pub fn is_empty(&self) -> bool {
+        Limits {
+            costs: vec![],
+            ..self.clone()
+        } == Limits::default()
+    }
+}
+
+pub fn check<N, T>(document: &N, database: &Database<T>, limits: &Limits) -> Vec<Diagnostic<Span>>
+where
+    N: Node<T>,
+    T: Eq + Hash + Borrow<str> + for<'a> From<&'a str> + ToString,
+{
+    let mut errors = vec![];
+
+    if !limits.is_empty() {
+        document.traverse(
+            &operation_limits::OperationLimits(database, limits),
+            &mut errors,
+        );
+    }
+
+    errors
+}
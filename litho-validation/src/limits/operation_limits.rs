@@ -0,0 +1,78 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+use super::Limits;
+use crate::Complexity;
+
+pub struct OperationLimits<'a, T>(pub &'a Database<T>, pub &'a Limits)
+where
+    T: Eq + Hash;
+
+impl<'a, T> Visit<'a, T> for OperationLimits<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + for<'b> From<&'b str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_operation_definition(
+        &self,
+        node: &'a Arc<OperationDefinition<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let complexity = Complexity::with_costs(node, self.0, &self.1.costs);
+
+        let span = match node.name.as_ref() {
+            Some(name) => name.span(),
+            None => node.selection_set.span(),
+        };
+
+        match self.1.max_depth {
+            Some(max) if complexity.depth > max => {
+                accumulator.push(Diagnostic::operation_exceeds_max_depth(
+                    complexity.depth.to_string(),
+                    max.to_string(),
+                    span,
+                ))
+            }
+            _ => {}
+        }
+
+        match self.1.max_fields {
+            Some(max) if complexity.fields > max => {
+                accumulator.push(Diagnostic::operation_exceeds_max_fields(
+                    complexity.fields.to_string(),
+                    max.to_string(),
+                    span,
+                ))
+            }
+            _ => {}
+        }
+
+        match self.1.max_aliases {
+            Some(max) if complexity.aliases > max => {
+                accumulator.push(Diagnostic::operation_exceeds_max_aliases(
+                    complexity.aliases.to_string(),
+                    max.to_string(),
+                    span,
+                ))
+            }
+            _ => {}
+        }
+
+        match self.1.max_cost {
+            Some(max) if complexity.cost > max => {
+                accumulator.push(Diagnostic::operation_exceeds_max_cost(
+                    complexity.cost.to_string(),
+                    max.to_string(),
+                    span,
+                ))
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,3 @@
+mod required_descriptions;
+
+pub use required_descriptions::RequiredDescriptions;
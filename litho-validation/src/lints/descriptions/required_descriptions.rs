@@ -0,0 +1,45 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+
+use super::super::naming::is_introspection_name;
+
+pub struct RequiredDescriptions;
+
+impl<'a, T> Visit<'a, T> for RequiredDescriptions
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_type_definition(
+        &self,
+        node: &'a Arc<TypeDefinition<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(name) = node.name().ok() else { return };
+
+        if node.description().is_none() && !is_introspection_name(name.as_ref().borrow()) {
+            accumulator.push(Diagnostic::missing_type_description(
+                name.as_ref().to_string(),
+                name.span(),
+            ));
+        }
+    }
+
+    fn visit_field_definition(
+        &self,
+        node: &'a Arc<FieldDefinition<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        if node.description.is_none() && !is_introspection_name(node.name.as_ref().borrow()) {
+            accumulator.push(Diagnostic::missing_field_description(
+                node.name.as_ref().to_string(),
+                node.name.span(),
+            ));
+        }
+    }
+}
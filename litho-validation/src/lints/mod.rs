@@ -0,0 +1,155 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_language::lex::Span;
+use litho_language::SchemaCoordinate;
+use litho_types::Database;
+
+mod descriptions;
+mod naming;
+mod queries;
+mod reachability;
+mod relay;
+
+/// Set of optional lint rules that enforce naming conventions and style in a
+/// schema. All rules are disabled by default.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Lints {
+    /// Type names must be PascalCase (L0001).
+    pub pascal_case_type_names: bool,
+
+    /// Field names must be camelCase (L0002).
+    pub camel_case_field_names: bool,
+
+    /// Argument names must be camelCase (L0003).
+    pub camel_case_argument_names: bool,
+
+    /// Enum values must be SCREAMING_SNAKE_CASE (L0004).
+    pub screaming_snake_case_enum_values: bool,
+
+    /// Input object names must end with `Input` (L0005).
+    pub input_object_suffix: bool,
+
+    /// Fields of the query root operation type must not start with `get`
+    /// (L0006).
+    pub no_get_prefix_on_query_fields: bool,
+
+    /// Types and fields that aren't part of the introspection system must have
+    /// a description (L0007 and L0008).
+    pub required_descriptions: bool,
+
+    /// Connection types, edge types, `PageInfo` and fields that return
+    /// connections must follow the Relay Cursor Connections spec (L0009 to
+    /// L0012).
+    pub relay_connections: bool,
+
+    /// Types must be reachable from a root operation type (L0013), directives
+    /// must be used (L0014) and interfaces must be implemented (L0015).
+    pub unused_definitions: bool,
+
+    /// Elements of the schema that are exempt from all lints (e.g. a type
+    /// that is named by a third party). Lints that are reported anywhere
+    /// within the definition of an exempt element are suppressed, so
+    /// exempting a type also exempts its fields and their arguments.
+    pub exemptions: Vec<SchemaCoordinate<String>>,
+}
+
+impl Lints {
+    pub fn all() -> Lints {
+        Lints {
+            pascal_case_type_names: true,
+            camel_case_field_names: true,
+            camel_case_argument_names: true,
+            screaming_snake_case_enum_values: true,
+            input_object_suffix: true,
+            no_get_prefix_on_query_fields: true,
+            required_descriptions: true,
+            relay_connections: true,
+            unused_definitions: true,
+            exemptions: vec![],
+        }
+    }
+
+    /// Returns true if no lint rules are enabled (regardless of exemptions).
+    pub fn is_empty(&self) -> bool {
+        Lints {
+            exemptions: vec![],
+            ..self.clone()
+        } == Lints::default()
+    }
+}
+
+pub fn check<N, T>(document: &N, database: &Database<T>, lints: &Lints) -> Vec<Diagnostic<Span>>
+where
+    N: Node<T>,
+    T: Eq + Hash + Borrow<str> + for<'a> From<&'a str> + ToString,
+{
+    let mut errors = vec![];
+
+    if lints.pascal_case_type_names {
+        document.traverse(&naming::PascalCaseTypeNames, &mut errors);
+    }
+
+    if lints.camel_case_field_names {
+        document.traverse(&naming::CamelCaseFieldNames, &mut errors);
+    }
+
+    if lints.camel_case_argument_names {
+        document.traverse(&naming::CamelCaseArgumentNames, &mut errors);
+    }
+
+    if lints.screaming_snake_case_enum_values {
+        document.traverse(&naming::ScreamingSnakeCaseEnumValues, &mut errors);
+    }
+
+    if lints.input_object_suffix {
+        document.traverse(&naming::InputObjectSuffix, &mut errors);
+    }
+
+    if lints.no_get_prefix_on_query_fields {
+        document.traverse(&queries::NoGetPrefix(database), &mut errors);
+    }
+
+    if lints.required_descriptions {
+        document.traverse(&descriptions::RequiredDescriptions, &mut errors);
+    }
+
+    if lints.relay_connections {
+        document.traverse(&relay::ConnectionTypes(database), &mut errors);
+        document.traverse(&relay::PaginationArguments(database), &mut errors);
+    }
+
+    if lints.unused_definitions {
+        document.traverse(&reachability::UnreachableTypes(database), &mut errors);
+        document.traverse(&reachability::UnusedDirectives(database), &mut errors);
+        document.traverse(
+            &reachability::UnimplementedInterfaces(database),
+            &mut errors,
+        );
+    }
+
+    if !lints.exemptions.is_empty() {
+        let exemptions = lints
+            .exemptions
+            .iter()
+            .filter_map(|coordinate| {
+                database.resolve_coordinate(&coordinate.map(|name| name.as_str().into()))
+            })
+            .flat_map(|element| element.definition_spans(database))
+            .collect::<Vec<_>>();
+
+        errors.retain(|error| {
+            let span = error.span();
+
+            !exemptions.iter().any(|exemption| {
+                exemption.source_id == span.source_id
+                    && exemption.contains(span.start)
+                    && exemption.contains(span.end)
+            })
+        });
+    }
+
+    errors
+}
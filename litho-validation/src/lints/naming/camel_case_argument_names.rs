@@ -0,0 +1,34 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+
+use super::{is_camel_case, is_introspection_name};
+
+pub struct CamelCaseArgumentNames;
+
+impl<'a, T> Visit<'a, T> for CamelCaseArgumentNames
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_arguments_definition(
+        &self,
+        node: &'a Arc<ArgumentsDefinition<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        for definition in node.definitions.iter() {
+            let borrowed = definition.name.as_ref().borrow();
+
+            if !is_introspection_name(borrowed) && !is_camel_case(borrowed) {
+                accumulator.push(Diagnostic::argument_name_not_camel_case(
+                    definition.name.as_ref().to_string(),
+                    definition.name.span(),
+                ));
+            }
+        }
+    }
+}
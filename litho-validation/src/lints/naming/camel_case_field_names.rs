@@ -0,0 +1,51 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+
+use super::{is_camel_case, is_introspection_name};
+
+pub struct CamelCaseFieldNames;
+
+impl CamelCaseFieldNames {
+    fn check_name<T>(&self, name: &Name<T>, accumulator: &mut Vec<Diagnostic<Span>>)
+    where
+        T: Borrow<str> + ToString,
+    {
+        let borrowed = name.as_ref().borrow();
+
+        if !is_introspection_name(borrowed) && !is_camel_case(borrowed) {
+            accumulator.push(Diagnostic::field_name_not_camel_case(
+                name.as_ref().to_string(),
+                name.span(),
+            ));
+        }
+    }
+}
+
+impl<'a, T> Visit<'a, T> for CamelCaseFieldNames
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_field_definition(
+        &self,
+        node: &'a Arc<FieldDefinition<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        self.check_name(&node.name, accumulator);
+    }
+
+    fn visit_input_fields_definition(
+        &self,
+        node: &'a InputFieldsDefinition<T>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        for definition in node.definitions.iter() {
+            self.check_name(&definition.name, accumulator);
+        }
+    }
+}
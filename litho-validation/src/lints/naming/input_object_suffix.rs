@@ -0,0 +1,29 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+
+pub struct InputObjectSuffix;
+
+impl<'a, T> Visit<'a, T> for InputObjectSuffix
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_input_object_type_definition(
+        &self,
+        node: &'a InputObjectTypeDefinition<T>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(name) = node.name.ok() else { return };
+
+        if !name.as_ref().borrow().ends_with("Input") {
+            accumulator.push(Diagnostic::input_object_without_input_suffix(
+                name.as_ref().to_string(),
+                name.span(),
+            ));
+        }
+    }
+}
@@ -0,0 +1,32 @@
+mod camel_case_argument_names;
+mod camel_case_field_names;
+mod input_object_suffix;
+mod pascal_case_type_names;
+mod screaming_snake_case_enum_values;
+
+pub use camel_case_argument_names::CamelCaseArgumentNames;
+pub use camel_case_field_names::CamelCaseFieldNames;
+pub use input_object_suffix::InputObjectSuffix;
+pub use pascal_case_type_names::PascalCaseTypeNames;
+pub use screaming_snake_case_enum_values::ScreamingSnakeCaseEnumValues;
+
+pub fn is_pascal_case(name: &str) -> bool {
+    name.starts_with(|char: char| char.is_ascii_uppercase())
+        && name.chars().all(|char| char.is_ascii_alphanumeric())
+}
+
+pub fn is_camel_case(name: &str) -> bool {
+    name.starts_with(|char: char| char.is_ascii_lowercase())
+        && name.chars().all(|char| char.is_ascii_alphanumeric())
+}
+
+pub fn is_screaming_snake_case(name: &str) -> bool {
+    name.starts_with(|char: char| char.is_ascii_uppercase())
+        && name
+            .chars()
+            .all(|char| char.is_ascii_uppercase() || char.is_ascii_digit() || char == '_')
+}
+
+pub fn is_introspection_name(name: &str) -> bool {
+    name.starts_with("__")
+}
@@ -0,0 +1,33 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+
+use super::{is_introspection_name, is_pascal_case};
+
+pub struct PascalCaseTypeNames;
+
+impl<'a, T> Visit<'a, T> for PascalCaseTypeNames
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_type_definition(
+        &self,
+        node: &'a Arc<TypeDefinition<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(name) = node.name().ok() else { return };
+        let borrowed = name.as_ref().borrow();
+
+        if !is_introspection_name(borrowed) && !is_pascal_case(borrowed) {
+            accumulator.push(Diagnostic::type_name_not_pascal_case(
+                name.as_ref().to_string(),
+                name.span(),
+            ));
+        }
+    }
+}
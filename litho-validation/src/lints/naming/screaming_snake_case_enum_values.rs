@@ -0,0 +1,31 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+
+use super::is_screaming_snake_case;
+
+pub struct ScreamingSnakeCaseEnumValues;
+
+impl<'a, T> Visit<'a, T> for ScreamingSnakeCaseEnumValues
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_enum_value_definition(
+        &self,
+        node: &'a EnumValueDefinition<T>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let name = &node.enum_value.0;
+
+        if !is_screaming_snake_case(name.as_ref().borrow()) {
+            accumulator.push(Diagnostic::enum_value_not_screaming_snake_case(
+                name.as_ref().to_string(),
+                name.span(),
+            ));
+        }
+    }
+}
@@ -0,0 +1,3 @@
+mod no_get_prefix;
+
+pub use no_get_prefix::NoGetPrefix;
@@ -0,0 +1,77 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+pub struct NoGetPrefix<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> NoGetPrefix<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    fn check_fields(
+        &self,
+        name: &T,
+        fields_definition: Option<&FieldsDefinition<T>>,
+        accumulator: &mut Vec<Diagnostic<Span>>,
+    ) {
+        if self.0.root_operation_type(None) != Some(name) {
+            return;
+        }
+
+        for definition in fields_definition
+            .into_iter()
+            .flat_map(|fields| fields.definitions.iter())
+        {
+            let has_prefix = definition
+                .name
+                .as_ref()
+                .borrow()
+                .strip_prefix("get")
+                .map(|rest| rest.starts_with(|char: char| !char.is_ascii_lowercase()))
+                .unwrap_or_default();
+
+            if has_prefix {
+                accumulator.push(Diagnostic::query_field_with_get_prefix(
+                    definition.name.as_ref().to_string(),
+                    definition.name.span(),
+                ));
+            }
+        }
+    }
+}
+
+impl<'a, T> Visit<'a, T> for NoGetPrefix<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_object_type_definition(
+        &self,
+        node: &'a ObjectTypeDefinition<T>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(name) = node.name.ok() else { return };
+
+        self.check_fields(name.as_ref(), node.fields_definition.as_ref(), accumulator);
+    }
+
+    fn visit_object_type_extension(
+        &self,
+        node: &'a ObjectTypeExtension<T>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(name) = node.name.ok() else { return };
+
+        self.check_fields(
+            name.0.as_ref(),
+            node.fields_definition.as_ref(),
+            accumulator,
+        );
+    }
+}
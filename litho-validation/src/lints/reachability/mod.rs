@@ -0,0 +1,7 @@
+mod unimplemented_interfaces;
+mod unreachable_types;
+mod unused_directives;
+
+pub use unimplemented_interfaces::UnimplementedInterfaces;
+pub use unreachable_types::UnreachableTypes;
+pub use unused_directives::UnusedDirectives;
@@ -0,0 +1,38 @@
+use std::hash::Hash;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+pub struct UnimplementedInterfaces<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> Visit<'a, T> for UnimplementedInterfaces<'a, T>
+where
+    T: Eq + Hash + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_interface_type_definition(
+        &self,
+        node: &'a InterfaceTypeDefinition<T>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(name) = node.name.ok() else { return };
+
+        if self
+            .0
+            .interface_implementations(name.as_ref())
+            .next()
+            .is_some()
+        {
+            return;
+        }
+
+        accumulator.push(Diagnostic::unimplemented_interface(
+            name.as_ref().to_string(),
+            name.span(),
+        ));
+    }
+}
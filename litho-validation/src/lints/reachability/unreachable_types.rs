@@ -0,0 +1,38 @@
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+pub struct UnreachableTypes<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> Visit<'a, T> for UnreachableTypes<'a, T>
+where
+    T: Eq + Hash + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_type_definition(
+        &self,
+        node: &'a Arc<TypeDefinition<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        if self.0.reachable_types().is_empty() {
+            return;
+        }
+
+        let Some(name) = node.name().ok() else { return };
+
+        if self.0.is_reachable_type(name.as_ref()) {
+            return;
+        }
+
+        accumulator.push(Diagnostic::unreachable_type(
+            name.as_ref().to_string(),
+            name.span(),
+        ));
+    }
+}
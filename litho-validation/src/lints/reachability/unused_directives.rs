@@ -0,0 +1,34 @@
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+pub struct UnusedDirectives<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> Visit<'a, T> for UnusedDirectives<'a, T>
+where
+    T: Eq + Hash + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_directive_definition(
+        &self,
+        node: &'a Arc<DirectiveDefinition<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(name) = node.name.ok() else { return };
+
+        if self.0.usages.directives.usages(node).next().is_some() {
+            return;
+        }
+
+        accumulator.push(Diagnostic::unused_directive(
+            name.as_ref().to_string(),
+            name.span(),
+        ));
+    }
+}
@@ -0,0 +1,212 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+/// Returns `true` if the given type is a connection type, i.e. if its name
+/// ends with `Connection` or if it implements an interface whose name does.
+pub fn is_connection_type<T>(database: &Database<T>, ty: &T) -> bool
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    has_suffix(database, ty, "Connection")
+}
+
+/// Returns `true` if the given type is an edge type, i.e. if its name ends
+/// with `Edge` or if it implements an interface whose name does.
+pub fn is_edge_type<T>(database: &Database<T>, ty: &T) -> bool
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    has_suffix(database, ty, "Edge")
+}
+
+fn has_suffix<T>(database: &Database<T>, ty: &T, suffix: &str) -> bool
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    ty.borrow().ends_with(suffix)
+        || database
+            .implemented_interfaces(ty)
+            .any(|interface| interface.0.as_ref().borrow().ends_with(suffix))
+}
+
+pub fn field_definition<'a, T>(
+    database: &'a Database<T>,
+    ty: &T,
+    name: &str,
+) -> Option<&'a Arc<FieldDefinition<T>>>
+where
+    T: Eq + Hash + Borrow<str>,
+{
+    database
+        .field_definitions(ty)
+        .find(|definition| definition.name.as_ref().borrow() == name)
+}
+
+/// Returns `true` if the given type is a (possibly non-null) scalar, which is
+/// how cursors are represented.
+pub fn is_scalar<T>(database: &Database<T>, ty: &Type<T>) -> bool
+where
+    T: Eq + Hash,
+{
+    ty.list_value_type().is_none()
+        && ty
+            .name()
+            .map(|name| {
+                database
+                    .type_definitions_by_name(name)
+                    .any(|definition| definition.is_scalar())
+            })
+            .unwrap_or_default()
+}
+
+pub struct ConnectionTypes<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> ConnectionTypes<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    fn check_field(
+        &self,
+        ty: &Name<T>,
+        field: &str,
+        expected: &str,
+        is_valid: impl FnOnce(&Type<T>) -> bool,
+        accumulator: &mut Vec<Diagnostic<Span>>,
+    ) {
+        let Some(definition) = field_definition(self.0, ty.as_ref(), field) else {
+            accumulator.push(Diagnostic::missing_connection_type_field(
+                ty.as_ref().to_string(),
+                field.to_owned(),
+                ty.span(),
+            ));
+            return;
+        };
+
+        let Some(field_ty) = definition.ty.ok() else {
+            return;
+        };
+
+        if !is_valid(field_ty) {
+            accumulator.push(Diagnostic::invalid_connection_type_field(
+                ty.as_ref().to_string(),
+                field.to_owned(),
+                expected.to_owned(),
+                definition.ty.span(),
+            ));
+        }
+    }
+
+    fn check_type(&self, ty: &Name<T>, accumulator: &mut Vec<Diagnostic<Span>>) {
+        if is_connection_type(self.0, ty.as_ref()) {
+            self.check_field(
+                ty,
+                "edges",
+                "a list of edge types",
+                |edges| {
+                    edges
+                        .list_value_type()
+                        .filter(|edge| edge.list_value_type().is_none())
+                        .and_then(|edge| edge.name())
+                        .map(|edge| is_edge_type(self.0, edge))
+                        .unwrap_or_default()
+                },
+                accumulator,
+            );
+
+            self.check_field(
+                ty,
+                "pageInfo",
+                "a non-null `PageInfo` object",
+                |page_info| {
+                    page_info.is_required()
+                        && page_info.list_value_type().is_none()
+                        && page_info
+                            .name()
+                            .map(|name| self.0.is_object_type(name))
+                            .unwrap_or_default()
+                },
+                accumulator,
+            );
+        } else if is_edge_type(self.0, ty.as_ref()) {
+            self.check_field(
+                ty,
+                "node",
+                "a non-list output type",
+                |node| {
+                    node.list_value_type().is_none()
+                        && node
+                            .name()
+                            .map(|name| self.0.is_output_type(name))
+                            .unwrap_or_default()
+                },
+                accumulator,
+            );
+
+            self.check_field(
+                ty,
+                "cursor",
+                "a scalar",
+                |cursor| is_scalar(self.0, cursor),
+                accumulator,
+            );
+        } else if ty.as_ref().borrow() == "PageInfo" {
+            for field in ["hasPreviousPage", "hasNextPage"] {
+                self.check_field(
+                    ty,
+                    field,
+                    "`Boolean!`",
+                    |ty| {
+                        ty.is_required()
+                            && ty.list_value_type().is_none()
+                            && ty.name().map(Borrow::borrow) == Some("Boolean")
+                    },
+                    accumulator,
+                );
+            }
+
+            for field in ["startCursor", "endCursor"] {
+                self.check_field(
+                    ty,
+                    field,
+                    "a scalar",
+                    |cursor| is_scalar(self.0, cursor),
+                    accumulator,
+                );
+            }
+        }
+    }
+}
+
+impl<'a, T> Visit<'a, T> for ConnectionTypes<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_object_type_definition(
+        &self,
+        node: &'a ObjectTypeDefinition<T>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(name) = node.name.ok() else { return };
+
+        self.check_type(name, accumulator);
+    }
+
+    fn visit_interface_type_definition(
+        &self,
+        node: &'a InterfaceTypeDefinition<T>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(name) = node.name.ok() else { return };
+
+        self.check_type(name, accumulator);
+    }
+}
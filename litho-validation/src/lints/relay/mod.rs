@@ -0,0 +1,5 @@
+mod connection_types;
+mod pagination_arguments;
+
+pub use connection_types::ConnectionTypes;
+pub use pagination_arguments::PaginationArguments;
@@ -0,0 +1,115 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+use super::connection_types::{field_definition, is_connection_type, is_scalar};
+
+pub struct PaginationArguments<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> PaginationArguments<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    /// Returns the cursor type of the edges of the given connection type, if
+    /// any.
+    fn cursor_type(&self, connection: &T) -> Option<&'a Arc<Type<T>>> {
+        let edge = field_definition(self.0, connection, "edges")?
+            .ty
+            .ok()?
+            .list_value_type()?
+            .name()?;
+
+        field_definition(self.0, edge, "cursor")?.ty.ok()
+    }
+
+    fn check_argument(
+        &self,
+        node: &FieldDefinition<T>,
+        argument: &InputValueDefinition<T>,
+        cursor: Option<&Arc<Type<T>>>,
+        accumulator: &mut Vec<Diagnostic<Span>>,
+    ) {
+        let Some(ty) = argument.ty.ok() else { return };
+
+        let expected = match argument.name.as_ref().borrow() {
+            "first" | "last" => match ty.list_value_type().is_none()
+                && ty.name().map(Borrow::borrow) == Some("Int")
+            {
+                true => return,
+                false => "`Int`".to_owned(),
+            },
+            "after" | "before" => match cursor {
+                Some(cursor) if cursor.name() == ty.name() && is_scalar(self.0, ty) => return,
+                Some(cursor) => format!("`{}`", cursor.as_nullable()),
+                None if is_scalar(self.0, ty) => return,
+                None => "a scalar".to_owned(),
+            },
+            _ => return,
+        };
+
+        accumulator.push(Diagnostic::invalid_pagination_argument(
+            node.name.as_ref().to_string(),
+            argument.name.as_ref().to_string(),
+            expected,
+            argument.ty.span(),
+        ));
+    }
+}
+
+impl<'a, T> Visit<'a, T> for PaginationArguments<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_field_definition(
+        &self,
+        node: &'a Arc<FieldDefinition<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(ty) = node.ty.ok() else { return };
+
+        let Some(connection) = ty
+            .name()
+            .filter(|_| ty.list_value_type().is_none())
+            .filter(|name| is_connection_type(self.0, name))
+        else {
+            return;
+        };
+
+        let arguments = node
+            .arguments_definition
+            .iter()
+            .flat_map(|definition| definition.definitions.iter())
+            .collect::<Vec<_>>();
+
+        let has_argument = |name: &str| {
+            arguments
+                .iter()
+                .any(|argument| argument.name.as_ref().borrow() == name)
+        };
+
+        let is_forward = has_argument("first") && has_argument("after");
+        let is_backward = has_argument("last") && has_argument("before");
+
+        if !is_forward && !is_backward {
+            accumulator.push(Diagnostic::missing_pagination_arguments(
+                node.name.as_ref().to_string(),
+                connection.to_string(),
+                node.name.span(),
+            ));
+        }
+
+        let cursor = self.cursor_type(connection);
+
+        for argument in arguments {
+            self.check_argument(node, argument, cursor, accumulator);
+        }
+    }
+}
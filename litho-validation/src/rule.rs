@@ -0,0 +1,75 @@
+use std::any::type_name;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_language::lex::Span;
+use litho_types::Database;
+
+/// Accumulator of custom rules, which gives them access to the database and
+/// collects the diagnostics that they report.
+pub struct RuleContext<'a, T>
+where
+    T: Eq + Hash,
+{
+    pub database: &'a Database<T>,
+    pub diagnostics: Vec<Diagnostic<Span>>,
+}
+
+impl<'a, T> RuleContext<'a, T>
+where
+    T: Eq + Hash,
+{
+    pub fn report(&mut self, diagnostic: Diagnostic<Span>) {
+        self.diagnostics.push(diagnostic)
+    }
+}
+
+/// Custom validation rule that runs after the builtin checks. Any visitor
+/// that accumulates into a [`RuleContext`] is a rule. Rules report their own
+/// diagnostics with [`Diagnostic::custom`].
+pub trait Rule<T>: Send + Sync
+where
+    T: Eq + Hash,
+{
+    fn name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    fn check<'a>(
+        &self,
+        definition: &'a Arc<Definition<T>>,
+        database: &'a Database<T>,
+    ) -> Vec<Diagnostic<Span>>;
+}
+
+impl<T, R> Rule<T> for R
+where
+    T: Eq + Hash,
+    R: for<'a> Visit<'a, T, Accumulator = RuleContext<'a, T>> + Send + Sync,
+{
+    fn check<'a>(
+        &self,
+        definition: &'a Arc<Definition<T>>,
+        database: &'a Database<T>,
+    ) -> Vec<Diagnostic<Span>> {
+        let mut context = RuleContext {
+            database,
+            diagnostics: vec![],
+        };
+
+        definition.traverse(self, &mut context);
+        context.diagnostics
+    }
+}
+
+impl<T> fmt::Debug for dyn Rule<T>
+where
+    T: Eq + Hash,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::BTreeSet;
+
+/// Returns up to 5 candidates that are similar to the given name, ordered from
+/// most to least similar. Candidates are compared using their (optimal string
+/// alignment) edit distance, which may be at most a third of the length of the
+/// name (and at most 3). Candidates that only differ in case are always
+/// considered similar.
+pub fn suggestions<'a, I>(name: &str, candidates: I) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let threshold = (name.chars().count() / 3).clamp(1, 3);
+
+    let mut suggestions = candidates
+        .into_iter()
+        .filter(|&candidate| candidate != name)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .flat_map(|candidate| {
+            let distance = match candidate.eq_ignore_ascii_case(name) {
+                true => 1,
+                false => distance(name, candidate),
+            };
+
+            (distance <= threshold).then_some((distance, candidate))
+        })
+        .collect::<Vec<_>>();
+
+    suggestions.sort();
+    suggestions
+        .into_iter()
+        .take(5)
+        .map(|(_, candidate)| candidate.to_owned())
+        .collect()
+}
+
+fn distance(left: &str, right: &str) -> usize {
+    let left = left.chars().collect::<Vec<_>>();
+    let right = right.chars().collect::<Vec<_>>();
+
+    let mut rows = vec![(0..=right.len()).collect::<Vec<_>>(); left.len() + 1];
+
+    for i in 1..=left.len() {
+        rows[i][0] = i;
+
+        for j in 1..=right.len() {
+            let cost = (left[i - 1] != right[j - 1]) as usize;
+
+            rows[i][j] = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && left[i - 1] == right[j - 2] && left[i - 2] == right[j - 1] {
+                rows[i][j] = rows[i][j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    rows[left.len()][right.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{distance, suggestions};
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("", ""), 0);
+        assert_eq!(distance("user", "user"), 0);
+        assert_eq!(distance("user", ""), 4);
+        assert_eq!(distance("", "user"), 4);
+        assert_eq!(distance("user", "users"), 1);
+        assert_eq!(distance("user", "usr"), 1);
+        assert_eq!(distance("user", "uses"), 1);
+        assert_eq!(distance("user", "usre"), 1);
+        assert_eq!(distance("kitten", "sitting"), 3);
+        assert_eq!(distance("ca", "abc"), 3);
+        assert_eq!(distance("naïve", "naive"), 1);
+    }
+
+    #[test]
+    fn test_suggestions_threshold() {
+        assert_eq!(suggestions("id", ["ids", "idxx"]), vec!["ids"]);
+        assert_eq!(suggestions("userName", ["usrNme", "uName"]), vec!["usrNme"]);
+        assert_eq!(
+            suggestions("firstNameOfUser", ["firstNmeOfUsr", "fNameOfUsr"]),
+            vec!["firstNmeOfUsr"]
+        );
+        assert_eq!(suggestions("userName", ["USERNAME"]), vec!["USERNAME"]);
+        assert!(suggestions("user", ["account", "role"]).is_empty());
+    }
+
+    #[test]
+    fn test_suggestions_order() {
+        assert_eq!(
+            suggestions("count", ["counts", "amount", "cont", "Count", "count"]),
+            vec!["Count", "cont", "counts"]
+        );
+        assert_eq!(
+            suggestions("fields", ["folds", "feels", "yields", "field", "fiedls"]),
+            vec!["fiedls", "field", "yields", "feels", "folds"]
+        );
+        assert_eq!(
+            suggestions(
+                "item",
+                ["items", "iten", "itam", "atem", "itema", "tem", "iem"]
+            ),
+            vec!["atem", "iem", "itam", "itema", "items"]
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+use crate::suggestions::suggestions;
+
+pub struct ExtendedTypesExist<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> Visit<'a, T> for ExtendedTypesExist<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_type_extension(
+        &self,
+        node: &'a Arc<TypeExtension<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(named_type) = node.named_type() else {
+            return;
+        };
+
+        if self.0.type_exists(named_type.0.as_ref()) {
+            return;
+        }
+
+        accumulator.push(Diagnostic::undefined_extended_type(
+            named_type.0.as_ref().to_string(),
+            suggestions(
+                named_type.0.as_ref().borrow(),
+                self.0
+                    .type_definitions()
+                    .flat_map(|definition| definition.name().ok())
+                    .map(|name| name.as_ref().borrow()),
+            ),
+            named_type.span(),
+        ));
+    }
+}
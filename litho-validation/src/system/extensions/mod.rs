@@ -1,3 +1,5 @@
+mod extended_types_exist;
 mod same_type_extensions;
 
+pub use extended_types_exist::ExtendedTypesExist;
 pub use same_type_extensions::SameTypeExtensions;
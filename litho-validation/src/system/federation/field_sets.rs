@@ -0,0 +1,271 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_language::chk::collect_errors;
+use litho_language::Parse;
+use litho_types::Database;
+
+use crate::suggestions::suggestions;
+
+pub struct FieldSets<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> FieldSets<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    /// Parses the `fields` argument of the given directive as a selection set.
+    fn parse(directive: &Directive<T>) -> Option<Result<Arc<SelectionSet<String>>, Span>> {
+        let value = directive.argument("fields")?.value.ok()?;
+
+        let Value::StringValue(fields) = value.as_ref() else {
+            return None;
+        };
+
+        let source = format!("query {{ {} }}", fields.to_string());
+        let result =
+            Document::<String>::parse_from_str(Default::default(), &source).unwrap_or_default();
+
+        if !collect_errors(&result).is_empty() {
+            return Some(Err(value.span()));
+        }
+
+        match result.0.definitions.as_slice() {
+            [definition] => match definition.as_ref() {
+                Definition::ExecutableDefinition(ExecutableDefinition::OperationDefinition(
+                    operation,
+                )) => operation
+                    .selection_set
+                    .ok()
+                    .cloned()
+                    .map(Ok)
+                    .or(Some(Err(value.span()))),
+                _ => Some(Err(value.span())),
+            },
+            _ => Some(Err(value.span())),
+        }
+    }
+
+    fn check_selection_set(
+        &self,
+        directive: &str,
+        ty: &'a T,
+        selection_set: &SelectionSet<String>,
+        span: Span,
+        accumulator: &mut Vec<Diagnostic<Span>>,
+    ) {
+        for selection in selection_set.selections.iter() {
+            match selection {
+                Selection::Field(field) => {
+                    let Some(name) = field.name.ok() else {
+                        continue;
+                    };
+
+                    if name.as_ref() == "__typename" {
+                        continue;
+                    }
+
+                    let Some(definition) = self.0.field_definitions(ty).find(|definition| {
+                        definition.name.as_ref().borrow() == name.as_ref().as_str()
+                    }) else {
+                        accumulator.push(Diagnostic::undefined_field_set_field(
+                            directive.to_owned(),
+                            ty.to_string(),
+                            name.as_ref().to_owned(),
+                            suggestions(
+                                name.as_ref(),
+                                self.0
+                                    .field_definitions(ty)
+                                    .map(|definition| definition.name.as_ref().borrow()),
+                            ),
+                            span,
+                        ));
+                        continue;
+                    };
+
+                    let Some(field_ty) = definition.ty.ok().and_then(|ty| ty.name()) else {
+                        continue;
+                    };
+
+                    match (
+                        self.0.is_composite_type(field_ty),
+                        field.selection_set.as_ref(),
+                    ) {
+                        (true, Some(selection_set)) => self.check_selection_set(
+                            directive,
+                            field_ty,
+                            selection_set,
+                            span,
+                            accumulator,
+                        ),
+                        (true, None) => {
+                            accumulator.push(Diagnostic::missing_field_set_selection_set(
+                                directive.to_owned(),
+                                field_ty.to_string(),
+                                name.as_ref().to_owned(),
+                                span,
+                            ))
+                        }
+                        (false, Some(_)) => {
+                            accumulator.push(Diagnostic::unexpected_field_set_selection_set(
+                                directive.to_owned(),
+                                field_ty.to_string(),
+                                name.as_ref().to_owned(),
+                                span,
+                            ))
+                        }
+                        (false, None) => {}
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    let ty = match fragment
+                        .type_condition
+                        .as_ref()
+                        .and_then(|condition| condition.named_type.ok())
+                    {
+                        Some(condition) => match self.0.type_definitions().find_map(|definition| {
+                            definition
+                                .name()
+                                .ok()
+                                .map(AsRef::as_ref)
+                                .filter(|&name| name.borrow() == condition.0.as_ref().as_str())
+                        }) {
+                            Some(ty) => ty,
+                            None => continue,
+                        },
+                        None => ty,
+                    };
+
+                    if let Some(selection_set) = fragment.selection_set.ok() {
+                        self.check_selection_set(directive, ty, selection_set, span, accumulator);
+                    }
+                }
+                Selection::FragmentSpread(_) => {
+                    accumulator.push(Diagnostic::invalid_field_set(directive.to_owned(), span))
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the given directive is defined with a `fields`
+    /// argument of the `FieldSet` type, as the federation directives are.
+    /// Other directives with the same name are left alone.
+    fn takes_field_set(&self, directive: &Arc<Directive<T>>) -> bool {
+        self.0
+            .inference
+            .definition_for_directive(directive)
+            .and_then(|definition| definition.arguments_definition.as_ref())
+            .into_iter()
+            .flat_map(|arguments| arguments.definitions.iter())
+            .any(|argument| {
+                argument.name.as_ref().borrow() == "fields"
+                    && argument
+                        .ty
+                        .ok()
+                        .and_then(|ty| ty.name())
+                        .map(|name| name.borrow() == "FieldSet")
+                        .unwrap_or_default()
+            })
+    }
+
+    fn check_directive(
+        &self,
+        directives: Option<&'a Directives<T>>,
+        name: &str,
+        ty: &'a T,
+        accumulator: &mut Vec<Diagnostic<Span>>,
+    ) {
+        for directive in directives
+            .into_iter()
+            .flat_map(|directives| directives.directives.iter())
+            .filter(|directive| {
+                directive
+                    .name
+                    .ok()
+                    .map(|directive| directive.as_ref().borrow() == name)
+                    .unwrap_or_default()
+                    && self.takes_field_set(directive)
+            })
+        {
+            match Self::parse(directive) {
+                Some(Ok(selection_set)) => {
+                    let Some(span) = directive
+                        .argument("fields")
+                        .and_then(|argument| argument.value.ok())
+                        .map(|value| value.span())
+                    else {
+                        continue;
+                    };
+
+                    self.check_selection_set(name, ty, &selection_set, span, accumulator)
+                }
+                Some(Err(span)) => {
+                    accumulator.push(Diagnostic::invalid_field_set(name.to_owned(), span))
+                }
+                None => {}
+            }
+        }
+    }
+
+    fn check_type(
+        &self,
+        ty: &'a T,
+        directives: Option<&'a Directives<T>>,
+        fields_definition: Option<&'a FieldsDefinition<T>>,
+        accumulator: &mut Vec<Diagnostic<Span>>,
+    ) {
+        self.check_directive(directives, "key", ty, accumulator);
+
+        for field in fields_definition
+            .into_iter()
+            .flat_map(|fields| fields.definitions.iter())
+        {
+            self.check_directive(field.directives.as_ref(), "requires", ty, accumulator);
+
+            if let Some(field_ty) = field.ty.ok().and_then(|ty| ty.name()) {
+                self.check_directive(field.directives.as_ref(), "provides", field_ty, accumulator);
+            }
+        }
+    }
+}
+
+impl<'a, T> Visit<'a, T> for FieldSets<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_type_definition(
+        &self,
+        node: &'a Arc<TypeDefinition<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(name) = node.name().ok() else { return };
+
+        self.check_type(
+            name.as_ref(),
+            node.directives(),
+            node.fields_definition(),
+            accumulator,
+        )
+    }
+
+    fn visit_type_extension(
+        &self,
+        node: &'a Arc<TypeExtension<T>>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(name) = node.name() else { return };
+
+        self.check_type(
+            name,
+            node.directives(),
+            node.fields_definition(),
+            accumulator,
+        )
+    }
+}
@@ -0,0 +1,3 @@
+mod field_sets;
+
+pub use field_sets::FieldSets;
@@ -1,3 +1,5 @@
+mod one_of_input_fields;
 mod self_referential_inputs;
 
+pub use one_of_input_fields::OneOfInputFields;
 pub use self_referential_inputs::SelfReferentialInputs;
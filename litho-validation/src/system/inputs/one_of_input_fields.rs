@@ -0,0 +1,82 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+pub struct OneOfInputFields<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> OneOfInputFields<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    fn check_fields(
+        &self,
+        name: &'a T,
+        fields_definition: Option<&'a InputFieldsDefinition<T>>,
+        accumulator: &mut Vec<Diagnostic<Span>>,
+    ) {
+        if !self.0.is_one_of_input_object(name) {
+            return;
+        }
+
+        for field in fields_definition
+            .into_iter()
+            .flat_map(|def| def.definitions.iter())
+        {
+            match field.ty.ok() {
+                Some(ty) if ty.is_required() => {
+                    accumulator.push(Diagnostic::non_null_one_of_input_field(
+                        name.to_string(),
+                        field.name.as_ref().to_string(),
+                        ty.to_string(),
+                        ty.span(),
+                    ));
+                }
+                _ => {}
+            }
+
+            if let Some(default_value) = field.default_value.as_ref() {
+                accumulator.push(Diagnostic::one_of_input_field_default_value(
+                    name.to_string(),
+                    field.name.as_ref().to_string(),
+                    default_value.span(),
+                ));
+            }
+        }
+    }
+}
+
+impl<'a, T> Visit<'a, T> for OneOfInputFields<'a, T>
+where
+    T: Eq + Hash + Borrow<str> + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_input_object_type_definition(
+        &self,
+        node: &'a InputObjectTypeDefinition<T>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(name) = node.name.ok() else { return };
+
+        self.check_fields(name.as_ref(), node.fields_definition.as_ref(), accumulator)
+    }
+
+    fn visit_input_object_type_extension(
+        &self,
+        node: &'a InputObjectTypeExtension<T>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(name) = node.name.ok() else { return };
+
+        self.check_fields(
+            name.0.as_ref(),
+            node.fields_definition.as_ref(),
+            accumulator,
+        )
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+pub struct ImplementationCycles<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> ImplementationCycles<'a, T>
+where
+    T: Eq + Hash + ToString,
+{
+    fn implements_transitively(&self, ty: &'a T, name: &T, visited: &mut HashSet<&'a T>) -> bool {
+        if !visited.insert(ty) {
+            return false;
+        }
+
+        self.0.implemented_interfaces(ty).any(|interface| {
+            interface.0.as_ref() == name
+                || self.implements_transitively(interface.0.as_ref(), name, visited)
+        })
+    }
+
+    fn check_interfaces(
+        &self,
+        name: &T,
+        implements_interfaces: Option<&'a ImplementsInterfaces<T>>,
+        accumulator: &mut Vec<Diagnostic<Span>>,
+    ) {
+        for interface in implements_interfaces
+            .into_iter()
+            .flat_map(|interfaces| interfaces.named_types())
+        {
+            if interface.0.as_ref() == name {
+                continue;
+            }
+
+            if self.implements_transitively(interface.0.as_ref(), name, &mut HashSet::new()) {
+                accumulator.push(Diagnostic::cyclic_interface_implementation(
+                    name.to_string(),
+                    interface.0.as_ref().to_string(),
+                    interface.span(),
+                ));
+            }
+        }
+    }
+}
+
+impl<'a, T> Visit<'a, T> for ImplementationCycles<'a, T>
+where
+    T: Eq + Hash + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_interface_type_definition(
+        &self,
+        node: &'a InterfaceTypeDefinition<T>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(name) = node.name.ok() else { return };
+
+        self.check_interfaces(
+            name.as_ref(),
+            node.implements_interfaces.as_ref(),
+            accumulator,
+        );
+    }
+
+    fn visit_interface_type_extension(
+        &self,
+        node: &'a InterfaceTypeExtension<T>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(name) = node.name.ok() else { return };
+
+        self.check_interfaces(
+            name.0.as_ref(),
+            node.implements_interfaces.as_ref(),
+            accumulator,
+        );
+    }
+}
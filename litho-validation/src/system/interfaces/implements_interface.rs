@@ -24,6 +24,12 @@ where
             .0
             .implemented_interfaces(interface_named_type.0.as_ref())
         {
+            // Interfaces that (indirectly) implement themselves are reported
+            // separately (E0133).
+            if inherited.0.as_ref() == name {
+                continue;
+            }
+
             if !implements_interfaces.implements_interface(inherited.0.as_ref()) {
                 return Some(Diagnostic::missing_inherited_interface(
                     name.to_string(),
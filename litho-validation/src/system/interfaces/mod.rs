@@ -1,3 +1,5 @@
+mod implementation_cycles;
 mod implements_interface;
 
+pub use implementation_cycles::ImplementationCycles;
 pub use implements_interface::ImplementsInterface;
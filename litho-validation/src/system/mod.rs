@@ -10,10 +10,12 @@ mod arguments;
 mod directives;
 mod enums;
 mod extensions;
+mod federation;
 mod fields;
 mod inputs;
 mod interfaces;
 mod names;
+mod schema;
 mod types;
 mod unions;
 
@@ -30,14 +32,20 @@ where
         &mut errors,
     );
     document.traverse(&enums::EnumValues(database), &mut errors);
+    document.traverse(&extensions::ExtendedTypesExist(database), &mut errors);
     document.traverse(&extensions::SameTypeExtensions(database), &mut errors);
+    document.traverse(&federation::FieldSets(database), &mut errors);
     document.traverse(&fields::FieldNameUniqueness(database), &mut errors);
     document.traverse(&fields::FieldsAreOutputTypes(database), &mut errors);
     document.traverse(&fields::HasFields(database), &mut errors);
+    document.traverse(&inputs::OneOfInputFields(database), &mut errors);
     document.traverse(&inputs::SelfReferentialInputs(database), &mut errors);
+    document.traverse(&interfaces::ImplementationCycles(database), &mut errors);
     document.traverse(&interfaces::ImplementsInterface(database), &mut errors);
     document.traverse(&names::ReservedNames(database), &mut errors);
     document.traverse(&names::UniqueNames(database), &mut errors);
+    document.traverse(&schema::RootOperationTypeUniqueness(database), &mut errors);
+    document.traverse(&schema::RootOperationTypes(database), &mut errors);
     document.traverse(&types::NamedTypesExist(database), &mut errors);
     document.traverse(&unions::UnionMemberTypes(database), &mut errors);
     errors
@@ -0,0 +1,5 @@
+mod root_operation_type_uniqueness;
+mod root_operation_types;
+
+pub use root_operation_type_uniqueness::RootOperationTypeUniqueness;
+pub use root_operation_types::RootOperationTypes;
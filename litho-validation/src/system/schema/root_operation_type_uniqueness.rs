@@ -0,0 +1,42 @@
+use std::hash::Hash;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+pub struct RootOperationTypeUniqueness<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> Visit<'a, T> for RootOperationTypeUniqueness<'a, T>
+where
+    T: Eq + Hash,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_root_operation_type_definition(
+        &self,
+        node: &'a RootOperationTypeDefinition<T>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let operation = node.operation_type.default_type_name();
+
+        let Some(first) = self
+            .0
+            .root_operation_type_definitions()
+            .find(|definition| definition.operation_type.default_type_name() == operation)
+        else {
+            return;
+        };
+
+        if first.operation_type.span() == node.operation_type.span() {
+            return;
+        }
+
+        accumulator.push(Diagnostic::duplicate_root_operation_type(
+            operation.to_lowercase(),
+            first.operation_type.span(),
+            node.operation_type.span(),
+        ));
+    }
+}
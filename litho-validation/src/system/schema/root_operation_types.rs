@@ -0,0 +1,63 @@
+use std::hash::Hash;
+
+use litho_diagnostics::Diagnostic;
+use litho_language::ast::*;
+use litho_types::Database;
+
+pub struct RootOperationTypes<'a, T>(pub &'a Database<T>)
+where
+    T: Eq + Hash;
+
+impl<'a, T> Visit<'a, T> for RootOperationTypes<'a, T>
+where
+    T: Eq + Hash + ToString,
+{
+    type Accumulator = Vec<Diagnostic<Span>>;
+
+    fn visit_schema_definition(
+        &self,
+        node: &'a SchemaDefinition<T>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        if node
+            .type_definitions
+            .ok()
+            .and_then(|definitions| definitions.definitions.ok())
+            .is_none()
+        {
+            return;
+        }
+
+        if self
+            .0
+            .root_operation_type_definitions()
+            .any(|definition| matches!(definition.operation_type, OperationType::Query(_)))
+        {
+            return;
+        }
+
+        accumulator.push(Diagnostic::missing_query_root_operation_type(
+            node.schema.span(),
+        ));
+    }
+
+    fn visit_root_operation_type_definition(
+        &self,
+        node: &'a RootOperationTypeDefinition<T>,
+        accumulator: &mut Self::Accumulator,
+    ) {
+        let Some(ty) = node.named_type.ok() else {
+            return;
+        };
+
+        if !self.0.type_exists(ty.0.as_ref()) || self.0.is_object_type(ty.0.as_ref()) {
+            return;
+        }
+
+        accumulator.push(Diagnostic::non_object_root_operation_type(
+            node.operation_type.default_type_name().to_lowercase(),
+            ty.0.as_ref().to_string(),
+            ty.span(),
+        ));
+    }
+}
@@ -1,20 +1,33 @@
+use std::borrow::Borrow;
 use std::hash::Hash;
 
 use litho_diagnostics::Diagnostic;
 use litho_language::ast::*;
 use litho_types::Database;
 
+use crate::suggestions::suggestions;
+
 pub struct NamedTypesExist<'a, T>(pub &'a Database<T>)
 where
     T: Eq + Hash;
 
 impl<'a, T> Visit<'a, T> for NamedTypesExist<'a, T>
 where
-    T: Eq + Hash + ToString,
+    T: Eq + Hash + Borrow<str> + ToString,
 {
     type Accumulator = Vec<Diagnostic<Span>>;
 
     fn visit_named_type(&self, node: &'a NamedType<T>, accumulator: &mut Self::Accumulator) {
+        // Names of type extensions are checked separately (E0131).
+        if self
+            .0
+            .type_extensions_by_name(node.0.as_ref())
+            .filter_map(|extension| extension.named_type())
+            .any(|name| std::ptr::eq(name, node))
+        {
+            return;
+        }
+
         if self
             .0
             .type_definitions_by_name(node.0.as_ref())
@@ -23,6 +36,13 @@ where
         {
             accumulator.push(Diagnostic::unknown_named_type(
                 node.0.as_ref().to_string(),
+                suggestions(
+                    node.0.as_ref().borrow(),
+                    self.0
+                        .type_definitions()
+                        .flat_map(|definition| definition.name().ok())
+                        .map(|name| name.as_ref().borrow()),
+                ),
                 node.span(),
             ))
         }